@@ -1,8 +1,61 @@
+import { mkdirSync, readFileSync, realpathSync, rmSync, symlinkSync, writeFileSync } from 'node:fs'
+import { tmpdir } from 'node:os'
+import { join, sep } from 'node:path'
+import { fileURLToPath } from 'node:url'
+import { Worker } from 'node:worker_threads'
+
 import test from 'ava'
-import { ClipboardManager, getClipboardText, setClipboardText, clearClipboard } from '../index'
+import {
+  ClipboardListener,
+  ClipboardManager,
+  ClipboardPoller,
+  getClipboardText,
+  setClipboardText,
+  clearClipboard,
+  checkClipboardHealth,
+  watchOnce,
+} from '../index'
+import type { ClipboardData, WatchOptions } from '../index'
 
 // 测试数据
 const TEST_TEXT = 'Hello, World!'
+// 2×2 的两帧 GIF 动画，每帧 100 毫秒
+const ANIMATED_GIF = Buffer.from(
+  'R0lGODlhAgACAIAAAAAAAAAAACH/C05FVFNDQVBFMi4wAwEAAAAh+QQICgAAACwAAAAAAgACAID/AAAAAAACAoRRACH5BAgKAAAALAAAAAACAAIAgAAA/wAAAAIChFEAOw==',
+  'base64',
+)
+
+// 将剪贴板设置为指定尺寸的纯白图片
+function solidImage(manager: ClipboardManager, width: number, height: number) {
+  manager.setImageFromCanvas({ data: new Uint8ClampedArray(width * height * 4).fill(255), width, height })
+}
+
+// 只包含指定图片数据的测试模式快照，尺寸由读取接口从数据中解析
+function imageSnapshot(data: Buffer): ClipboardData {
+  return { availableFormats: [], image: { width: 0, height: 0, size: data.length, data } }
+}
+
+const delay = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms))
+
+// 轮询等待条件成立，超时返回 false
+async function waitUntil(condition: () => boolean, timeoutMs = 3000) {
+  const deadline = Date.now() + timeoutMs
+  while (!condition()) {
+    if (Date.now() > deadline) {
+      return false
+    }
+    await delay(20)
+  }
+  return true
+}
+
+// 开始监听并收集事件，等待监听线程就绪后返回
+async function startListener(listener: ClipboardListener, options?: WatchOptions) {
+  const events: ClipboardData[] = []
+  listener.watch((data) => events.push(data), options)
+  await delay(300)
+  return events
+}
 
 // ClipboardManager 基本测试
 test('ClipboardManager - 创建实例', (t) => {
@@ -18,6 +71,181 @@ test('ClipboardManager - 文本操作', (t) => {
   t.is(retrievedText, TEST_TEXT)
 })
 
+test('ClipboardManager - 设置代码片段', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setCode('if (a < b) {}', 'ts')
+  t.is(manager.getText(), 'if (a < b) {}')
+  t.true(manager.getHtml().includes('<pre><code class="language-ts">if (a &lt; b) {}</code></pre>'))
+})
+
+test('ClipboardManager - 设置带样式类的文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setStyledText('a < b', 'note')
+  t.is(manager.getText(), 'a < b')
+  t.true(manager.getHtml().includes('<span class="note">a &lt; b</span>'))
+  t.throws(() => manager.setStyledText('a', 'bad"class'))
+})
+
+test('ClipboardManager - 去重追加文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText('first')
+  t.true(manager.appendTextUnique('second'))
+  t.false(manager.appendTextUnique('second'))
+  t.true(manager.appendTextUnique('third', ', '))
+  t.is(manager.getText(), 'first\nsecond, third')
+})
+
+test('ClipboardManager - 获取 HTML 声明的编码', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setHtml('<meta charset="utf-8"><p>Hello</p>')
+  t.is(manager.getHtmlCharset(), 'UTF-8')
+  manager.setHtml('<p>Hello</p>')
+  t.is(manager.getHtmlCharset(), null)
+})
+
+test('ClipboardManager - 设置颜色', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setColor({ r: 255, g: 128, b: 0 }, { image: true })
+  t.is(manager.getText(), '#FF8000')
+  t.true(manager.getHtml().includes('<span style="background:#FF8000">'))
+  t.is(manager.getImageData().width, 1)
+  manager.setColor({ r: 0, g: 0, b: 0, a: 0.5 })
+  t.is(manager.getText(), 'rgba(0, 0, 0, 0.5)')
+  t.throws(() => manager.setColor({ r: 0, g: 0, b: 0 }, { text: false, html: false }))
+})
+
+test('ClipboardManager - 移除指定格式', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  manager.removeFormats(['html'])
+  t.is(manager.getText(), TEST_TEXT)
+  t.false(manager.hasFormat('html'))
+  t.throws(() => manager.removeFormats(['unknown']))
+})
+
+test('ClipboardManager - 获取各格式大小', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  const sizes = manager.getFormatSizes()
+  t.is(sizes.find((entry) => entry.format === 'text')?.size, Buffer.byteLength(TEST_TEXT))
+  t.true(sizes.some((entry) => entry.format === 'html'))
+})
+
+test('ClipboardManager - 获取各格式哈希', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  const before = manager.getFormatHashes()
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Changed</b>' })
+  const after = manager.getFormatHashes()
+  const hashOf = (hashes: typeof before, format: string) => hashes.find((entry) => entry.format === format)?.hash
+  t.is(hashOf(before, 'text'), hashOf(after, 'text'))
+  t.not(hashOf(before, 'html'), hashOf(after, 'html'))
+})
+
+test('ClipboardManager - 获取 shell 转义后的文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(`it's 100%`)
+  t.is(manager.getTextShellEscaped('bash'), `'it'\\''s 100%'`)
+  t.is(manager.getTextShellEscaped('powershell'), `'it''s 100%'`)
+  t.is(manager.getTextShellEscaped('cmd'), `"it's 100%%cd:~,%"`)
+  t.throws(() => manager.getTextShellEscaped('fish'))
+
+  // cmd 的结果面向交互式提示符
+  const cmdCases: Array<[string, string]> = [
+    ['%PATH%', '"%%cd:~,%PATH%%cd:~,%"'],
+    ['say "hi"', '"say ""hi"""'],
+    ['a & b | c', '"a & b | c"'],
+    ['wow!', '"wow!"'],
+  ]
+  for (const [text, escaped] of cmdCases) {
+    manager.setText(text)
+    t.is(manager.getTextShellEscaped('cmd'), escaped)
+  }
+  manager.setText('a\nb')
+  t.throws(() => manager.getTextShellEscaped('cmd'), { code: 'InvalidArg' })
+})
+
+test('ClipboardManager - 获取文本统计', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText('hello world\nsecond line\n')
+  t.deepEqual(manager.getTextStats(), {
+    charCount: 24,
+    lineCount: 2,
+    wordCount: 4,
+    isSingleLine: false,
+    isWhitespaceOnly: false,
+  })
+})
+
+test('ClipboardManager - 按指定顺序读取文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: 'plain', html: '<p>rich <b>html</b></p>' })
+  t.is(manager.getPreferredText(['html', 'text'])?.trim(), 'rich html')
+  t.is(manager.getPreferredText(['rtf', 'text']), 'plain')
+  t.throws(() => manager.getPreferredText(['image']))
+})
+
+test('ClipboardManager - 终端粘贴的换行处理', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setTextForTerminal('ls\nrm -rf build\r\n\n')
+  t.is(manager.getText(), 'ls\nrm -rf build')
+  manager.setTextWithExec('make')
+  t.is(manager.getText(), 'make\n')
+  manager.setTextWithExec('make\n')
+  t.is(manager.getText(), 'make\n')
+})
+
+test('ClipboardManager - RTF 转换为纯文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setRichText('{\\rtf1\\ansi{\\fonttbl{\\f0 Arial;}}\\f0 Hello \\b World\\b0\\par caf\\u233?}')
+  t.is(manager.getRtfAsText(), 'Hello World\ncafé')
+  manager.setText('plain')
+  t.throws(() => manager.getRtfAsText())
+})
+
+test('ClipboardManager - 获取去除空白的文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(`  ${TEST_TEXT}\n`)
+  t.is(manager.getTextTrimmed(), TEST_TEXT)
+
+  manager.setText(' \n\t')
+  t.is(manager.getTextTrimmed(), null)
+})
+
+test('ClipboardManager - 读取文本到预分配 Buffer', (t) => {
+  const manager = new ClipboardManager()
+  const buffer = Buffer.alloc(64)
+
+  manager.setText(TEST_TEXT)
+  const written = manager.getTextInto(buffer)
+  t.is(buffer.subarray(0, written).toString(), TEST_TEXT)
+  t.throws(() => manager.getTextInto(Buffer.alloc(4)))
+})
+
+test('ClipboardManager - 识别文本中的路径', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(`  ${process.cwd()}\n`)
+  t.is(manager.getTextAsPath(), process.cwd())
+
+  manager.setText(TEST_TEXT)
+  t.is(manager.getTextAsPath(), null)
+})
+
 test('ClipboardManager - 清空剪贴板', (t) => {
   const manager = new ClipboardManager()
 
@@ -26,6 +254,1076 @@ test('ClipboardManager - 清空剪贴板', (t) => {
   t.pass() // 清空操作不抛出错误即可
 })
 
+test('ClipboardManager - 读取格式数据前缀', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setBuffer('application/x-clipboard-test', Buffer.from(TEST_TEXT))
+  const head = manager.peekFormat('application/x-clipboard-test', 5)
+  t.is(head.toString(), 'Hello')
+})
+
+test('ClipboardManager - 剪贴板指纹', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  const first = manager.getClipboardFingerprint()
+  manager.setText(TEST_TEXT)
+  t.is(manager.getClipboardFingerprint(), first)
+
+  manager.setText(`${TEST_TEXT}!`)
+  t.not(manager.getClipboardFingerprint(), first)
+})
+
+test('ClipboardManager - 同时写入宽字符与 ANSI 文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setTextWide('你好，世界！')
+  t.is(manager.getText(), '你好，世界！')
+})
+
+test('ClipboardManager - 文本来源标识', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setTextWithSource(TEST_TEXT, 'my-app')
+  t.is(manager.getText(), TEST_TEXT)
+  t.is(manager.getSource(), 'my-app')
+  manager.setText('other')
+  t.is(manager.getSource(), null)
+})
+
+test('ClipboardManager - 分块读写自定义格式', (t) => {
+  const manager = new ClipboardManager()
+  const format = 'application/x-clipboard-chunks'
+
+  manager.setBufferFromChunks(format, [Buffer.from('Hello, '), Buffer.from('World!')])
+  const chunks = manager.getBufferChunks(format, 5)
+  t.deepEqual(
+    chunks.map((chunk) => chunk.length),
+    [5, 5, 3],
+  )
+  t.is(Buffer.concat(chunks).toString(), 'Hello, World!')
+  t.throws(() => manager.getBufferChunks(format, 0))
+})
+
+test('ClipboardManager - 测试模式读写内存快照', (t) => {
+  const manager = new ClipboardManager()
+  manager.setText(TEST_TEXT)
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: 'snapshot', files: ['/tmp/a.txt'] })
+  try {
+    t.is(manager.getText(), 'snapshot')
+    t.deepEqual(manager.getFiles(), ['/tmp/a.txt'])
+    t.false(manager.hasFormat('html'))
+
+    manager.setHtml('<b>test</b>')
+    t.is(manager.getHtml(), '<b>test</b>')
+    t.deepEqual(manager.getAvailableFormats(), ['html'])
+    t.throws(() => manager.getText())
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+
+  t.is(manager.getText(), TEST_TEXT)
+})
+
+test('ClipboardManager - 测试模式下的格式写入接口不访问系统剪贴板', (t) => {
+  const manager = new ClipboardManager()
+  manager.setText(TEST_TEXT)
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    manager.setTextWide('wide')
+    t.is(manager.getText(), 'wide')
+
+    manager.setWithPriority([
+      { format: 'html', value: '<b>first</b>' },
+      { format: 'text', value: 'first' },
+      { format: 'application/x-custom', value: 'dropped' },
+    ])
+    t.deepEqual(manager.getAvailableFormats(), ['text', 'html'])
+    t.is(manager.getHtml(), '<b>first</b>')
+
+    solidImage(manager, 2, 2)
+    const png = manager.getImageRaw()
+    manager.clear()
+    manager.setImageBytesNative(png)
+    t.deepEqual(manager.getAvailableFormats(), ['image'])
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+
+  t.is(manager.getText(), TEST_TEXT)
+})
+
+test.serial('ClipboardManager - 异步分格式读取完整数据', async (t) => {
+  const manager = new ClipboardManager()
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>parallel</b>' })
+
+  const data = await manager.getFullClipboardDataParallelAsync()
+  t.is(data.text, TEST_TEXT)
+  t.true(data.availableFormats.includes('html'))
+  t.is(data.image, undefined)
+})
+
+test.serial('ClipboardManager - 等待格式出现', async (t) => {
+  const manager = new ClipboardManager()
+  await t.throwsAsync(() => manager.waitForFormat('bogus'), { code: 'InvalidArg' })
+  await t.throwsAsync(() => manager.waitForFormat('text', -1), { code: 'InvalidArg' })
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: 'waiting' })
+  try {
+    t.is((await manager.waitForFormat('text', 1000)).text, 'waiting')
+    await t.throwsAsync(() => manager.waitForFormat('image', 50), { message: /Timed out/ })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test.serial('ClipboardManager - 测试模式下的异步接口', async (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: 'snapshot' })
+  try {
+    t.is(await manager.getTextAsync(), 'snapshot')
+    await manager.setTextAsync('async')
+    t.is(manager.getText(), 'async')
+    t.is(getClipboardText(), 'async')
+    t.is((await manager.getFullClipboardDataBudgetedAsync(1000)).text, 'async')
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 一次写入多张图片', (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.setImages([]), { code: 'InvalidArg' })
+
+  solidImage(manager, 2, 2)
+  const small = manager.getImageRaw()
+  solidImage(manager, 3, 1)
+  const wide = manager.getImageRaw()
+
+  t.is(manager.setImages([small, wide]), process.platform === 'darwin')
+  if (process.platform !== 'darwin') {
+    t.is(manager.getImageData().width, 2)
+  }
+
+  // 测试模式下各平台都只写入第一张图片
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    t.false(manager.setImages([wide, small]))
+    t.is(manager.getImageData().width, 3)
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 从 canvas 像素设置图片', (t) => {
+  const manager = new ClipboardManager()
+  const data = new Uint8ClampedArray(2 * 3 * 4).fill(255)
+
+  manager.setImageFromCanvas({ data, width: 2, height: 3 })
+  const image = manager.getImageData()
+  t.is(image.width, 2)
+  t.is(image.height, 3)
+  t.throws(() => manager.setImageFromCanvas({ data, width: 3, height: 3 }))
+})
+
+test('ClipboardManager - 限制图片最大边长', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 40, 20)
+  const png = manager.getImageRaw()
+
+  manager.setImageCapped(png, 10)
+  const image = manager.getImageData()
+  t.is(image.width, 10)
+  t.is(image.height, 5)
+  manager.setImageCapped(png, 100)
+  t.is(manager.getImageData().width, 40)
+})
+
+test('ClipboardManager - 一次解码编码为多种格式', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 4, 4)
+
+  const encodings = manager.getImageEncodings(['png', 'jpeg:80', 'webp'])
+  t.deepEqual(
+    encodings.map((entry) => entry.format),
+    ['png', 'jpeg:80', 'webp'],
+  )
+  t.is(encodings[1].data[0], 0xff)
+  t.is(encodings[2].data.subarray(8, 12).toString(), 'WEBP')
+  t.throws(() => manager.getImageEncodings(['gif']))
+})
+
+test('ClipboardManager - 图片切分为图块', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 5, 3)
+
+  const tiles = manager.getImageTiles(2, 2)
+  t.deepEqual(
+    tiles.map((tile) => [tile.width, tile.height]),
+    [
+      [2, 1],
+      [3, 1],
+      [2, 2],
+      [3, 2],
+    ],
+  )
+  t.throws(() => manager.getImageTiles(0, 2))
+  t.throws(() => manager.getImageTiles(4, 1))
+})
+
+test('ClipboardManager - 从文本生成二维码', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText('https://example.com')
+  manager.setQrFromText()
+  const image = manager.getImageData()
+  t.is(image.width, image.height)
+  t.throws(() => manager.setQrFromText('a'.repeat(5000)))
+})
+
+test('ClipboardManager - 提取 HTML 内嵌图片', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 3, 2)
+  const base64 = manager.getImageBase64()
+
+  manager.setHtml(`<p>photo</p><img src="data:image/png;base64,${base64}">`)
+  const image = manager.extractImageFromHtml()
+  t.is(image?.width, 3)
+  t.is(image?.height, 2)
+  // 图片前的非 ASCII 标签与属性不影响查找
+  manager.setHtml(`<p é>照片</p><img 中 src="data:image/png;base64,${base64}">`)
+  t.is(manager.extractImageFromHtml()?.width, 3)
+  manager.setHtml('<p>no image</p>')
+  t.is(manager.extractImageFromHtml(), null)
+})
+
+test('ClipboardManager - 异步设置图片', async (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 4, 4)
+  const png = manager.getImageRaw()
+
+  await manager.setImageRawAsync(png)
+  t.is(manager.getImageData().width, 4)
+  await manager.setImageBase64Async(png.toString('base64'))
+  t.is(manager.getImageData().height, 4)
+  await t.throwsAsync(() => manager.setImageBase64Async('not base64!'))
+})
+
+test('ClipboardManager - 限时读取完整数据', async (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  const data = await manager.getFullClipboardDataBudgetedAsync(5000)
+  t.false(data.partial)
+  t.is(data.text, TEST_TEXT)
+  await t.throwsAsync(() => manager.getFullClipboardDataBudgetedAsync(-1))
+})
+
+test('ClipboardManager - 确认写入的格式', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContentsExpecting({ availableFormats: [], text: TEST_TEXT, html: '<b>Hi</b>' }, ['text', 'html'])
+  t.is(manager.getText(), TEST_TEXT)
+  t.throws(() => manager.setContentsExpecting({ availableFormats: [], text: TEST_TEXT }, ['text', 'rtf']), {
+    message: /rtf/,
+  })
+})
+
+test('ClipboardManager - 附带元数据的文本', (t) => {
+  const manager = new ClipboardManager()
+  const metadata = JSON.stringify({ sourceId: 42, copiedAt: 1700000000000 })
+
+  manager.setWithMetadata(TEST_TEXT, metadata)
+  t.is(manager.getText(), TEST_TEXT)
+  t.is(manager.getMetadata(), metadata)
+  manager.setText('other')
+  t.is(manager.getMetadata(), null)
+})
+
+test('ClipboardManager - 同步标记', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContentsMarked({ availableFormats: [], text: TEST_TEXT }, 'device-a')
+  t.is(manager.getText(), TEST_TEXT)
+  t.is(manager.getMarker(), 'device-a')
+  manager.setText('other')
+  t.is(manager.getMarker(), null)
+})
+
+test('ClipboardManager - 检测文本语言', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText('The quick brown fox jumps over the lazy dog')
+  t.is(manager.getTextWithLanguage().language, 'en')
+  manager.setText('ok')
+  const short = manager.getTextWithLanguage()
+  t.is(short.text, 'ok')
+  t.falsy(short.language)
+})
+
+test('ClipboardManager - 内容被替换时回调', async (t) => {
+  const manager = new ClipboardManager()
+
+  const replaced = new Promise<void>((resolve) => manager.setTextUntilReplaced(TEST_TEXT, resolve))
+  new ClipboardManager().setText('other')
+  await replaced
+  t.is(manager.getText(), 'other')
+})
+
+test('ClipboardManager - 规范化文件路径', (t) => {
+  const manager = new ClipboardManager()
+  const existing = join(tmpdir(), 'canonical.txt')
+  writeFileSync(existing, '')
+
+  manager.setFiles([existing, join(tmpdir(), 'missing-canonical.txt')])
+  t.deepEqual(manager.getFilesCanonical(), [realpathSync(existing)])
+  t.throws(() => manager.getFilesCanonical(true))
+})
+
+test('ClipboardManager - 去除重复的文件', (t) => {
+  const manager = new ClipboardManager()
+  const existing = join(tmpdir(), 'dedup.txt')
+  const other = join(tmpdir(), 'dedup-other.txt')
+  writeFileSync(existing, '')
+  writeFileSync(other, '')
+  // 手动拼接路径，保留 join 会规范化掉的 `..`，让原始字符串与 existing 不同
+  const nested = join(tmpdir(), 'dedup-nested')
+  mkdirSync(nested, { recursive: true })
+  const aliased = `${nested}${sep}..${sep}dedup.txt`
+  t.not(aliased, existing)
+
+  manager.setFiles([existing, other, aliased])
+  t.deepEqual(manager.getFilesDeduplicated(), [existing, other])
+})
+
+test('ClipboardManager - 文件总大小', (t) => {
+  const manager = new ClipboardManager()
+  const folder = join(tmpdir(), 'total-size')
+  mkdirSync(join(folder, 'nested'), { recursive: true })
+  writeFileSync(join(folder, 'nested', 'a.txt'), 'hello')
+  const file = join(tmpdir(), 'total-size.txt')
+  writeFileSync(file, 'abc')
+
+  manager.setFiles([file, folder, join(tmpdir(), 'total-size-missing.txt')])
+  t.is(manager.getFilesTotalSize(), 3)
+  t.is(manager.getFilesTotalSize(true), 8)
+})
+
+test('ClipboardManager - 以 zip 归档传递文件', (t) => {
+  const manager = new ClipboardManager()
+  const folder = join(tmpdir(), 'archive-src')
+  mkdirSync(join(folder, 'nested'), { recursive: true })
+  writeFileSync(join(folder, 'nested', 'note.txt'), 'hello')
+
+  manager.setFilesAsArchive([folder])
+  const dest = join(tmpdir(), 'archive-dest')
+  t.deepEqual(manager.getFilesFromArchive(dest), [join(dest, 'archive-src')])
+  t.is(readFileSync(join(dest, 'archive-src', 'nested', 'note.txt'), 'utf8'), 'hello')
+  t.throws(() => manager.setFilesAsArchive([]))
+})
+
+test('ClipboardManager - 打包包含指向上级目录链接的目录', (t) => {
+  const manager = new ClipboardManager()
+  const folder = join(tmpdir(), 'archive-loop')
+  mkdirSync(join(folder, 'nested'), { recursive: true })
+  writeFileSync(join(folder, 'nested', 'note.txt'), 'hello')
+  const link = join(folder, 'nested', 'loop')
+  rmSync(link, { force: true })
+  symlinkSync(folder, link, 'junction')
+
+  manager.setFilesAsArchive([folder])
+  const dest = join(tmpdir(), 'archive-loop-dest')
+  manager.getFilesFromArchive(dest)
+  t.is(readFileSync(join(dest, 'archive-loop', 'nested', 'note.txt'), 'utf8'), 'hello')
+})
+
+test('ClipboardManager - 粘贴方式汇总', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(' https://example.com/page ')
+  const link = manager.getPasteCapabilities()
+  t.true(link.canPasteText)
+  t.true(link.canPasteAsLink)
+  t.false(link.canPasteImage)
+
+  manager.setContents({ availableFormats: [], text: 'Hello', html: '<b>Hello</b>' })
+  const rich = manager.getPasteCapabilities()
+  t.false(rich.canPasteAsLink)
+  t.true(rich.canPasteAsMarkdown)
+  t.true(rich.canPastePlain)
+})
+
+test('ClipboardManager - 按类别分组文件', (t) => {
+  const manager = new ClipboardManager()
+  const files = ['photo.PNG', 'notes.pdf', 'backup.zip', 'script.xyz', 'clip.png'].map((name) => join(tmpdir(), name))
+  files.forEach((file) => writeFileSync(file, ''))
+
+  manager.setFiles(files)
+  t.deepEqual(
+    manager.getFilesGrouped().map((group) => [group.category, group.paths.length]),
+    [
+      ['image', 2],
+      ['document', 1],
+      ['archive', 1],
+      ['other', 1],
+    ],
+  )
+})
+
+test('ClipboardManager - 从文件设置文本', (t) => {
+  const manager = new ClipboardManager()
+  const file = join(tmpdir(), 'clipboard-rs-text.txt')
+  writeFileSync(file, TEST_TEXT)
+
+  t.is(manager.setTextFromFile(file), TEST_TEXT.length)
+  t.is(manager.getText(), TEST_TEXT)
+  t.throws(() => manager.setTextFromFile(file, 4))
+  writeFileSync(file, Buffer.from([0xff, 0xfe, 0x00]))
+  t.throws(() => manager.setTextFromFile(file))
+  t.throws(() => manager.setTextFromFile(join(tmpdir(), 'clipboard-rs-missing.txt')))
+})
+
+test('ClipboardManager - 获取 UTF-8 编码的 HTML', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setHtml('<p>你好，世界！</p>')
+  t.true(manager.getHtmlUtf8().includes('<p>你好，世界！</p>'))
+})
+
+test('ClipboardManager - 获取清理后的 HTML', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setHtml('<p onclick="evil()">Hi <a href="javascript:alert(1)">link</a></p><script>alert(1)</script>')
+  const html = manager.getHtmlSanitized()
+  t.true(html.includes('<p>Hi <a>link</a></p>'))
+  t.false(html.includes('script'))
+})
+
+test('ClipboardManager - 检测写入的内容是否被覆盖', (t) => {
+  const manager = new ClipboardManager()
+
+  const token = manager.setTextTracked(TEST_TEXT)
+  t.true(manager.isStillCurrent(token))
+  manager.setText('other')
+  t.false(manager.isStillCurrent(token))
+})
+
+test('ClipboardManager - 检查文本与 HTML 是否一致', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<p>Hello, <b>World</b>!</p>' })
+  t.true(manager.checkTextHtmlConsistency())
+  manager.setContents({ availableFormats: [], text: 'stale', html: '<p>Hello, <b>World</b>!</p>' })
+  t.false(manager.checkTextHtmlConsistency())
+})
+
+test('ClipboardManager - 单次会话读取指定格式', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  const data = manager.withReadSession(['text', 'image'])
+  t.is(data.text, TEST_TEXT)
+  t.falsy(data.html)
+  t.throws(() => manager.withReadSession(['unknown']))
+})
+
+test('ClipboardManager - 等待格式出现', async (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  const data = await manager.waitForFormat('text', 1000)
+  t.is(data.text, TEST_TEXT)
+  await t.throwsAsync(() => manager.waitForFormat('unknown'))
+})
+
+test('ClipboardManager - 检查图片数据', (t) => {
+  t.false(ClipboardManager.isValidImage(Buffer.from(TEST_TEXT)))
+})
+
+test('ClipboardManager - 分块写入图片的大小上限', (t) => {
+  const manager = new ClipboardManager()
+
+  const writer = manager.setImageFromStream(8)
+  writer.write(Buffer.alloc(4))
+  t.is(writer.getBytesWritten(), 4)
+  t.throws(() => writer.write(Buffer.alloc(8)))
+  t.throws(() => writer.finish())
+})
+
+test('ClipboardManager - 导出并导入归档', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  const archive = manager.exportArchive()
+  manager.setText('other')
+
+  manager.importArchive(archive)
+  t.is(manager.getText(), TEST_TEXT)
+  t.true(manager.getHtml().includes('<b>Hello</b>'))
+  t.throws(() => manager.importArchive(Buffer.from('not an archive')))
+})
+
+test('ClipboardManager - 按相对路径设置文件', (t) => {
+  const manager = new ClipboardManager()
+  const base = join(tmpdir(), 'relative-base')
+  mkdirSync(join(base, 'nested'), { recursive: true })
+  writeFileSync(join(base, 'nested', 'a.txt'), 'a')
+  writeFileSync(join(tmpdir(), 'relative-outside.txt'), '')
+
+  manager.setFilesRelative(base, ['nested/a.txt'])
+  t.deepEqual(manager.getFiles(), [realpathSync(join(base, 'nested', 'a.txt'))])
+  t.throws(() => manager.setFilesRelative(base, ['../relative-outside.txt']), { code: 'InvalidArg' })
+  t.throws(() => manager.setFilesRelative(base, ['missing.txt']), { code: 'InvalidArg' })
+  t.throws(() => manager.setFilesRelative(join(base, 'missing'), ['a.txt']), { code: 'InvalidArg' })
+  t.throws(() => manager.setFilesRelative(join(base, 'nested', 'a.txt'), ['a.txt']), { code: 'InvalidArg' })
+})
+
+test.serial('ClipboardManager - 失败时重试设置文本', async (t) => {
+  const manager = new ClipboardManager()
+  await t.throwsAsync(() => manager.setTextWithRetry('retry', 3, -1), { code: 'InvalidArg' })
+  await t.throwsAsync(() => manager.setTextWithRetry('retry', 3, Number.NaN), { code: 'InvalidArg' })
+
+  await manager.setTextWithRetry('retry', 0, 10)
+  t.is(manager.getText(), 'retry')
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    await manager.setTextWithRetry('retry-snapshot', 3, 10)
+    t.is(manager.getText(), 'retry-snapshot')
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+  t.is(manager.getText(), 'retry')
+})
+
+test('ClipboardManager - 列出格式及大小', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  const sizes = manager.inspect()
+  t.is(sizes.find((entry) => entry.format === 'text')?.size, Buffer.byteLength(TEST_TEXT))
+  t.is(sizes.find((entry) => entry.format === 'html')?.size, Buffer.byteLength('<b>Hello</b>'))
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: 'snapshot', files: ['/tmp/a.txt'] })
+  try {
+    t.deepEqual(manager.inspect(), [
+      { format: 'text', size: 8 },
+      { format: 'files', size: Buffer.byteLength('/tmp/a.txt') },
+    ])
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 裁剪后设置图片', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 8, 6)
+  const png = manager.getImageRaw()
+
+  manager.setImageCropped(png, 2, 1, 5, 4)
+  const image = manager.getImageData()
+  t.is(image.width, 5)
+  t.is(image.height, 4)
+  t.throws(() => manager.setImageCropped(png, 4, 0, 5, 4), { code: 'InvalidArg' })
+  t.throws(() => manager.setImageCropped(png, 0, 0, 0, 4), { code: 'InvalidArg' })
+})
+
+test('ClipboardManager - 获取来源应用优先提供的格式', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  t.is(manager.getPrimaryFormat(), 'text')
+  manager.setBuffer('application/x-primary-format', Buffer.from('custom'))
+  t.is(manager.getPrimaryFormat(), 'application/x-primary-format')
+})
+
+test('ClipboardManager - 严格模式获取图片', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 3, 2)
+  const png = manager.getImageRaw()
+
+  ClipboardManager.setTestMode(imageSnapshot(png))
+  try {
+    t.is(manager.getImageStrict().width, 3)
+    ClipboardManager.setTestMode(imageSnapshot(ANIMATED_GIF))
+    t.throws(() => manager.getImageStrict(), { message: /animated image\/gif image with 2 frames/ })
+    ClipboardManager.setTestMode(imageSnapshot(Buffer.from('not an image')))
+    t.throws(() => manager.getImageStrict(), { message: /unsupported image format/ })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 以原始字节写入格式', (t) => {
+  const manager = new ClipboardManager()
+  const payload = Buffer.from([1, 2, 3, 0, 255])
+
+  manager.setRawFormat('application/x-raw-format', payload, false)
+  t.deepEqual(manager.getBuffer('application/x-raw-format'), payload)
+  if (process.platform === 'win32') {
+    t.throws(() => manager.setRawFormat('CF_BITMAP', payload, true), { message: /handle-based format/ })
+  } else {
+    // macOS 与 Linux 的格式名称本身即为原生标识
+    manager.setRawFormat('application/x-raw-native', payload, true)
+    t.deepEqual(manager.getBuffer('application/x-raw-native'), payload)
+  }
+})
+
+test('ClipboardManager - 获取图片尺寸信息', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 5, 3)
+
+  const info = manager.getImageInfo()
+  t.is(info.width, 5)
+  t.is(info.height, 3)
+  t.is(info.pngSize, manager.getImageRaw().length)
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: TEST_TEXT })
+  try {
+    t.throws(() => manager.getImageInfo())
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 以 Blob 结构获取图片', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode(imageSnapshot(ANIMATED_GIF))
+  try {
+    const blob = manager.getImageAsBlob()
+    t.is(blob.mimeType, 'image/gif')
+    t.regex(blob.suggestedName, /^clipboard-\d+\.gif$/)
+    t.deepEqual(blob.data, ANIMATED_GIF)
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 居中放置到透明画布', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 4, 2)
+  const png = manager.getImageRaw()
+
+  t.throws(() => manager.setImagePadded(png, 0, 8), { code: 'InvalidArg' })
+
+  // 测试模式以 PNG 保存图片，透明通道不受平台剪贴板格式影响
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    manager.setImagePadded(png, 8, 8)
+    const image = manager.getImageData()
+    t.is(image.width, 8)
+    t.is(image.height, 8)
+    // 4×2 的图片缩放为 8×4，上下各留出 2 行透明像素
+    t.is(manager.getImageTransparencyRatio(), 0.5)
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 解析 URI 列表', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setBuffer(
+    'text/uri-list',
+    Buffer.from('# copied\r\nfile:///tmp/my%20file.txt\r\nfile://remote-host/share/a.txt\r\nhttps://example.com/\r\n'),
+  )
+  t.deepEqual(manager.getUriList(), {
+    files: ['/tmp/my file.txt'],
+    urls: ['file://remote-host/share/a.txt', 'https://example.com/'],
+  })
+})
+
+test('ClipboardManager - 获取 X11 剪贴板所有者标题', (t) => {
+  const manager = new ClipboardManager()
+  manager.setText(TEST_TEXT)
+
+  const title = manager.getX11OwnerTitle()
+  if (process.platform === 'linux') {
+    t.true(title === null || typeof title === 'string')
+  } else {
+    t.is(title, null)
+  }
+})
+
+test.serial('ClipboardManager - 固定文本', async (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.pinText('pinned', -1), { code: 'InvalidArg' })
+  t.throws(() => manager.pinText('pinned', Number.POSITIVE_INFINITY), { code: 'InvalidArg' })
+
+  manager.pinText('pinned', 10000)
+  try {
+    t.is(manager.getText(), 'pinned')
+    new ClipboardManager().setText('intruder')
+    t.true(await waitUntil(() => manager.getText() === 'pinned'))
+  } finally {
+    manager.unpin()
+  }
+
+  // unpin 之后不再恢复，重复调用没有副作用
+  manager.unpin()
+  await delay(300)
+  new ClipboardManager().setText('after-unpin')
+  await delay(500)
+  t.is(manager.getText(), 'after-unpin')
+})
+
+test('ClipboardManager - 获取图片主色调', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: TEST_TEXT })
+  try {
+    t.throws(() => manager.getImageDominantColor())
+    const data = new Uint8ClampedArray(4 * 4 * 4).map((_, index) => [200, 30, 60, 255][index % 4])
+    manager.setImageFromCanvas({ data, width: 4, height: 4 })
+    t.deepEqual(manager.getImageDominantColor(), { r: 200, g: 30, b: 60 })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 获取指定大小以内的 JPEG 图片', (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.getImageUnderSize(0), { code: 'InvalidArg' })
+  t.throws(() => manager.getImageUnderSize(Number.NaN), { code: 'InvalidArg' })
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    solidImage(manager, 64, 64)
+    const jpeg = manager.getImageUnderSize(4096)
+    t.true(jpeg.length <= 4096)
+    t.deepEqual([...jpeg.subarray(0, 2)], [0xff, 0xd8])
+    t.throws(() => manager.getImageUnderSize(10))
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 获取图片透明像素比例', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode({ availableFormats: [], text: TEST_TEXT })
+  try {
+    t.throws(() => manager.getImageTransparencyRatio())
+    // 4 个像素中 1 个完全透明，1 个半透明
+    const data = Uint8ClampedArray.from([0, 0, 0, 0, 0, 0, 0, 128, 0, 0, 0, 255, 0, 0, 0, 255])
+    manager.setImageFromCanvas({ data, width: 2, height: 2 })
+    t.is(manager.getImageTransparencyRatio(), 0.25)
+    solidImage(manager, 2, 2)
+    t.is(manager.getImageTransparencyRatio(), 0)
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 获取图片动画信息', (t) => {
+  const manager = new ClipboardManager()
+  solidImage(manager, 2, 2)
+  const png = manager.getImageRaw()
+
+  ClipboardManager.setTestMode(imageSnapshot(ANIMATED_GIF))
+  try {
+    t.deepEqual(manager.getImageAnimationInfo(), { frameCount: 2, totalDurationMs: 200, isAnimated: true })
+    ClipboardManager.setTestMode(imageSnapshot(png))
+    t.deepEqual(manager.getImageAnimationInfo(), { frameCount: 1, totalDurationMs: 0, isAnimated: false })
+    ClipboardManager.setTestMode({ availableFormats: [], text: TEST_TEXT })
+    t.throws(() => manager.getImageAnimationInfo())
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 获取原始格式的图片', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode(imageSnapshot(ANIMATED_GIF))
+  try {
+    t.deepEqual(manager.getImageNative(), {
+      mimeType: 'image/gif',
+      data: ANIMATED_GIF,
+      width: 2,
+      height: 2,
+      wasTranscoded: false,
+    })
+    ClipboardManager.setTestMode(imageSnapshot(Buffer.from('not an image')))
+    t.throws(() => manager.getImageNative())
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test.serial('ClipboardManager - 多个 worker 线程并发读写', async (t) => {
+  const indexPath = fileURLToPath(new URL('../index.js', import.meta.url))
+  // 每个 worker 创建自己的实例，连续写入成对的文本与 HTML
+  const spawn = (id: number) =>
+    new Promise<number>((resolve, reject) => {
+      const worker = new Worker(
+        `const { ClipboardManager } = require(${JSON.stringify(indexPath)})
+const manager = new ClipboardManager()
+for (let i = 0; i < 50; i++) {
+  const text = 'worker-${id}-' + i
+  manager.setContents({ availableFormats: [], text, html: '<b>' + text + '</b>' })
+}`,
+        { eval: true },
+      )
+      worker.on('error', reject)
+      worker.on('exit', resolve)
+    })
+
+  const manager = new ClipboardManager()
+  let running = true
+  const workers = Promise.all([spawn(1), spawn(2)]).finally(() => {
+    running = false
+  })
+  while (running) {
+    // 同一次加锁中读取的文本与 HTML 总是来自同一次写入
+    const data = manager.withReadSession(['text', 'html'])
+    if (data.text?.startsWith('worker-') && data.html !== undefined) {
+      t.true(data.html.includes(`<b>${data.text}</b>`))
+    }
+    await delay(1)
+  }
+  t.deepEqual(await workers, [0, 0])
+})
+
+test('ClipboardManager - 比较图片相似度', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    solidImage(manager, 32, 32)
+    const png = manager.getImageRaw()
+    t.throws(() => manager.compareImageTo(Buffer.from('not an image')))
+    t.is(manager.compareImageTo(png), 1)
+    solidImage(manager, 64, 64)
+    t.true(manager.compareImageTo(png) > 0.9)
+    manager.setText(TEST_TEXT)
+    t.throws(() => manager.compareImageTo(png))
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 按优先顺序写入多种格式', (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.setWithPriority([{ format: 'image', value: 'not a buffer' }]), { code: 'InvalidArg' })
+  t.throws(() => manager.setWithPriority([{ format: 'application/x-custom' }]), { code: 'InvalidArg' })
+  const duplicated = [
+    { format: 'text', value: 'a' },
+    { format: 'text', value: 'b' },
+  ]
+  t.throws(() => manager.setWithPriority(duplicated), { code: 'InvalidArg' })
+
+  manager.setWithPriority([
+    { format: 'html', value: '<b>priority</b>' },
+    { format: 'text', value: 'priority' },
+    { format: 'application/x-priority', data: Buffer.from([1, 2, 3]) },
+  ])
+  t.is(manager.getText(), 'priority')
+  t.true(manager.getHtml().includes('<b>priority</b>'))
+  t.deepEqual(manager.getBuffer('application/x-priority'), Buffer.from([1, 2, 3]))
+})
+
+test('ClipboardManager - 解码 HEIC 图片', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode(imageSnapshot(ANIMATED_GIF))
+  try {
+    // 非 HEIC 图片按常规方式解码为 PNG
+    const image = manager.getImageDecodingHeic()
+    t.is(image.width, 2)
+    t.deepEqual([...image.data.subarray(0, 4)], [0x89, 0x50, 0x4e, 0x47])
+    // 只有文件头的 HEIC 数据无法解码，未启用 heic 特性时同样返回错误
+    const header = Buffer.concat([Buffer.from([0, 0, 0, 24]), Buffer.from('ftypheic'), Buffer.alloc(12)])
+    ClipboardManager.setTestMode(imageSnapshot(header))
+    t.throws(() => manager.getImageDecodingHeic(), { message: /Failed to decode HEIC image/ })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 写入按应用限定的文本', (t) => {
+  const manager = new ClipboardManager()
+
+  t.false(manager.setTextScoped('scoped-default'))
+  t.is(manager.getText(), 'scoped-default')
+  t.false(manager.setTextScoped('scoped-blank', ' '))
+  t.is(manager.getText(), 'scoped-blank')
+
+  // macOS 写入命名剪贴板，通用剪贴板保持不变；其他平台与 setText 相同
+  const isMac = process.platform === 'darwin'
+  t.is(manager.setTextScoped('scoped-app', 'com.example.clipboard-test'), isMac)
+  t.is(manager.getText(), isMac ? 'scoped-blank' : 'scoped-app')
+})
+
+test('ClipboardManager - 读取各原生格式的原始字节', (t) => {
+  const manager = new ClipboardManager()
+  const payload = Buffer.from([0, 1, 2, 254, 255])
+
+  manager.setBuffer('application/x-raw-contents', payload)
+  const raw = manager.getRawContents()
+  const custom = raw.find((entry) => entry.platformName === 'application/x-raw-contents')
+  t.is(custom?.format, 'application/x-raw-contents')
+  t.deepEqual(custom?.data, payload)
+
+  manager.setText(TEST_TEXT)
+  const text = manager.getRawContents().find((entry) => entry.format === 'text')
+  if (!text) {
+    t.fail('text format is missing from raw contents')
+    return
+  }
+  // platformName 可以原样写回
+  manager.clear()
+  manager.setRawFormat(text.platformName, text.data, true)
+  t.true(manager.getText().startsWith(TEST_TEXT))
+})
+
+test('ClipboardManager - 按原始格式写入图片字节', (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.setImageBytesNative(Buffer.from('not an image')), { code: 'InvalidArg' })
+
+  solidImage(manager, 3, 2)
+  const png = manager.getImageRaw()
+  manager.clear()
+  manager.setImageBytesNative(png)
+  const image = manager.getImageNative()
+  t.is(image.mimeType, 'image/png')
+  t.deepEqual(image.data, png)
+  t.false(image.wasTranscoded)
+})
+
+test('ClipboardManager - 合成到纯色背景后设置图片', (t) => {
+  const manager = new ClipboardManager()
+  t.throws(() => manager.setImageFlattened(Buffer.from('not an image'), { r: 0, g: 0, b: 0 }))
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    manager.setImageFromCanvas({ data: new Uint8ClampedArray(4 * 4 * 4), width: 4, height: 4 })
+    const transparent = manager.getImageRaw()
+    t.is(manager.getImageTransparencyRatio(), 1)
+
+    manager.setImageFlattened(transparent, { r: 255, g: 0, b: 0 })
+    t.is(manager.getImageTransparencyRatio(), 0)
+    t.deepEqual(manager.getImageDominantColor(), { r: 255, g: 0, b: 0 })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 按 EXIF 方向获取图片', (t) => {
+  const manager = new ClipboardManager()
+
+  ClipboardManager.setTestMode({ availableFormats: [] })
+  try {
+    solidImage(manager, 4, 2)
+    const [{ data: jpeg }] = manager.getImageEncodings(['jpeg:90'])
+    // 在 SOI 之后插入 EXIF 段：大端 TIFF 头，IFD 中只有 Orientation（0x0112）= 6，即顺时针旋转 90°
+    const tiff = Buffer.from('4d4d002a00000008' + '0001' + '011200030000000100060000' + '00000000', 'hex')
+    const exif = Buffer.concat([Buffer.from('Exif\0\0', 'latin1'), tiff])
+    const marker = Buffer.from([0xff, 0xe1, 0, 0])
+    marker.writeUInt16BE(exif.length + 2, 2)
+    ClipboardManager.setTestMode(imageSnapshot(Buffer.concat([jpeg.subarray(0, 2), marker, exif, jpeg.subarray(2)])))
+
+    t.like(manager.getImageData(), { width: 4, height: 2 })
+    t.like(manager.getImageOriented(), { width: 2, height: 4 })
+  } finally {
+    ClipboardManager.setTestMode(null)
+  }
+})
+
+test('ClipboardManager - 列出无法读取的格式', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setContents({ availableFormats: [], text: TEST_TEXT, html: '<b>Hello</b>' })
+  t.deepEqual(manager.getUnsupportedFormats(), [])
+  t.false(manager.hasUnsupportedContent())
+  manager.setBuffer('application/x-supported-custom', Buffer.from('custom'))
+  t.false(manager.getUnsupportedFormats().includes('application/x-supported-custom'))
+})
+
+test('ClipboardTransaction - 批量读写', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setText(TEST_TEXT)
+  const tx = manager.begin()
+  t.is(tx.getText(), TEST_TEXT)
+  tx.setText('transaction')
+  tx.setHtml('<b>transaction</b>')
+  t.is(manager.getText(), TEST_TEXT)
+
+  tx.commit()
+  t.is(manager.getText(), 'transaction')
+  t.throws(() => tx.getText())
+  t.throws(() => tx.commit())
+
+  // 未暂存的标准格式按快照保留
+  const keep = manager.begin()
+  keep.setText('kept html')
+  keep.commit()
+  t.is(manager.getHtml(), '<b>transaction</b>')
+
+  const aborted = manager.begin()
+  aborted.setText('aborted')
+  aborted.abort()
+  aborted.abort()
+  t.is(manager.getText(), 'kept html')
+  t.throws(() => aborted.commit())
+})
+
+test('ClipboardManager - 保存并读取快照文件', (t) => {
+  const file = join(tmpdir(), 'clipboard-rs-snapshot.bin')
+
+  ClipboardManager.saveSnapshotToFile({ availableFormats: [], text: TEST_TEXT, files: ['/tmp/a.txt'] }, file)
+  const data = ClipboardManager.loadSnapshotFromFile(file)
+  t.is(data.text, TEST_TEXT)
+  t.deepEqual(data.files, ['/tmp/a.txt'])
+  t.deepEqual(data.availableFormats, ['text', 'files'])
+  writeFileSync(file, TEST_TEXT)
+  t.throws(() => ClipboardManager.loadSnapshotFromFile(file))
+})
+
+test('ClipboardPoller - 轮询检测变化', (t) => {
+  const manager = new ClipboardManager()
+  const poller = new ClipboardPoller()
+
+  manager.setText(`${TEST_TEXT} (poll)`)
+  const data = poller.pollChanges()
+  t.is(data?.text, `${TEST_TEXT} (poll)`)
+  t.is(poller.pollChanges(), null)
+})
+
+test('ClipboardPoller - 重复写入相同内容', (t) => {
+  const manager = new ClipboardManager()
+  const text = `${TEST_TEXT} (repeat)`
+  manager.setText(text)
+  const poller = new ClipboardPoller()
+
+  manager.setText(text)
+  const data = poller.pollChanges()
+  // Windows 与 macOS 使用系统序列号，任何写入都视为变化；其他平台比较内容指纹
+  if (process.platform === 'win32' || process.platform === 'darwin') {
+    t.is(data?.text, text)
+  } else {
+    t.is(data, null)
+  }
+  t.is(poller.pollChanges(), null)
+})
+
+test('ClipboardManager - 多语言文本', (t) => {
+  const manager = new ClipboardManager()
+
+  manager.setTextLocalized([
+    { locale: 'en-US', text: TEST_TEXT },
+    { locale: 'zh-CN', text: '你好，世界！' },
+  ])
+  t.is(manager.getText(), TEST_TEXT)
+  t.is(manager.getTextLocalized('zh-CN'), '你好，世界！')
+  t.is(manager.getTextLocalized('fr-FR'), TEST_TEXT)
+})
+
 // 静态函数测试
 test('静态函数 - 文本操作', (t) => {
   setClipboardText(TEST_TEXT)
@@ -38,3 +1336,305 @@ test('静态函数 - 清空剪贴板', (t) => {
   clearClipboard()
   t.pass() // 清空操作不抛出错误即可
 })
+
+test('静态函数 - 剪贴板健康检查', (t) => {
+  const health = checkClipboardHealth()
+  t.is(typeof health.available, 'boolean')
+  t.truthy(health.backend)
+})
+
+test('watchOnce - 取消后不再触发回调', async (t) => {
+  t.throws(() => watchOnce(() => {}, -1), { code: 'InvalidArg' })
+
+  let calls = 0
+  const handle = watchOnce(() => {
+    calls += 1
+  })
+  handle.stop()
+  handle.stop()
+  setClipboardText(`watch-once-${Date.now()}`)
+  await new Promise((resolve) => setTimeout(resolve, 300))
+  t.is(calls, 0)
+})
+
+test.serial('ClipboardListener - 事件附带变化前的内容', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener)
+  try {
+    manager.setText('previous-first')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'previous-first')))
+    manager.setText('previous-second')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'previous-second')))
+    t.is(events.find((event) => event.text === 'previous-second')?.previousText, 'previous-first')
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 按 imageMode 编码事件中的图片', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  t.throws(() => listener.watch(() => {}, { imageMode: 'gif' }), { code: 'InvalidArg' })
+  t.throws(() => listener.watch(() => {}, { imageMode: 'jpeg:0' }), { code: 'InvalidArg' })
+  t.false(listener.isWatching())
+
+  const events = await startListener(listener, { imageMode: 'jpeg:80' })
+  try {
+    solidImage(manager, 4, 4)
+    t.true(await waitUntil(() => events.some((event) => event.image !== undefined)))
+    const image = events.find((event) => event.image !== undefined)?.image
+    t.deepEqual([...(image?.data.subarray(0, 2) ?? [])], [0xff, 0xd8])
+    t.is(image?.width, 4)
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 检测截图', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener, { detectScreenshots: true })
+  try {
+    solidImage(manager, 32, 24)
+    t.true(await waitUntil(() => events.some((event) => event.image?.width === 32)))
+    t.true(events.find((event) => event.image?.width === 32)?.isLikelyScreenshot)
+    manager.setText('not-a-screenshot')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'not-a-screenshot')))
+    t.false(events.find((event) => event.text === 'not-a-screenshot')?.isLikelyScreenshot)
+  } finally {
+    listener.stop()
+  }
+
+  const plain = await startListener(listener)
+  try {
+    manager.setText('screenshot-detection-off')
+    t.true(await waitUntil(() => plain.some((event) => event.text === 'screenshot-detection-off')))
+    t.is(plain.find((event) => event.text === 'screenshot-detection-off')?.isLikelyScreenshot, undefined)
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 节流合并连续变化', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener, { throttleMs: 500 })
+  try {
+    for (const index of [1, 2, 3]) {
+      manager.setText(`throttle-${index}`)
+      await delay(50)
+    }
+    // 尾部回调送达最新内容，中间的变化被合并
+    t.true(await waitUntil(() => events.some((event) => event.text === 'throttle-3')))
+    t.true(events.filter((event) => event.text?.startsWith('throttle-')).length <= 2)
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 保留最近事件', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener, { historySize: 2 })
+  try {
+    for (const index of [1, 2, 3]) {
+      manager.setText(`history-${index}`)
+      t.true(await waitUntil(() => events.some((event) => event.text === `history-${index}`)))
+    }
+  } finally {
+    listener.stop()
+  }
+
+  // 停止监听后仍可读取，超出 historySize 的最旧事件被丢弃
+  t.deepEqual(listener.getHistory().map((event) => event.text), ['history-2', 'history-3'])
+})
+
+test.serial('ClipboardListener - 回调繁忙时的处理方式', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  t.throws(() => listener.watch(() => {}, { backpressure: 'block' }), { code: 'InvalidArg' })
+
+  const texts: Array<string | undefined> = []
+  listener.watch(
+    (data) => {
+      // 同步占用 JS 线程，模拟处理缓慢的回调
+      Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, 100)
+      texts.push(data.text)
+    },
+    { backpressure: 'queue' },
+  )
+  try {
+    await delay(300)
+    for (const index of [1, 2, 3]) {
+      manager.setText(`queued-${index}`)
+      await delay(150)
+    }
+    // queue 模式逐个处理所有事件，不丢弃
+    t.true(await waitUntil(() => texts.includes('queued-3')))
+    t.deepEqual(texts.filter((text) => text?.startsWith('queued-')), ['queued-1', 'queued-2', 'queued-3'])
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 监听指定选区', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  t.throws(() => listener.watch(() => {}, { selections: ['secondary'] }), { code: 'InvalidArg' })
+
+  const events = await startListener(listener, { selections: ['clipboard'] })
+  try {
+    manager.setText('selection-clipboard')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'selection-clipboard')))
+    t.is(events.find((event) => event.text === 'selection-clipboard')?.selection, 'clipboard')
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 过滤函数替换或丢弃事件', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events: ClipboardData[] = []
+  listener.watch(
+    (data) => events.push(data),
+    null,
+    (data) => (data.text === 'filter-drop' ? null : { ...data, text: data.text?.toUpperCase() }),
+  )
+  try {
+    await delay(300)
+    manager.setText('filter-drop')
+    await delay(300)
+    manager.setText('filter-keep')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'FILTER-KEEP')))
+    t.false(events.some((event) => event.text?.toLowerCase() === 'filter-drop'))
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 只监听图片', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  t.throws(() => listener.watchImages(0, () => {}), { code: 'InvalidArg' })
+
+  const thumbnails: Array<{ width: number; height: number }> = []
+  listener.watchImages(16, (image) => thumbnails.push(image))
+  try {
+    await delay(300)
+    manager.setText('watch-images-text')
+    await delay(300)
+    t.is(thumbnails.length, 0)
+    solidImage(manager, 64, 32)
+    t.true(await waitUntil(() => thumbnails.length > 0))
+    t.like(thumbnails[0], { width: 16, height: 8 })
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 事件来源', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener)
+  try {
+    manager.setText('origin-check')
+    t.true(await waitUntil(() => events.some((event) => event.text === 'origin-check')))
+    const origin = events.find((event) => event.text === 'origin-check')?.origin
+    t.true(origin === 'user' || origin === 'programmatic' || origin === 'unknown')
+  } finally {
+    listener.stop()
+  }
+})
+
+test.serial('ClipboardListener - 停用与启用回调', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const events = await startListener(listener, { startDisarmed: true })
+  const texts = () => events.map((event) => event.text)
+  try {
+    manager.setText('disarmed-initial')
+    await delay(300)
+    t.false(texts().includes('disarmed-initial'))
+
+    listener.arm()
+    manager.setText('armed')
+    t.true(await waitUntil(() => texts().includes('armed')))
+
+    listener.disarm()
+    t.true(listener.isWatching())
+    manager.setText('disarmed-again')
+    await delay(300)
+    t.false(texts().includes('disarmed-again'))
+
+    listener.arm()
+    manager.setText('rearmed')
+    t.true(await waitUntil(() => texts().includes('rearmed')))
+  } finally {
+    listener.stop()
+  }
+})
+
+test('ClipboardListener - 获取生效的监听配置', (t) => {
+  const listener = new ClipboardListener()
+  const defaults = listener.getConfig()
+  t.like(defaults, {
+    isWayland: listener.getListenerType() === 'wayland',
+    isWatching: false,
+    imageMode: 'png',
+    detectScreenshots: false,
+    historySize: 0,
+    backpressure: 'drop',
+    selections: ['clipboard'],
+    paused: false,
+  })
+  t.is(defaults.throttleMs, undefined)
+  t.is(defaults.autoSaveImagesDir, undefined)
+
+  listener.watch(() => {}, {
+    imageMode: 'JPEG:80',
+    throttleMs: 250,
+    historySize: 5,
+    backpressure: 'Queue',
+    selections: ['primary', 'clipboard'],
+    startDisarmed: true,
+  })
+  try {
+    t.like(listener.getConfig(), {
+      isWatching: true,
+      imageMode: 'jpeg:80',
+      throttleMs: 250,
+      historySize: 5,
+      backpressure: 'queue',
+      // primary 只在 Linux 上生效
+      selections: process.platform === 'linux' ? ['clipboard', 'primary'] : ['clipboard'],
+      paused: true,
+    })
+    // 选项无效时保留上一次的配置
+    t.throws(() => listener.watch(() => {}, { throttleMs: 0, backpressure: 'bogus' }), { code: 'InvalidArg' })
+    t.is(listener.getConfig().backpressure, 'queue')
+  } finally {
+    listener.stop()
+  }
+  t.false(listener.getConfig().isWatching)
+})
+
+test.serial('ClipboardListener - 自动保存事件中的图片', async (t) => {
+  const manager = new ClipboardManager()
+  const listener = new ClipboardListener()
+  const dir = join(tmpdir(), 'auto-save-images', String(Date.now()))
+  const events = await startListener(listener, { autoSaveImagesDir: dir, imageMode: 'jpeg:80' })
+  try {
+    t.is(listener.getConfig().autoSaveImagesDir, dir)
+    solidImage(manager, 4, 4)
+    t.true(await waitUntil(() => events.some((event) => event.savedImagePath !== undefined)))
+    // 扩展名与 imageMode 对应的编码一致，而不是固定为 png
+    const saved = events.find((event) => event.savedImagePath !== undefined)?.savedImagePath ?? ''
+    t.true(saved.startsWith(dir))
+    t.regex(saved, /\d+(-\d+)?\.jpg$/)
+    t.deepEqual([...readFileSync(saved).subarray(0, 2)], [0xff, 0xd8])
+  } finally {
+    listener.stop()
+  }
+})
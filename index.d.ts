@@ -30,18 +30,47 @@ export declare class ClipboardListener {
   /**
    * 开始监听剪贴板变化
    * callback: 当剪贴板变化时调用的回调函数，参数为包含所有格式数据的复杂对象
+   * options: 可选的监听选项，例如 `{ imageMode: 'jpeg:80' }`
+   * filter: 可选的同步过滤函数，在 callback 之前调用；返回 null 丢弃事件，返回对象时以其替换事件数据。
+   * 过滤函数在 JS 线程上执行，监听线程最多等待 1 秒，超时或抛出异常的事件同样被丢弃
    * 自动根据当前环境选择合适的监听方式（Wayland 或通用）
    */
-  watch(callback: (arg: ClipboardData) => void): void
+  watch(
+    callback: (arg: ClipboardData) => void,
+    options?: WatchOptions | undefined | null,
+    filter?: ((arg: ClipboardData) => ClipboardData | null) | undefined | null,
+  ): void
+  /**
+   * 只监听剪贴板图片，图片出现或变化时回调不超过 maxDimension 的 PNG 缩略图
+   * 非图片的变化不读取任何内容也不触发回调；与上一张相同的图片不会重复回调，中间出现非图片内容后再次复制会重新回调
+   * 与 watch 共用监听状态，调用时会停止正在进行的监听
+   */
+  watchImages(maxDimension: number, callback: (arg: ImageData) => void): void
   /** 停止监听剪贴板变化 */
   stop(): void
+  /** 启用回调：监听线程保持运行，之后的剪贴板变化会送达回调 */
+  arm(): void
+  /** 停用回调：监听线程保持运行但丢弃所有事件，调用 arm 后恢复；与 stop 不同，不会停止监听线程 */
+  disarm(): void
+  /**
+   * 获取监听器保留的最近事件（需开启 historySize 选项），按时间从旧到新排列
+   * 停止监听后仍可读取
+   */
+  getHistory(): Array<ClipboardData>
   /** 检查是否正在监听 */
   isWatching(): boolean
   /** 获取当前使用的监听器类型 */
   getListenerType(): string
+  /** 获取监听器当前生效的配置：最近一次 watch 传入的选项经解析后的值，未指定的选项为默认值 */
+  getConfig(): ListenerConfig
 }
 
-/** 剪贴板管理器，提供跨平台的剪贴板操作功能 */
+/**
+ * 剪贴板管理器，提供跨平台的剪贴板操作功能
+ * 同一进程内的所有实例（包括各 worker 线程中创建的实例）、快速操作函数以及监听器读取剪贴板时
+ * 通过进程级锁串行访问剪贴板（Wayland 环境同样如此），一次读取多个格式的操作在同一次加锁中完成，
+ * 不会被本进程其他线程的写入打断；锁不能阻止其他进程修改剪贴板
+ */
 export declare class ClipboardManager {
   /** 创建新的剪贴板管理器实例 */
   constructor()
@@ -49,54 +78,588 @@ export declare class ClipboardManager {
   getText(): string
   /** 设置剪贴板中的纯文本内容 */
   setText(text: string): void
+  /**
+   * 设置纯文本内容，Windows 下同时显式写入 CF_UNICODETEXT 与 CF_TEXT，兼容只读取其中一种格式的旧程序
+   * 其他平台与 setText 相同
+   */
+  setTextWide(text: string): void
+  /** 去除末尾的所有换行后设置文本，粘贴到终端时最后一行命令不会被自动执行 */
+  setTextForTerminal(text: string): void
+  /** 设置文本并确保以换行结尾，粘贴到终端时最后一行命令会被立即执行；已以换行结尾的文本保持不变 */
+  setTextWithExec(text: string): void
+  /**
+   * 读取 UTF-8 文本文件并设置为剪贴板文本，返回文件的字节数
+   * 文件读取失败返回 GenericFailure 错误，超过 maxBytes 或不是有效的 UTF-8 时返回 InvalidArg 错误
+   */
+  setTextFromFile(path: string, maxBytes?: number | undefined | null): number
+  /**
+   * 设置文本并在指定时长内保持不变：后台线程检测到剪贴板被修改时重新写入该文本
+   * 这是尽力而为的保持，并非系统级锁定，其他应用仍可短暂修改剪贴板
+   * 再次调用会替换之前的固定，调用 unpin 或管理器被回收时提前结束
+   */
+  pinText(text: string, durationMs: number): void
+  /** 提前结束 pinText 的固定，没有固定时无副作用 */
+  unpin(): void
+  /** 设置纯文本内容，并返回记录写入后剪贴板状态的标记，配合 isStillCurrent 使用 */
+  setTextTracked(text: string): ClipboardToken
+  /**
+   * 判断剪贴板是否仍是 setTextTracked 写入的内容
+   * Windows 与 macOS 下比较序列号，任何写入都视为变化；其他平台比较文本指纹，再次复制相同文本仍视为未变化
+   */
+  isStillCurrent(token: ClipboardToken): boolean
+  /**
+   * 设置纯文本，并在剪贴板内容被其他内容替换后调用一次 onReplaced 回调
+   * 后台线程按固定间隔检查剪贴板，检测到替换或管理器被回收后结束；
+   * 再次调用会取消之前的检测，且不触发之前的回调
+   */
+  setTextUntilReplaced(text: string, onReplaced?: (() => void) | undefined | null): void
+  /**
+   * 设置多语言文本
+   * 每个语言版本写入独立的自定义格式，第一个版本同时作为默认纯文本
+   */
+  setTextLocalized(variants: Array<LocalizedText>): void
+  /** 获取指定语言的文本，不存在该语言版本时返回默认纯文本 */
+  getTextLocalized(locale: string): string
+  /** 设置纯文本，并在同一次写入中将来源标识写入自定义格式 `application/x-clip-source` */
+  setTextWithSource(text: string, source: string): void
+  /** 获取 setTextWithSource 写入的来源标识，剪贴板中没有来源标识时返回 null */
+  getSource(): string | null
+  /**
+   * 设置复合内容，并在同一次写入中将标记写入自定义格式 `application/x-clip-marker`
+   * 多设备同步时可写入设备 ID，再通过 getMarker 忽略自己写入的变化，避免回环
+   */
+  setContentsMarked(contents: ClipboardData, marker: string): void
+  /** 获取 setContentsMarked 写入的标记，剪贴板中没有标记时返回 null */
+  getMarker(): string | null
+  /**
+   * 设置纯文本，并在同一次写入中将元数据写入自定义格式 `application/x-clip-meta`
+   * 元数据通常为 JSON 字符串，原样保存不做校验；其他应用仍只看到普通文本
+   */
+  setWithMetadata(text: string, metadata: string): void
+  /** 获取 setWithMetadata 写入的元数据，剪贴板中没有元数据时返回 null */
+  getMetadata(): string | null
+  /**
+   * 设置只供指定应用读取的文本，返回是否实际限定了读取范围
+   * macOS 上 bundle_id 不为空时写入以 bundle_id 命名的私有剪贴板（命名 NSPasteboard），通用剪贴板保持不变，
+   * 目标应用需通过 `NSPasteboard(name:)` 读取；这种隔离只依赖名称约定，知道名称的应用仍可读取
+   * Windows 和 Linux 没有按应用限定剪贴板的机制，此时以及 bundle_id 为空时与 setText 相同并返回 false
+   */
+  setTextScoped(text: string, bundleId?: string | undefined | null): boolean
+  /** 获取剪贴板文本并检测其语言，检测为尽力而为，文本过短或无法确定时 language 为 null */
+  getTextWithLanguage(): TextWithLang
+  /** 获取剪贴板文本的字符数、行数、单词数等结构统计，只返回统计结果而不传输文本 */
+  getTextStats(): TextStats
+  /**
+   * 按调用方给定的格式顺序（text、html、rtf）读取文本，HTML 与 RTF 会转换为纯文本
+   * 返回第一个存在且不为空白的结果，都没有时返回 null；包含不支持的格式名称时返回 InvalidArg 错误
+   */
+  getPreferredText(precedence: Array<string>): string | null
+  /**
+   * 获取按指定 shell（bash、powershell、cmd）规则转义后的文本，可作为单个参数直接粘贴到命令行
+   * cmd 的结果只适用于交互式 `cmd.exe` 提示符，不适用于批处理文件，也不转义延迟展开使用的 `!`
+   * 不支持的 shell 名称或无法转义的文本（例如 cmd 下包含换行）返回 InvalidArg 错误
+   */
+  getTextShellEscaped(shell: string): string
+  /** 获取去除首尾空白后的文本，剪贴板没有文本或文本为空白时返回 null */
+  getTextTrimmed(): string | null
+  /**
+   * 将剪贴板文本的 UTF-8 字节写入调用方提供的 Buffer，返回写入的字节数
+   * 文本大于 Buffer 容量时返回 InvalidArg 错误，Buffer 内容保持不变
+   */
+  getTextInto(buffer: Buffer): number
+  /**
+   * 剪贴板文本为已存在的绝对路径时返回该路径（去除首尾空白），否则返回 null
+   * 支持 POSIX 绝对路径、Windows 盘符路径和 UNC 路径
+   */
+  getTextAsPath(): string | null
   /** 获取剪贴板中的 HTML 内容 */
   getHtml(): string
+  /**
+   * 获取剪贴板中的 HTML 内容，并将其字符编码统一转换为 UTF-8
+   * 依次根据 BOM、meta charset 声明与内容特征检测原始编码，同时去除 Windows 的 CF_HTML 头部
+   */
+  getHtmlUtf8(): string
+  /**
+   * 获取剪贴板中的 HTML 内容并清理为可安全渲染的 HTML
+   * 仅保留白名单中的元素与属性，移除脚本、事件处理属性以及 javascript: 等危险链接
+   */
+  getHtmlSanitized(): string
+  /**
+   * 获取剪贴板 HTML 通过 BOM 或 `<meta charset>`、`content-type` meta 标签声明的编码名称，不解码内容
+   * 没有声明时返回 null；剪贴板中没有 HTML 时返回错误
+   */
+  getHtmlCharset(): string | null
+  /**
+   * 检查剪贴板中的纯文本与 HTML 的文本内容是否一致
+   * 去除 HTML 标签后与纯文本比较，比较时合并连续空白；任一格式不存在时返回错误
+   */
+  checkTextHtmlConsistency(): boolean
   /** 设置剪贴板中的 HTML 内容 */
   setHtml(html: string): void
   /** 获取剪贴板中的富文本内容 */
   getRichText(): string
+  /**
+   * 获取 RTF 富文本中的纯文本：跳过控制字与字体表等不显示的组，解码转义字符
+   * 剪贴板中没有 RTF 内容时返回错误
+   */
+  getRtfAsText(): string
   /** 设置剪贴板中的富文本内容 */
   setRichText(text: string): void
+  /**
+   * 设置代码片段：HTML 格式为转义后的 `<pre><code>` 片段，纯文本格式为原始代码
+   * 指定 language 时为 code 元素添加 `language-<语言>` 样式类
+   */
+  setCode(text: string, language?: string | undefined | null): void
+  /** 设置带样式类的文本：HTML 格式为转义后的 `<span class="...">` 片段，纯文本格式为原始文本 */
+  setStyledText(text: string, cssClass: string): void
+  /**
+   * 将文本以 separator（默认为换行）分隔追加到剪贴板现有文本之后，现有文本的最后一段已是该文本时不追加
+   * 剪贴板没有文本时直接写入；返回是否写入了剪贴板，写入后剪贴板只包含文本格式
+   */
+  appendTextUnique(text: string, separator?: string | undefined | null): boolean
+  /**
+   * 在同一次写入中以多种形式设置颜色：纯文本（不透明时为 `#RRGGBB`，否则为 `rgba(...)`）、
+   * HTML 色块以及可选的 1×1 图片；formats 控制包含哪些形式，至少需要包含一种
+   */
+  setColor(color: RgbaColor, formats?: ColorFormats | undefined | null): void
   /** 获取剪贴板中的图片数据（以 base64 编码返回） */
   getImageBase64(): string
   /** 获取剪贴板中的图片详细信息（包含宽度、高度、大小和原始数据） */
   getImageData(): ImageData
+  /**
+   * 严格模式获取剪贴板中的图片详细信息
+   * 仅当图片为普通的单帧栅格图片时返回，动画、多页或无法识别的图片返回错误而不是静默转换为单帧 PNG
+   */
+  getImageStrict(): ImageData
+  /**
+   * 获取按 EXIF 方向信息旋转或翻转后的剪贴板图片，使手机照片等带方向标记的图片正向显示
+   * 没有方向信息（包括平台只提供解码后的位图）或无法解码原始数据时与 getImageData 相同
+   */
+  getImageOriented(): ImageData
+  /**
+   * 从剪贴板 HTML 中提取第一张以 data URL 内嵌的图片，适用于只复制了 HTML 而没有单独图片格式的网页内容
+   * 剪贴板中没有 HTML 或 HTML 中没有内嵌图片时返回 null，内嵌数据无法解码时返回错误
+   */
+  extractImageFromHtml(): ImageData | null
+  /**
+   * 获取剪贴板图片的动画帧信息，支持 GIF、APNG 和 WebP，其他格式视为静态图片
+   * 平台只提供解码后的位图时同样视为静态图片，剪贴板中没有图片时返回错误
+   */
+  getImageAnimationInfo(): AnimationInfo
   /** 从 base64 编码的图片数据设置剪贴板图片 */
   setImageBase64(base64Data: string): void
   /** 从原始字节数据设置剪贴板图片 */
   setImageRaw(imageData: Buffer): void
+  /**
+   * 将多张图片分别作为独立的剪贴板项目写入，便于一次粘贴多张图片（例如粘贴到 Keynote）
+   * 仅 macOS 支持多项目剪贴板，此时图片统一转换为 PNG 并返回 true；其他平台只写入第一张图片并返回 false
+   * 测试模式的快照只能保存一张图片，macOS 上同样只写入第一张并返回 false；images 为空时返回 InvalidArg 错误
+   */
+  setImages(images: Array<Buffer>): boolean
+  /**
+   * 将已编码的图片字节按文件头识别的 MIME 类型原样写入平台对应的图片格式，不解码也不重新编码
+   * Windows 写入 PNG、JFIF、GIF 注册格式（只读取 CF_DIB 的旧程序无法粘贴），macOS 写入对应的 UTI，Linux 写入 MIME 类型；
+   * 平台没有对应格式时回退为 setImageRaw 的解码写入，无法识别的数据返回 InvalidArg 错误
+   */
+  setImageBytesNative(buffer: Buffer): void
+  /** 检查数据能否被解码为图片，不会修改剪贴板 */
+  static isValidImage(imageData: Buffer): boolean
+  /**
+   * 裁剪图片到指定矩形区域后设置到剪贴板
+   * 矩形超出图片范围时返回 InvalidArg 错误
+   */
+  setImageCropped(imageData: Buffer, x: number, y: number, width: number, height: number): void
+  /** 将图片按原比例缩放到画布范围内，居中放置在指定尺寸的透明画布上后设置到剪贴板 */
+  setImagePadded(imageData: Buffer, canvasWidth: number, canvasHeight: number): void
+  /** 设置剪贴板图片，最长边超过 max_dimension 时按原比例缩小到该尺寸，否则原样设置 */
+  setImageCapped(imageData: Buffer, maxDimension: number): void
+  /**
+   * 将图片合成到指定颜色的纯色背景上并去除透明通道后设置到剪贴板
+   * 适用于粘贴到不支持透明度的程序（如旧版 Office），避免透明区域显示为黑色
+   */
+  setImageFlattened(imageData: Buffer, background: RgbColor): void
+  /**
+   * 从 canvas 的 ImageData（`{ data, width, height }`）直接设置剪贴板图片，无需先在 JS 中编码为 PNG
+   * data 的长度必须等于 width × height × 4，否则返回 InvalidArg 错误
+   */
+  setImageFromCanvas(image: CanvasImageData): void
+  /**
+   * 将文本生成二维码图片并设置到剪贴板，未指定 data 时使用剪贴板中的当前文本
+   * 没有可用文本或文本超出二维码容量时返回 InvalidArg 错误
+   */
+  setQrFromText(data?: string | undefined | null): void
+  /**
+   * 创建分块写入图片的写入器，数据在原生侧累积，调用 finish 后解码并设置到剪贴板
+   * max_size 为允许的最大字节数，默认 64 MiB，超出时写入失败并丢弃已接收的数据
+   */
+  setImageFromStream(maxSize?: number | undefined | null): ImageStreamWriter
   /** 获取剪贴板中的图片原始数据（Buffer） */
   getImageRaw(): Buffer
+  /**
+   * 获取剪贴板图片的尺寸及 PNG 编码后的大小，图片数据保留在原生侧
+   * 支持剪贴板序列号的平台（Windows 与 macOS）会缓存编码结果，剪贴板未变化时下一次 getImageRaw 直接复用
+   */
+  getImageInfo(): ImageInfo
+  /**
+   * 以 Blob 兼容的结构获取剪贴板图片，MIME 类型反映图片的实际源格式
+   * 无法获取源格式数据时返回 PNG 编码的图片
+   */
+  getImageAsBlob(): BlobData
+  /**
+   * 获取剪贴板图片的原始数据及其 MIME 类型和尺寸
+   * 平台提供原始格式数据时原样返回，否则返回 PNG 编码的图片并将 wasTranscoded 设为 true
+   * 无法直接解析尺寸的 HEIC/HEIF 图片通过 libheif 解码后转码，编译时未启用 `heic` 特性时返回错误
+   */
+  getImageNative(): NativeImage
+  /** 获取剪贴板图片的主色调，缩小图片后统计得到，剪贴板中没有图片时返回错误 */
+  getImageDominantColor(): RgbColor
+  /**
+   * 获取剪贴板图片中完全透明像素所占的比例（0.0–1.0），没有 alpha 通道的图片返回 0
+   * 剪贴板中没有图片时返回错误
+   */
+  getImageTransparencyRatio(): number
+  /**
+   * 将剪贴板图片切分为 rows × cols 个尺寸相近的图块，按行优先顺序返回各图块的 PNG 数据
+   * rows 或 cols 为 0、或超过图片的高度或宽度时返回 InvalidArg 错误
+   */
+  getImageTiles(rows: number, cols: number): Array<ImageData>
+  /**
+   * 获取剪贴板图片并转换为 PNG，HEIC/HEIF 图片（例如从 macOS 照片复制）先通过 libheif 解码
+   * 其他格式按常规方式解码；需要解码 HEIC 但编译时未启用 `heic` 特性时返回错误
+   */
+  getImageDecodingHeic(): ImageData
+  /**
+   * 比较剪贴板图片与给定图片的视觉相似度（0.0–1.0），基于感知哈希，对缩放和压缩失真不敏感
+   * 剪贴板中没有图片或给定数据不是图片时返回错误
+   */
+  compareImageTo(buffer: Buffer): number
+  /**
+   * 将剪贴板图片解码一次后分别编码为多种格式，格式描述为 `png`、`jpeg:<quality>` 或 `webp`
+   * 结果与 formats 的顺序一致；格式描述无效时在读取剪贴板之前返回 InvalidArg 错误
+   */
+  getImageEncodings(formats: Array<string>): Array<EncodedImage>
+  /**
+   * 获取压缩到指定字节数以内的 JPEG 图片
+   * 依次降低 JPEG 质量，仍超出时按比例缩小图片后重试，最小质量和尺寸下仍无法满足时返回错误
+   */
+  getImageUnderSize(maxBytes: number): Buffer
   /** 获取剪贴板中的文件列表 */
   getFiles(): Array<string>
+  /** 读取并解析剪贴板中的 `text/uri-list` 格式，将本地文件路径与远程 URL 分开返回 */
+  getUriList(): UriList
+  /**
+   * 获取规范化的文件路径：转换为绝对路径、解析符号链接并统一分隔符，`file://` URI 会先转换为路径
+   * strict 为 false（默认）时跳过无法解析的路径（例如已被删除的文件），为 true 时遇到无法解析的路径返回错误
+   */
+  getFilesCanonical(strict?: boolean | undefined | null): Array<string>
+  /**
+   * 获取去除重复项后的文件列表，按首次出现的顺序保留原始路径
+   * 比较时解析符号链接并规范化路径，无法解析的路径（例如已被删除的文件）按原始字符串比较
+   */
+  getFilesDeduplicated(): Array<string>
+  /**
+   * 获取剪贴板中所有文件的总字节数，无法读取的文件（例如已被删除）计为 0
+   * recursive 为 true 时累加目录中的全部内容，为 false（默认）时目录计为 0
+   */
+  getFilesTotalSize(recursive?: boolean | undefined | null): number
+  /**
+   * 将文件与目录（递归包含其内容）打包为 zip 归档，以 `application/zip` 自定义格式写入剪贴板
+   * 适用于只能传递文本与二进制数据、无法传递原生文件引用的剪贴板同步通道
+   */
+  setFilesAsArchive(files: Array<string>): void
+  /**
+   * 将剪贴板中 `application/zip` 格式的归档解压到 dest_dir（不存在时自动创建），已有的同名文件会被覆盖
+   * 返回解压出的顶层文件与目录路径，即打包时选择的各项
+   */
+  getFilesFromArchive(destDir: string): Array<string>
+  /**
+   * 获取按扩展名分组的文件列表，类别依次为 image、video、audio、document、archive、other
+   * 只返回包含文件的类别
+   */
+  getFilesGrouped(): Array<FileGroup>
   /** 设置剪贴板中的文件列表 */
   setFiles(files: Array<string>): void
+  /**
+   * 以基础目录加相对路径的方式设置剪贴板中的文件列表
+   * 每个相对路径都会拼接到 base_dir 后规范化，解析到 base_dir 之外的路径会被拒绝
+   */
+  setFilesRelative(baseDir: string, relativePaths: Array<string>): void
   /** 设置剪贴板中的自定义格式数据 */
   setBuffer(format: string, buffer: Buffer): void
+  /**
+   * 以原始字节写入指定格式的数据，不做任何转换
+   * platform_native 为 true 时 format 视为平台原生格式标识（如 Windows 的 `CF_DIB`、macOS 的 `public.tiff`），
+   * 为 false 时与 setBuffer 相同，按自定义格式名称写入。macOS 与 Linux 的格式名称本身即为原生标识
+   */
+  setRawFormat(format: string, data: Buffer, platformNative: boolean): void
   /** 获取剪贴板中的自定义格式数据 */
   getBuffer(format: string): Buffer
+  /**
+   * 读取自定义格式数据并按 chunk_size 字节切分，便于通过有消息大小限制的通道传输
+   * 最后一块可能不足 chunk_size，数据为空时返回空数组；chunk_size 为 0 时返回 InvalidArg 错误
+   */
+  getBufferChunks(format: string, chunkSize: number): Array<Buffer>
+  /** 按顺序拼接各块数据后写入自定义格式，与 getBufferChunks 配合使用 */
+  setBufferFromChunks(format: string, chunks: Array<Buffer>): void
+  /**
+   * 获取剪贴板中指定格式数据的前 n 个字节，适用于大数据的类型嗅探
+   * Wayland 下只从管道读取所需字节，其他平台读取完整数据后截断
+   */
+  peekFormat(format: string, n: number): Buffer
   /** 设置剪贴板中的复合内容（可同时设置多种格式） */
   setContents(contents: ClipboardData): void
+  /**
+   * 按给定顺序写入多种格式，先写入的格式优先提供给粘贴方
+   * 与 setContents 的固定顺序不同，可以让 HTML 等格式先于纯文本被提供；同一格式出现多次时返回错误
+   */
+  setWithPriority(contents: Array<PrioritizedContent>): void
+  /**
+   * 设置复合内容后读回可用格式，确认 expected_formats 中的格式都已写入，缺少任一格式时返回错误并列出缺少的格式
+   * 期望格式可以是标准格式名称（text、html 等）或平台原生格式名称
+   */
+  setContentsExpecting(contents: ClipboardData, expectedFormats: Array<string>): void
   /** 检查剪贴板是否包含指定格式的内容 */
   hasFormat(format: string): boolean
   /** 获取剪贴板中所有可用的格式 */
   getAvailableFormats(): Array<string>
+  /**
+   * 根据剪贴板中存在的格式汇总可用的粘贴方式，便于统一构建粘贴菜单
+   * 只在包含文本时读取文本内容以判断是否为链接
+   */
+  getPasteCapabilities(): PasteCapabilities
+  /**
+   * 获取剪贴板来源应用优先提供的格式（按平台原生格式顺序的第一项）
+   * 标准格式映射为 text、rtf、html、image、files，其他格式返回原始名称，剪贴板为空时返回 null
+   */
+  getPrimaryFormat(): string | null
+  /**
+   * 获取整个剪贴板内容的指纹（SHA-256 十六进制字符串）
+   * 内容完全相同的剪贴板得到相同的指纹，可用于历史记录去重
+   */
+  getClipboardFingerprint(): string
+  /**
+   * 分别获取每个标准格式的内容哈希（SHA-256 十六进制字符串）
+   * 与上次的结果比较即可得知哪些格式发生了变化，只同步变化的格式
+   * Windows 上所有格式在同一次剪贴板会话中读取，其他平台各格式分别读取
+   */
+  getFormatHashes(): Array<FormatHash>
+  /**
+   * 列出剪贴板中所有可用格式及其数据大小
+   * 标准格式通过对应的读取接口统计，其他格式通过自定义格式接口统计，读取失败的格式会被跳过
+   */
+  inspect(): Array<FormatSize>
+  /**
+   * 列出所有可用格式及其数据大小，读取期间持有进程内剪贴板锁，本进程的写入不会穿插其中
+   * 各格式仍分别打开剪贴板读取，其他应用在读取期间修改剪贴板时结果可能混合新旧内容
+   * 标准格式的大小与 inspect 的统计方式相同，其他格式按原生格式名称读取原始字节统计
+   */
+  getFormatSizes(): Array<FormatSize>
+  /**
+   * 按平台原生格式逐一读取剪贴板的原始字节，不做任何转码
+   * 返回的 platformName 可直接传给 `setRawFormat(platformName, data, true)` 原样写回；无法按字节读取的格式会被跳过
+   */
+  getRawContents(): Array<RawFormat>
+  /**
+   * 获取剪贴板中无法通过本库读取的平台格式：既不对应标准格式，也无法作为自定义格式读取到非空数据
+   * 例如 Windows 的图元文件等基于句柄的格式，或读取失败的延迟渲染格式；会读取每个自定义格式的数据，数据较大时耗时较长
+   */
+  getUnsupportedFormats(): Array<string>
+  /** 检查剪贴板中是否存在无法通过本库读取的平台格式，判断方式与 getUnsupportedFormats 相同 */
+  hasUnsupportedContent(): boolean
+  /**
+   * 在一次剪贴板会话中读取指定的标准格式（text、rtf、html、image、files），空列表表示全部
+   * Windows 上只打开一次剪贴板完成所有读取，缩短其他应用无法访问剪贴板的时间
+   */
+  withReadSession(formats: Array<string>): ClipboardData
+  /**
+   * 从剪贴板中移除指定的标准格式（text、rtf、html、image、files），其余标准格式重新写入
+   * 一次读取、一次写入完成所有移除；自定义格式不会被保留，全部移除时清空剪贴板
+   */
+  removeFormats(formats: Array<string>): void
+  /**
+   * 获取 X11 下 CLIPBOARD 选区所有者窗口的标题，可用于记录复制来源
+   * 没有所有者、无法读取标题或不是 X11 环境时返回 null
+   */
+  getX11OwnerTitle(): string | null
+  /**
+   * 将剪贴板的全部格式导出为带版本号的二进制归档，可保存或传输后通过 importArchive 还原
+   * 标准格式使用 text、rtf、html、image（PNG）、files 作为名称，其他格式保留原始名称
+   */
+  exportArchive(): Buffer
+  /** 从 exportArchive 生成的归档还原剪贴板内容，归档无效时返回 InvalidArg 错误 */
+  importArchive(archive: Buffer): void
+  /**
+   * 将一条剪贴板数据保存为快照文件，使用与 exportArchive 相同的归档格式
+   * 只保存标准格式，图片数据按原始字节写入
+   */
+  static saveSnapshotToFile(data: ClipboardData, path: string): void
+  /**
+   * 从 saveSnapshotToFile 保存的快照文件读取剪贴板数据，不修改剪贴板
+   * 文件读取失败返回 GenericFailure 错误，文件不是有效的快照时返回 InvalidArg 错误
+   */
+  static loadSnapshotFromFile(path: string): ClipboardData
+  /**
+   * 启用或关闭测试模式：传入快照后，基础读写接口（getText、getImageData、hasFormat、setContents、clear 等）、
+   * 对应的异步接口、快速操作函数以及基于它们的接口改为读写内存中的快照，不访问系统剪贴板；传入 null 恢复正常行为
+   * 写入只保留标准格式；直接访问原生格式的接口（如 getBuffer、setBuffer、getRawContents）和监听器不受影响，仍访问系统剪贴板
+   */
+  static setTestMode(snapshot?: ClipboardData | undefined | null): void
+  /** 开始一个批量读写事务：在一次剪贴板会话中读取全部标准格式作为快照，写入在 commit 时一次完成 */
+  begin(): ClipboardTransaction
   /** 清空剪贴板 */
   clear(): void
   /** 异步获取剪贴板文本内容 */
   getTextAsync(): Promise<string>
   /** 异步设置剪贴板文本内容 */
   setTextAsync(text: string): Promise<void>
+  /**
+   * 异步设置剪贴板文本内容，失败时重试
+   * 适用于剪贴板可能被其他进程短暂占用的场景（如 Windows），重试间隔在阻塞线程中等待
+   */
+  setTextWithRetry(text: string, attempts: number, delayMs: number): Promise<void>
+  /**
+   * 异步等待剪贴板中出现指定格式（text、html、rtf、image、files），出现后返回完整的剪贴板数据
+   * timeout_ms 为空时一直等待，超时后返回错误；检查格式失败（例如无法访问剪贴板）时立即返回错误
+   */
+  waitForFormat(format: string, timeoutMs?: number | undefined | null): Promise<ClipboardData>
+  /**
+   * 异步获取完整的剪贴板数据，各格式在阻塞线程池中使用独立的上下文读取
+   * 原始数据的读取受进程级锁保护，实际仍逐个进行；并行的只有图片的 PNG 编码，可与其他格式的读取同时进行
+   * 各格式在不同时刻读取，读取期间剪贴板被修改时结果可能混合新旧内容；Windows 下在一个线程中按顺序读取
+   */
+  getFullClipboardDataParallelAsync(): Promise<ClipboardData>
+  /**
+   * 异步获取完整的剪贴板数据，所有格式的读取总时长不超过 budget_ms 毫秒
+   * 按文本、HTML、RTF、文件、图片的优先顺序逐个读取，超出预算时停止并返回已读取的内容，partial 为 true，
+   * 未读取的格式为空；超时的格式仍会在后台线程中读取完毕，结果被丢弃
+   */
+  getFullClipboardDataBudgetedAsync(budgetMs: number): Promise<ClipboardData>
   /** 异步获取剪贴板图片数据（以 base64 编码返回） */
   getImageBase64Async(): Promise<string>
+  /** 异步设置剪贴板图片（从 base64 编码的图片数据），解码与写入在阻塞线程中进行 */
+  setImageBase64Async(base64Data: string): Promise<void>
+  /** 异步设置剪贴板图片（从原始图片字节），解码与写入在阻塞线程中进行 */
+  setImageRawAsync(imageData: Buffer): Promise<void>
   /** 异步获取剪贴板图片详细信息（包含宽度、高度、大小和原始数据） */
   getImageDataAsync(): Promise<ImageData>
 }
 
+/**
+ * 轮询式剪贴板变化检测器，不使用后台线程和回调
+ * 适用于不便使用原生线程或线程安全函数的运行环境
+ * 优先使用系统剪贴板序列号判断变化，不支持时比较内容指纹
+ */
+export declare class ClipboardPoller {
+  /** 创建新的轮询器实例，并记录当前剪贴板状态作为基准 */
+  constructor()
+  /**
+   * 检查自上次轮询以来剪贴板是否变化
+   * 变化时返回完整的剪贴板数据，未变化时返回 null
+   */
+  pollChanges(): ClipboardData | null
+}
+
+/**
+ * 批量读写剪贴板的事务，由 ClipboardManager.begin 创建
+ * 创建时在一次剪贴板会话中读取全部标准格式，读取方法均返回该快照中的内容，事务期间不会一直占用剪贴板，
+ * commit 时另行打开剪贴板写入；写入方法只暂存数据，调用 commit 时一次性写入剪贴板。
+ * 未提交的事务在调用 abort 或被垃圾回收时丢弃暂存内容，不修改剪贴板
+ */
+export declare class ClipboardTransaction {
+  /** 获取快照中的纯文本内容 */
+  getText(): string | null
+  /** 获取快照中的 HTML 内容 */
+  getHtml(): string | null
+  /** 获取快照中的 RTF 富文本内容 */
+  getRichText(): string | null
+  /** 获取快照中的图片数据（PNG） */
+  getImageData(): ImageData | null
+  /** 获取快照中的文件列表 */
+  getFiles(): Array<string> | null
+  /** 暂存纯文本内容 */
+  setText(text: string): void
+  /** 暂存 HTML 内容 */
+  setHtml(html: string): void
+  /** 暂存 RTF 富文本内容 */
+  setRichText(text: string): void
+  /** 暂存图片，数据无法解析为图片时返回 InvalidArg 错误 */
+  setImageRaw(imageData: Buffer): void
+  /** 暂存文件列表 */
+  setFiles(files: Array<string>): void
+  /** 暂存自定义格式数据，同名格式会被替换 */
+  setBuffer(format: string, buffer: Buffer): void
+  /**
+   * 将暂存的全部内容一次性写入剪贴板并结束事务，没有暂存内容时不修改剪贴板
+   * 未暂存的标准格式按快照中的内容一并写入，剪贴板中原有的自定义格式不会保留
+   */
+  commit(): void
+  /** 丢弃暂存内容并结束事务，不修改剪贴板；重复调用无副作用 */
+  abort(): void
+}
+
+/**
+ * 分块写入剪贴板图片的写入器，由 ClipboardManager.setImageFromStream 创建
+ * 调用 finish 完成写入，调用 abort 取消并释放已接收的数据，完成或取消后不能继续写入
+ */
+export declare class ImageStreamWriter {
+  /** 追加一块图片数据，累计大小超过上限时返回错误并取消写入 */
+  write(chunk: Buffer): void
+  /** 完成写入，解码已接收的数据并设置到剪贴板 */
+  finish(): void
+  /** 取消写入并丢弃已接收的数据，重复调用无副作用 */
+  abort(): void
+  /** 获取已接收的字节数 */
+  getBytesWritten(): number
+}
+
+/** watchOnce 返回的句柄，用于在变化发生前取消监听 */
+export declare class WatchOnceHandle {
+  /** 停止监听，回调不再触发；回调已触发或已超时后调用无副作用 */
+  stop(): void
+}
+
+/** 图片的动画帧信息 */
+export interface AnimationInfo {
+  /** 帧数，静态图片为 1 */
+  frameCount: number
+  /** 所有帧的总时长（毫秒），静态图片为 0 */
+  totalDurationMs: number
+  /** 是否为多帧动画 */
+  isAnimated: boolean
+}
+
+/** 可直接映射为 JS Blob/File 的图片数据 */
+export interface BlobData {
+  /** 图片的实际 MIME 类型，例如 `image/png` */
+  mimeType: string
+  /** 图片数据 */
+  data: Buffer
+  /** 建议的文件名，例如 `clipboard-1700000000000.png` */
+  suggestedName: string
+}
+
+/** 与 DOM ImageData 结构相同的像素数据，data 为逐行排列的非预乘 RGBA 像素 */
+export interface CanvasImageData {
+  data: Uint8ClampedArray
+  width: number
+  height: number
+}
+
+/**
+ * 检测剪贴板是否可用
+ *
+ * 尝试创建剪贴板上下文并读取可用格式列表，不会修改剪贴板内容
+ */
+export declare function checkClipboardHealth(): HealthStatus
+
 /** 快速清空剪贴板 */
 export declare function clearClipboard(): void
 
-/** 剪贴板数据结构，包含所有可用格式的数据 */
+/**
+ * 剪贴板数据结构，包含所有可用格式的数据
+ * 同时作为 setContents 等写入接口的参数；previous*、isLikelyScreenshot、selection、origin、partial
+ * 与 savedImage* 只出现在读取结果和监听器事件中，写入时被忽略
+ */
 export interface ClipboardData {
   /** 可用的格式列表 */
   availableFormats: Array<string>
@@ -110,6 +673,83 @@ export interface ClipboardData {
   image?: ImageData
   /** 文件列表 */
   files?: Array<string>
+  /** 变化前的纯文本内容（仅监听器事件，首个事件为空） */
+  previousText?: string
+  /** 变化前的 RTF 富文本内容（仅监听器事件，首个事件为空） */
+  previousRtf?: string
+  /** 变化前的 HTML 内容（仅监听器事件，首个事件为空） */
+  previousHtml?: string
+  /** 变化前的图片数据（仅监听器事件，首个事件为空） */
+  previousImage?: ImageData
+  /** 变化前的文件列表（仅监听器事件，首个事件为空） */
+  previousFiles?: Array<string>
+  /** 是否可能为截图（仅在监听选项开启 detectScreenshots 时提供） */
+  isLikelyScreenshot?: boolean
+  /** 事件来源的选区：clipboard 或 primary（仅监听器事件） */
+  selection?: string
+  /**
+   * 变化的来源（仅监听器事件，尽力判断）：复制时剪贴板所有者属于前台窗口所在进程为 `user`，
+   * 否则为 `programmatic`；平台没有可用信号（macOS、Wayland、PRIMARY 选区）时为 `unknown`
+   */
+  origin?: string
+  /** 是否因超出时间预算而只读取了部分格式，未读取的格式为空（仅 getFullClipboardDataBudgetedAsync） */
+  partial?: boolean
+  /** 自动保存的图片文件路径（仅监听选项开启 autoSaveImagesDir 且事件包含图片时提供） */
+  savedImagePath?: string
+  /** 自动保存图片失败的原因，此时 savedImagePath 为空 */
+  savedImageError?: string
+}
+
+/** 写入剪贴板后记录的状态标记，用于判断剪贴板内容是否仍是这次写入的内容 */
+export interface ClipboardToken {
+  /** 写入后的系统剪贴板序列号，仅 Windows 与 macOS 提供 */
+  sequence?: number
+  /** 写入文本的指纹，系统不提供序列号时使用 */
+  fingerprint?: string
+}
+
+/** setColor 写入的表示形式 */
+export interface ColorFormats {
+  /** 是否写入 `#RRGGBB` 或 `rgba(...)` 形式的纯文本，默认为 true */
+  text?: boolean
+  /** 是否写入以该颜色为背景的 `<span>` HTML 色块，默认为 true */
+  html?: boolean
+  /** 是否写入该颜色的 1×1 图片，默认为 false */
+  image?: boolean
+}
+
+/** 按指定格式编码的图片数据 */
+export interface EncodedImage {
+  /** 请求的编码格式，与传入的格式描述相同 */
+  format: string
+  /** 编码后的图片数据 */
+  data: Buffer
+  /** 数据大小（字节） */
+  size: number
+}
+
+/** 按类别分组的文件列表 */
+export interface FileGroup {
+  /** 文件类别：image、video、audio、document、archive 或 other */
+  category: string
+  /** 属于该类别的文件路径，保持剪贴板中的顺序 */
+  paths: Array<string>
+}
+
+/** 剪贴板格式及其内容哈希 */
+export interface FormatHash {
+  /** 格式名称 */
+  format: string
+  /** 内容的 SHA-256 十六进制字符串 */
+  hash: string
+}
+
+/** 剪贴板格式及其数据大小 */
+export interface FormatSize {
+  /** 格式名称 */
+  format: string
+  /** 数据大小（字节） */
+  size: number
 }
 
 /** 快速获取剪贴板自定义格式数据 */
@@ -136,6 +776,20 @@ export declare function getClipboardText(): string
 /** 快速获取完整的剪贴板数据 */
 export declare function getFullClipboardData(): ClipboardData
 
+/** 剪贴板健康状态，用于诊断无显示服务、无头 CI 等环境下的可用性 */
+export interface HealthStatus {
+  /** 剪贴板后端是否可用 */
+  available: boolean
+  /** 是否可以读取剪贴板 */
+  canRead: boolean
+  /** 是否可以写入剪贴板（根据后端初始化结果推断，不会实际写入） */
+  canWrite: boolean
+  /** 当前使用的后端（wayland、x11、windows、macos） */
+  backend: string
+  /** 检测失败时的错误信息 */
+  error?: string
+}
+
 /** 图片数据结构，包含图片的详细信息 */
 export interface ImageData {
   /** 图片宽度（像素） */
@@ -148,6 +802,16 @@ export interface ImageData {
   data: Buffer
 }
 
+/** 剪贴板图片信息，不包含图片数据 */
+export interface ImageInfo {
+  /** 图片宽度（像素） */
+  width: number
+  /** 图片高度（像素） */
+  height: number
+  /** PNG 编码后的大小（字节） */
+  pngSize: number
+}
+
 /**
  * 检测 Wayland 剪贴板监听是否可用
  *
@@ -155,6 +819,104 @@ export interface ImageData {
  */
 export declare function isWaylandClipboardAvailable(): boolean
 
+/** 监听器当前生效的配置，包含 watch 选项解析后的值以及未指定选项的默认值 */
+export interface ListenerConfig {
+  /** 是否使用 Wayland 专用监听器 */
+  isWayland: boolean
+  /** 是否正在监听 */
+  isWatching: boolean
+  /** 事件中图片的编码方式：`png`、`jpeg:<quality>` 或 `webp` */
+  imageMode: string
+  /** 是否检测截图 */
+  detectScreenshots: boolean
+  /** 节流间隔（毫秒），未启用节流时为空 */
+  throttleMs?: number
+  /** 保留最近事件的数量，0 表示不保留 */
+  historySize: number
+  /** 回调繁忙时的处理方式：`drop`、`latest` 或 `queue` */
+  backpressure: string
+  /** 实际监听的选区，其他平台忽略的 primary 不包含在内 */
+  selections: Array<string>
+  /** 回调是否已停用（未 arm 或已 disarm），停用期间事件被丢弃 */
+  paused: boolean
+  /** 自动保存图片的目录 */
+  autoSaveImagesDir?: string
+}
+
+/** 带语言标记的文本 */
+export interface LocalizedText {
+  /** 语言标记，例如 `en-US`、`zh-CN` */
+  locale: string
+  /** 文本内容 */
+  text: string
+}
+
+/** 尽量保留原始格式的剪贴板图片 */
+export interface NativeImage {
+  /** 图片数据的 MIME 类型，例如 `image/jpeg` */
+  mimeType: string
+  /** 图片数据 */
+  data: Buffer
+  /** 图片宽度（像素） */
+  width: number
+  /** 图片高度（像素） */
+  height: number
+  /** 无法获取原始数据而转码为 PNG 时为 true */
+  wasTranscoded: boolean
+}
+
+/** 当前剪贴板内容支持的粘贴方式 */
+export interface PasteCapabilities {
+  /** 包含纯文本 */
+  canPasteText: boolean
+  /** 包含图片 */
+  canPasteImage: boolean
+  /** 包含文件列表 */
+  canPasteFiles: boolean
+  /** 文本是单个 http(s) 链接，可以作为链接粘贴 */
+  canPasteAsLink: boolean
+  /** 包含 HTML，可以转换为 Markdown 粘贴 */
+  canPasteAsMarkdown: boolean
+  /** 包含文本、HTML 或 RTF，可以作为无格式文本粘贴 */
+  canPastePlain: boolean
+}
+
+/** setWithPriority 中按顺序写入的一项内容 */
+export interface PrioritizedContent {
+  /** 格式名称：text、html、rtf、image、files，其他名称作为自定义格式写入 */
+  format: string
+  /** 文本内容；files 格式为每行一个文件路径，自定义格式按 UTF-8 写入 */
+  value?: string
+  /** 二进制内容，image 格式必须使用该字段，自定义格式可以使用该字段代替 value */
+  data?: Buffer
+}
+
+/** 剪贴板中某个格式未经转换的原始数据 */
+export interface RawFormat {
+  /** 标准格式映射为 text、rtf、html、image、files，其他格式与 platformName 相同 */
+  format: string
+  /** 平台原生格式标识，例如 Windows 的 `CF_DIB`、macOS 的 `public.png`、Linux 的 MIME 类型 */
+  platformName: string
+  /** 原始字节 */
+  data: Buffer
+}
+
+/** 带透明度的 RGB 颜色 */
+export interface RgbaColor {
+  r: number
+  g: number
+  b: number
+  /** 不透明度（0.0–1.0），默认为 1.0 */
+  a?: number
+}
+
+/** RGB 颜色 */
+export interface RgbColor {
+  r: number
+  g: number
+  b: number
+}
+
 /** 快速设置剪贴板自定义格式数据 */
 export declare function setClipboardBuffer(format: string, buffer: Buffer): void
 
@@ -175,3 +937,71 @@ export declare function setClipboardImageRaw(imageData: Buffer): void
 
 /** 快速设置剪贴板文本内容 */
 export declare function setClipboardText(text: string): void
+
+/** 剪贴板文本的结构统计 */
+export interface TextStats {
+  /** 字符数（按 Unicode 标量值计数，与 JS 字符串的 length 不同） */
+  charCount: number
+  /** 行数，末尾的换行不产生新行，空文本为 0 */
+  lineCount: number
+  /** 按空白分隔的单词数 */
+  wordCount: number
+  /** 是否不超过一行 */
+  isSingleLine: boolean
+  /** 是否为空或只包含空白 */
+  isWhitespaceOnly: boolean
+}
+
+/** 文本及其检测到的语言 */
+export interface TextWithLang {
+  /** 文本内容 */
+  text: string
+  /** ISO 639-1 语言代码，例如 `en`、`zh`；文本过短或无法确定时为空 */
+  language?: string
+}
+
+/** `text/uri-list` 中的条目，本地文件与远程 URL 分开存放 */
+export interface UriList {
+  /** 本地文件路径（由 `file://` URI 解码得到） */
+  files: Array<string>
+  /** 其他 URI，例如 `http(s)://` 链接 */
+  urls: Array<string>
+}
+
+/**
+ * 只监听下一次剪贴板变化：回调至多触发一次，触发后自动停止监听，无需创建 ClipboardListener
+ * timeoutMs 为等待的最长毫秒数，超时后停止监听且不再触发回调；未指定时一直等待，
+ * 等待期间监听线程会让 Node 进程保持运行，可调用返回句柄的 stop 提前取消
+ */
+export declare function watchOnce(callback: (arg: ClipboardData) => void, timeoutMs?: number | undefined | null): WatchOnceHandle
+
+/** 剪贴板监听选项 */
+export interface WatchOptions {
+  /** 监听事件中图片的编码方式：`png`（默认）、`jpeg:<quality>`（quality 取值 1-100）或 `webp`（无损） */
+  imageMode?: string
+  /** 是否检测截图，开启后事件中会提供 isLikelyScreenshot 字段 */
+  detectScreenshots?: boolean
+  /** 节流间隔（毫秒）：首个变化立即回调，持续变化时每个间隔最多回调一次最新内容 */
+  throttleMs?: number
+  /** 保留最近事件的数量，通过 getHistory 读取，超出时丢弃最旧的事件 */
+  historySize?: number
+  /**
+   * 回调繁忙时的处理方式：`drop`（默认，非阻塞调用，队列满时丢弃）、
+   * `latest`（回调执行期间只保留最新事件）或 `queue`（阻塞调用，逐个处理所有事件）
+   */
+  backpressure?: string
+  /**
+   * 要监听的选区：`clipboard`（默认）和/或 `primary`
+   * primary 仅 Linux 支持且只提供文本，其他平台忽略 primary
+   */
+  selections?: Array<string>
+  /** 是否以未启用回调的状态开始监听，调用 arm 后才开始回调，避免准备完成前的事件被送达 */
+  startDisarmed?: boolean
+  /**
+   * 自动保存图片的目录：事件包含图片时在监听线程中写入 `<毫秒时间戳>.<扩展名>`，目录不存在时自动创建
+   * 扩展名按图片数据的文件头识别，与 imageMode 一致（例如 `jpeg:80` 保存为 `.jpg`），并非总是 `.png`；
+   * 同一毫秒内已有同名文件时追加序号（`<毫秒时间戳>-1.<扩展名>`），不会覆盖已有文件
+   * 保存的路径通过事件的 savedImagePath 提供，失败原因通过 savedImageError 提供
+   */
+  autoSaveImagesDir?: string
+}
@@ -578,6 +578,11 @@ if (!nativeBinding) {
 module.exports = nativeBinding
 module.exports.ClipboardListener = nativeBinding.ClipboardListener
 module.exports.ClipboardManager = nativeBinding.ClipboardManager
+module.exports.ClipboardPoller = nativeBinding.ClipboardPoller
+module.exports.ClipboardTransaction = nativeBinding.ClipboardTransaction
+module.exports.ImageStreamWriter = nativeBinding.ImageStreamWriter
+module.exports.WatchOnceHandle = nativeBinding.WatchOnceHandle
+module.exports.checkClipboardHealth = nativeBinding.checkClipboardHealth
 module.exports.clearClipboard = nativeBinding.clearClipboard
 module.exports.getClipboardBuffer = nativeBinding.getClipboardBuffer
 module.exports.getClipboardFiles = nativeBinding.getClipboardFiles
@@ -595,3 +600,4 @@ module.exports.setClipboardHtml = nativeBinding.setClipboardHtml
 module.exports.setClipboardImage = nativeBinding.setClipboardImage
 module.exports.setClipboardImageRaw = nativeBinding.setClipboardImageRaw
 module.exports.setClipboardText = nativeBinding.setClipboardText
+module.exports.watchOnce = nativeBinding.watchOnce
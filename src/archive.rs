@@ -0,0 +1,282 @@
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+type ArchiveResult<T> = std::result::Result<T, String>;
+
+const ARCHIVE_MAGIC: &[u8; 4] = b"CBAR";
+const ARCHIVE_VERSION: u16 = 1;
+
+/// 将格式名称与数据打包为归档，整数均为小端序：
+/// 4 字节魔数 `CBAR` + u16 版本号 + u32 条目数量，
+/// 每个条目为 u16 名称长度 + UTF-8 名称 + u32 数据长度 + 数据
+pub(crate) fn encode(entries: &[(String, Vec<u8>)]) -> ArchiveResult<Vec<u8>> {
+  let mut archive = Vec::new();
+  archive.extend_from_slice(ARCHIVE_MAGIC);
+  archive.extend_from_slice(&ARCHIVE_VERSION.to_le_bytes());
+  archive.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+
+  for (name, data) in entries {
+    let name_len =
+      u16::try_from(name.len()).map_err(|_| format!("Format name is too long: {name}"))?;
+    let data_len =
+      u32::try_from(data.len()).map_err(|_| format!("Format data is too large: {name}"))?;
+    archive.extend_from_slice(&name_len.to_le_bytes());
+    archive.extend_from_slice(name.as_bytes());
+    archive.extend_from_slice(&data_len.to_le_bytes());
+    archive.extend_from_slice(data);
+  }
+
+  Ok(archive)
+}
+
+/// 按顺序读取归档字节的游标
+struct Reader<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn take(&mut self, len: usize) -> ArchiveResult<&'a [u8]> {
+    let end = self
+      .offset
+      .checked_add(len)
+      .filter(|end| *end <= self.bytes.len())
+      .ok_or_else(|| "Archive is truncated".to_string())?;
+    let slice = &self.bytes[self.offset..end];
+    self.offset = end;
+    Ok(slice)
+  }
+
+  fn read_u16(&mut self) -> ArchiveResult<u16> {
+    let bytes = self.take(2)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+  }
+
+  fn read_u32(&mut self) -> ArchiveResult<u32> {
+    let bytes = self.take(4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+  }
+}
+
+/// 解析归档，返回格式名称与数据
+pub(crate) fn decode(bytes: &[u8]) -> ArchiveResult<Vec<(String, Vec<u8>)>> {
+  let mut reader = Reader { bytes, offset: 0 };
+  if reader.take(ARCHIVE_MAGIC.len())? != ARCHIVE_MAGIC {
+    return Err("Not a clipboard archive".to_string());
+  }
+  let version = reader.read_u16()?;
+  if version != ARCHIVE_VERSION {
+    return Err(format!("Unsupported archive version: {version}"));
+  }
+
+  let count = reader.read_u32()?;
+  let mut entries = Vec::new();
+  for _ in 0..count {
+    let name_len = reader.read_u16()? as usize;
+    let name = std::str::from_utf8(reader.take(name_len)?)
+      .map_err(|_| "Archive contains an invalid format name".to_string())?
+      .to_string();
+    let data_len = reader.read_u32()? as usize;
+    entries.push((name, reader.take(data_len)?.to_vec()));
+  }
+
+  if reader.offset != bytes.len() {
+    return Err("Archive has trailing data".to_string());
+  }
+  Ok(entries)
+}
+
+/// 将文件与目录（递归包含其内容）打包为 zip 归档，条目名称相对于各路径的父目录
+/// 目录中指向目录的符号链接会被跳过，指向文件的符号链接按文件内容打包
+pub(crate) fn zip_paths(paths: &[PathBuf]) -> ArchiveResult<Vec<u8>> {
+  let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+  let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+  for path in paths {
+    let name = path
+      .file_name()
+      .ok_or_else(|| format!("Path has no file name: {}", path.display()))?;
+    add_zip_entry(&mut writer, path, Path::new(name), options)?;
+  }
+  let cursor = writer
+    .finish()
+    .map_err(|e| format!("Failed to finish zip archive: {e}"))?;
+  Ok(cursor.into_inner())
+}
+
+fn add_zip_entry(
+  writer: &mut ZipWriter<Cursor<Vec<u8>>>,
+  path: &Path,
+  name: &Path,
+  options: SimpleFileOptions,
+) -> ArchiveResult<()> {
+  // zip 条目名称统一使用 `/` 作为分隔符
+  let entry_name = name
+    .components()
+    .map(|component| component.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/");
+
+  if path.is_dir() {
+    writer
+      .add_directory(entry_name.as_str(), options)
+      .map_err(|e| format!("Failed to add directory {}: {e}", path.display()))?;
+    let mut children = std::fs::read_dir(path)
+      .and_then(|entries| {
+        entries
+          .map(|entry| entry.map(|entry| entry.path()))
+          .collect::<std::io::Result<Vec<_>>>()
+      })
+      .map_err(|e| format!("Failed to read directory {}: {e}", path.display()))?;
+    // 不进入指向目录的符号链接，避免链接指向上级目录时无限递归
+    children.retain(|child| {
+      !std::fs::symlink_metadata(child).is_ok_and(|metadata| metadata.file_type().is_symlink())
+        || !child.is_dir()
+    });
+    // 按名称排序，保证相同内容得到相同的归档
+    children.sort();
+    for child in children {
+      if let Some(child_name) = child.file_name() {
+        add_zip_entry(writer, &child, &name.join(child_name), options)?;
+      }
+    }
+    return Ok(());
+  }
+
+  let data =
+    std::fs::read(path).map_err(|e| format!("Failed to read file {}: {e}", path.display()))?;
+  writer
+    .start_file(entry_name.as_str(), options)
+    .map_err(|e| format!("Failed to add file {}: {e}", path.display()))?;
+  writer
+    .write_all(&data)
+    .map_err(|e| format!("Failed to add file {}: {e}", path.display()))
+}
+
+/// 将 zip 归档解压到目录，返回顶层条目（即打包时选择的文件与目录）解压后的路径
+/// 名称包含 `..` 或绝对路径等会写到目标目录之外的条目视为无效归档
+pub(crate) fn unzip_to(bytes: &[u8], dest: &Path) -> ArchiveResult<Vec<PathBuf>> {
+  let mut archive =
+    ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Not a valid zip archive: {e}"))?;
+  let mut top_level = Vec::new();
+  for index in 0..archive.len() {
+    let mut entry = archive
+      .by_index(index)
+      .map_err(|e| format!("Failed to read zip entry: {e}"))?;
+    let name = entry
+      .enclosed_name()
+      .ok_or_else(|| format!("Zip entry has an unsafe path: {}", entry.name()))?;
+    let path = dest.join(&name);
+    if let Some(first) = name.components().next() {
+      let top = dest.join(first);
+      if !top_level.contains(&top) {
+        top_level.push(top);
+      }
+    }
+
+    if entry.is_dir() {
+      std::fs::create_dir_all(&path)
+        .map_err(|e| format!("Failed to create directory {}: {e}", path.display()))?;
+      continue;
+    }
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create directory {}: {e}", parent.display()))?;
+    }
+    let mut file = std::fs::File::create(&path)
+      .map_err(|e| format!("Failed to create file {}: {e}", path.display()))?;
+    std::io::copy(&mut entry, &mut file)
+      .map_err(|e| format!("Failed to write file {}: {e}", path.display()))?;
+  }
+  Ok(top_level)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample_entries() -> Vec<(String, Vec<u8>)> {
+    vec![
+      ("text".to_string(), "你好".as_bytes().to_vec()),
+      ("application/x-empty".to_string(), Vec::new()),
+      ("image".to_string(), vec![0x89, b'P', b'N', b'G']),
+    ]
+  }
+
+  #[test]
+  fn decode_round_trips_encoded_entries() {
+    let entries = sample_entries();
+    assert_eq!(decode(&encode(&entries).unwrap()).unwrap(), entries);
+    assert_eq!(decode(&encode(&[]).unwrap()).unwrap(), Vec::new());
+  }
+
+  #[test]
+  fn decode_rejects_invalid_headers() {
+    let mut archive = encode(&sample_entries()).unwrap();
+    archive[0] = b'X';
+    assert_eq!(decode(&archive).unwrap_err(), "Not a clipboard archive");
+
+    let mut archive = encode(&sample_entries()).unwrap();
+    archive[4..6].copy_from_slice(&2u16.to_le_bytes());
+    assert_eq!(
+      decode(&archive).unwrap_err(),
+      "Unsupported archive version: 2"
+    );
+  }
+
+  #[test]
+  fn decode_rejects_truncated_and_trailing_data() {
+    let archive = encode(&sample_entries()).unwrap();
+    for len in 0..archive.len() {
+      assert!(decode(&archive[..len]).is_err(), "prefix of {len} bytes");
+    }
+
+    let mut trailing = archive.clone();
+    trailing.push(0);
+    assert_eq!(decode(&trailing).unwrap_err(), "Archive has trailing data");
+
+    // 条目数量远大于实际数据时按截断处理，不会预先分配
+    let mut inflated = archive[..6].to_vec();
+    inflated.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(decode(&inflated).unwrap_err(), "Archive is truncated");
+  }
+
+  #[test]
+  fn decode_rejects_invalid_format_names() {
+    let mut archive = encode(&[("ab".to_string(), Vec::new())]).unwrap();
+    // 名称位于 10 字节头部与 2 字节名称长度之后
+    archive[12] = 0xff;
+    assert_eq!(
+      decode(&archive).unwrap_err(),
+      "Archive contains an invalid format name"
+    );
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn zip_paths_skips_symlinks_to_ancestor_directories() {
+    let root = std::env::temp_dir().join(format!("archive-loop-{}", std::process::id()));
+    let nested = root.join("nested");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(nested.join("note.txt"), "hello").unwrap();
+    let link = nested.join("loop");
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(&root, &link).unwrap();
+
+    let bytes = zip_paths(std::slice::from_ref(&root)).unwrap();
+    let archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut names: Vec<_> = archive.file_names().map(str::to_string).collect();
+    names.sort();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+    assert_eq!(
+      names,
+      [
+        format!("{root_name}/"),
+        format!("{root_name}/nested/"),
+        format!("{root_name}/nested/note.txt"),
+      ]
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+  }
+}
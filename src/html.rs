@@ -0,0 +1,617 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+/// 查找 meta charset 声明时检查的最大字节数，与 HTML 规范的预扫描长度一致
+const META_PRESCAN_LIMIT: usize = 1024;
+
+/// 从 CF_HTML 数据中提取 StartFragment 与 EndFragment 之间的 HTML 片段
+/// 数据不是 CF_HTML 格式或偏移无效时返回 None
+pub(crate) fn cf_html_fragment(raw: &[u8]) -> Option<&[u8]> {
+  if !raw.starts_with(b"Version:") {
+    return None;
+  }
+  let header = String::from_utf8_lossy(&raw[..raw.len().min(META_PRESCAN_LIMIT)]);
+  let offset = |key: &str| {
+    let start = header.find(key)? + key.len();
+    header[start..].lines().next()?.trim().parse::<usize>().ok()
+  };
+
+  match (offset("StartFragment:"), offset("EndFragment:")) {
+    (Some(start), Some(end)) if start <= end && end <= raw.len() => Some(&raw[start..end]),
+    _ => None,
+  }
+}
+
+/// 在 HTML 开头查找 `<meta charset>` 或 `<meta http-equiv content="...; charset=...">` 声明的编码
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+  let head = String::from_utf8_lossy(&bytes[..bytes.len().min(META_PRESCAN_LIMIT)]).to_lowercase();
+  let mut rest = head.as_str();
+  while let Some(index) = rest.find("charset") {
+    rest = &rest[index + "charset".len()..];
+    let Some(value) = rest.trim_start().strip_prefix('=') else {
+      continue;
+    };
+    let label: String = value
+      .trim_start()
+      .trim_start_matches(['"', '\''])
+      .chars()
+      .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+      .collect();
+    if let Some(encoding) = Encoding::for_label(label.as_bytes()) {
+      // 按 HTML 规范，声明为 UTF-16 的 meta 实际已按 ASCII 兼容编码读取，视为 UTF-8
+      return Some(if encoding == UTF_16LE || encoding == UTF_16BE {
+        UTF_8
+      } else {
+        encoding
+      });
+    }
+  }
+  None
+}
+
+/// 根据零字节的分布猜测无 BOM 的 UTF-16 文本的字节序
+fn guess_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+  let sample = &bytes[..bytes.len().min(META_PRESCAN_LIMIT) & !1];
+  if sample.is_empty() {
+    return None;
+  }
+  let pairs = sample.len() / 2;
+  let even_zeros = sample.iter().step_by(2).filter(|b| **b == 0).count();
+  let odd_zeros = sample
+    .iter()
+    .skip(1)
+    .step_by(2)
+    .filter(|b| **b == 0)
+    .count();
+  if odd_zeros * 2 > pairs && even_zeros * 8 < pairs {
+    Some(UTF_16LE)
+  } else if even_zeros * 2 > pairs && odd_zeros * 8 < pairs {
+    Some(UTF_16BE)
+  } else {
+    None
+  }
+}
+
+/// 检测 HTML 字节的编码：依次检查 BOM、meta charset 声明、UTF-16 零字节分布与 UTF-8 有效性，
+/// 都无法确定时按 Windows-1252 处理
+pub(crate) fn detect_encoding(bytes: &[u8]) -> &'static Encoding {
+  if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+    return encoding;
+  }
+  if let Some(encoding) = guess_utf16(bytes) {
+    return encoding;
+  }
+  if let Some(encoding) = meta_charset(bytes) {
+    // 声明与内容冲突时以内容为准：有效的非 ASCII UTF-8 几乎不可能是其他编码
+    if encoding != UTF_8 && !bytes.is_ascii() && std::str::from_utf8(bytes).is_ok() {
+      return UTF_8;
+    }
+    return encoding;
+  }
+  if std::str::from_utf8(bytes).is_ok() {
+    UTF_8
+  } else {
+    WINDOWS_1252
+  }
+}
+
+/// 获取 HTML 字节通过 BOM 或 meta 标签声明的编码名称，没有声明时返回 None，不根据内容猜测
+/// CF_HTML 数据先检查片段，再检查包含 head 的完整文档
+pub(crate) fn declared_charset(raw: &[u8]) -> Option<&'static str> {
+  let bytes = cf_html_fragment(raw).unwrap_or(raw);
+  if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+    return Some(encoding.name());
+  }
+  meta_charset(bytes)
+    .or_else(|| meta_charset(raw))
+    .map(Encoding::name)
+}
+
+/// 将剪贴板中的原始 HTML 字节解码为 UTF-8 字符串，CF_HTML 数据只保留片段部分
+pub(crate) fn decode_html(raw: &[u8]) -> String {
+  // CF_HTML 按规范始终为 UTF-8，但片段中的 meta 声明可能与之不符，因此仍对片段做检测
+  let bytes = cf_html_fragment(raw).unwrap_or(raw);
+  let (text, _, _) = detect_encoding(bytes).decode(bytes);
+  text.trim_end_matches('\0').to_string()
+}
+
+/// 内容不作为文本显示的元素
+const HIDDEN_ELEMENTS: [&str; 4] = ["script", "style", "head", "template"];
+
+/// 前后会产生换行的块级元素及 br，提取文本时按空白处理，其余标签直接去除
+const BLOCK_ELEMENTS: [&str; 27] = [
+  "address",
+  "article",
+  "aside",
+  "blockquote",
+  "br",
+  "dd",
+  "div",
+  "dl",
+  "dt",
+  "footer",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "header",
+  "hr",
+  "li",
+  "ol",
+  "p",
+  "pre",
+  "section",
+  "table",
+  "td",
+  "tr",
+  "ul",
+];
+
+/// 解码单个 HTML 字符引用（不含 `&` 与 `;`），无法识别时返回 None
+fn decode_entity(entity: &str) -> Option<char> {
+  if let Some(number) = entity.strip_prefix('#') {
+    let code = match number.strip_prefix(['x', 'X']) {
+      Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+      None => number.parse().ok()?,
+    };
+    return char::from_u32(code);
+  }
+  match entity {
+    "amp" => Some('&'),
+    "lt" => Some('<'),
+    "gt" => Some('>'),
+    "quot" => Some('"'),
+    "apos" => Some('\''),
+    "nbsp" => Some('\u{a0}'),
+    _ => None,
+  }
+}
+
+/// 解码位于开头的字符引用，返回字符及引用的字节长度
+fn leading_entity(input: &str) -> Option<(char, usize)> {
+  let end = input[1..].find(';').filter(|end| *end <= 10)?;
+  decode_entity(&input[1..1 + end]).map(|ch| (ch, end + 2))
+}
+
+/// 解码文本中的常见字符引用，无法识别的引用原样保留
+fn decode_entities(text: &str) -> String {
+  let mut decoded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(index) = rest.find('&') {
+    decoded.push_str(&rest[..index]);
+    rest = &rest[index..];
+    match leading_entity(rest) {
+      Some((ch, len)) => {
+        decoded.push(ch);
+        rest = &rest[len..];
+      }
+      None => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+/// 跳过元素的内容直到其结束标签之后，没有结束标签时跳过全部剩余内容
+fn skip_element<'a>(rest: &'a str, name: &str) -> &'a str {
+  let closing = format!("</{name}");
+  rest
+    .to_ascii_lowercase()
+    .find(&closing)
+    .and_then(|start| rest[start..].find('>').map(|end| &rest[start + end + 1..]))
+    .unwrap_or("")
+}
+
+/// 提取 HTML 的文本内容：去除标签、注释以及脚本和样式等不可见元素，解码常见字符引用
+/// 块级元素按空白处理，结果中的空白未做合并
+pub(crate) fn html_to_text(html: &str) -> String {
+  let mut text = String::with_capacity(html.len());
+  let mut rest = html;
+  while let Some(ch) = rest.chars().next() {
+    match ch {
+      '<' if rest.starts_with("<!--") => {
+        rest = rest[4..].find("-->").map_or("", |end| &rest[4 + end + 3..]);
+      }
+      '<' if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!') => {
+        let Some(end) = rest.find('>') else {
+          text.push_str(rest);
+          break;
+        };
+        let is_closing = rest[1..].starts_with('/');
+        let name: String = rest[1..end]
+          .trim_start_matches('/')
+          .chars()
+          .take_while(|c| c.is_ascii_alphanumeric())
+          .collect::<String>()
+          .to_ascii_lowercase();
+        rest = &rest[end + 1..];
+        if !is_closing && HIDDEN_ELEMENTS.contains(&name.as_str()) {
+          rest = skip_element(rest, &name);
+        } else if BLOCK_ELEMENTS.contains(&name.as_str()) {
+          text.push(' ');
+        }
+      }
+      '&' => match leading_entity(rest) {
+        Some((ch, len)) => {
+          text.push(ch);
+          rest = &rest[len..];
+        }
+        None => {
+          text.push('&');
+          rest = &rest[1..];
+        }
+      },
+      _ => {
+        text.push(ch);
+        rest = &rest[ch.len_utf8()..];
+      }
+    }
+  }
+  text
+}
+
+/// 将连续空白（包括不换行空格）合并为单个空格并去除首尾空白
+pub(crate) fn normalize_whitespace(text: &str) -> String {
+  text
+    .split(|c: char| c.is_whitespace() || c == '\u{a0}')
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// 清理 HTML 时连同内容一起移除的元素
+const DROPPED_ELEMENTS: [&str; 15] = [
+  "embed", "frame", "frameset", "head", "iframe", "math", "noscript", "object", "script", "select",
+  "style", "svg", "template", "textarea", "title",
+];
+
+/// 清理 HTML 时保留的元素，其余元素只保留内容
+const ALLOWED_ELEMENTS: [&str; 54] = [
+  "a",
+  "abbr",
+  "address",
+  "article",
+  "aside",
+  "b",
+  "blockquote",
+  "br",
+  "caption",
+  "cite",
+  "code",
+  "dd",
+  "del",
+  "div",
+  "dl",
+  "dt",
+  "em",
+  "figcaption",
+  "figure",
+  "footer",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "header",
+  "hr",
+  "i",
+  "img",
+  "ins",
+  "kbd",
+  "li",
+  "mark",
+  "ol",
+  "p",
+  "pre",
+  "q",
+  "s",
+  "section",
+  "small",
+  "span",
+  "strong",
+  "sub",
+  "sup",
+  "table",
+  "tbody",
+  "td",
+  "tfoot",
+  "th",
+  "thead",
+  "tr",
+  "u",
+  "ul",
+];
+
+/// 没有结束标签的空元素
+const VOID_ELEMENTS: [&str; 3] = ["br", "hr", "img"];
+
+/// 清理 HTML 时保留的属性及其所属元素，`*` 表示所有保留的元素
+const ALLOWED_ATTRIBUTES: [(&str, &str); 13] = [
+  ("*", "dir"),
+  ("*", "lang"),
+  ("*", "title"),
+  ("a", "href"),
+  ("img", "alt"),
+  ("img", "height"),
+  ("img", "src"),
+  ("img", "width"),
+  ("ol", "start"),
+  ("td", "colspan"),
+  ("td", "rowspan"),
+  ("th", "colspan"),
+  ("th", "rowspan"),
+];
+
+/// 图片 src 允许使用的 data URL 前缀
+const SAFE_DATA_IMAGE_PREFIXES: [&str; 4] = [
+  "data:image/png",
+  "data:image/jpeg",
+  "data:image/gif",
+  "data:image/webp",
+];
+
+/// 解析后的开始或结束标签
+struct Tag {
+  name: String,
+  closing: bool,
+  attributes: Vec<(String, String)>,
+  /// 标签在原文中的字节长度
+  len: usize,
+}
+
+/// 解析位于开头的标签，属性值中的字符引用已解码；不是标签或标签未结束时返回 None
+fn parse_tag(input: &str) -> Option<Tag> {
+  let bytes = input.as_bytes();
+  let skip_whitespace = |mut pos: usize| {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+      pos += 1;
+    }
+    pos
+  };
+  let token_end = |pos: usize, is_end: fn(char) -> bool| {
+    input[pos..]
+      .find(is_end)
+      .map_or(input.len(), |len| pos + len)
+  };
+
+  let closing = input[1..].starts_with('/');
+  let mut pos = if closing { 2 } else { 1 };
+  let name_end = token_end(pos, |c| !c.is_ascii_alphanumeric());
+  if name_end == pos || !bytes[pos].is_ascii_alphabetic() {
+    return None;
+  }
+  let name = input[pos..name_end].to_ascii_lowercase();
+  pos = name_end;
+
+  let mut attributes = Vec::new();
+  loop {
+    while bytes
+      .get(pos)
+      .is_some_and(|b| b.is_ascii_whitespace() || *b == b'/')
+    {
+      pos += 1;
+    }
+    if *bytes.get(pos)? == b'>' {
+      return Some(Tag {
+        name,
+        closing,
+        attributes,
+        len: pos + 1,
+      });
+    }
+
+    // 属性名至少包含当前字符，按字符宽度前进，避免在多字节字符内部切分
+    let first_len = input[pos..].chars().next()?.len_utf8();
+    let attr_end = token_end(pos + first_len, |c| {
+      c.is_ascii_whitespace() || matches!(c, '=' | '>' | '/')
+    });
+    let attr_name = input[pos..attr_end].to_ascii_lowercase();
+    pos = skip_whitespace(attr_end);
+    let mut value = String::new();
+    if bytes.get(pos) == Some(&b'=') {
+      pos = skip_whitespace(pos + 1);
+      match *bytes.get(pos)? {
+        quote @ (b'"' | b'\'') => {
+          let end = input[pos + 1..].find(char::from(quote))? + pos + 1;
+          value = decode_entities(&input[pos + 1..end]);
+          pos = end + 1;
+        }
+        _ => {
+          let end = token_end(pos, |c| c.is_ascii_whitespace() || c == '>');
+          value = decode_entities(&input[pos..end]);
+          pos = end;
+        }
+      }
+    }
+    attributes.push((attr_name, value));
+  }
+}
+
+/// 检查 URL 是否可以安全保留：相对 URL 以及 http、https、mailto 协议，图片还允许常见格式的 data URL
+fn is_safe_url(url: &str, allow_data_image: bool) -> bool {
+  // 浏览器解析 URL 时忽略其中的空白与控制字符，判断协议前先去除
+  let url = url
+    .chars()
+    .filter(|c| !c.is_whitespace() && !c.is_control())
+    .collect::<String>()
+    .to_ascii_lowercase();
+  let Some(colon) = url.find(':') else {
+    return true;
+  };
+  // 冒号出现在路径、查询或片段中时为相对 URL
+  if url[..colon].contains(['/', '?', '#']) {
+    return true;
+  }
+  match &url[..colon] {
+    "http" | "https" | "mailto" => true,
+    "data" => {
+      allow_data_image
+        && SAFE_DATA_IMAGE_PREFIXES
+          .iter()
+          .any(|prefix| url.starts_with(prefix))
+    }
+    _ => false,
+  }
+}
+
+/// 转义属性值中的特殊字符
+fn escape_attribute(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('"', "&quot;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+}
+
+/// 按白名单输出开始标签，丢弃不允许的属性以及不安全的 URL
+fn write_start_tag(output: &mut String, tag: &Tag) {
+  output.push('<');
+  output.push_str(&tag.name);
+  for (name, value) in &tag.attributes {
+    let allowed = ALLOWED_ATTRIBUTES
+      .iter()
+      .any(|(element, attribute)| (*element == "*" || *element == tag.name) && attribute == name);
+    let is_url = matches!(name.as_str(), "href" | "src");
+    if !allowed || (is_url && !is_safe_url(value, tag.name == "img")) {
+      continue;
+    }
+    output.push_str(&format!(" {name}=\"{}\"", escape_attribute(value)));
+  }
+  output.push('>');
+}
+
+/// 查找第一个 src 为 `data:image/` URL 的 img 元素，返回其 src
+pub(crate) fn first_data_image_src(html: &str) -> Option<String> {
+  html.match_indices('<').find_map(|(start, _)| {
+    let tag = parse_tag(&html[start..])?;
+    if tag.closing || tag.name != "img" {
+      return None;
+    }
+    tag
+      .attributes
+      .into_iter()
+      .find(|(name, _)| name == "src")
+      .map(|(_, src)| src.trim().to_string())
+      .filter(|src| {
+        src
+          .get(..11)
+          .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:image/"))
+      })
+  })
+}
+
+/// 按白名单清理 HTML：移除脚本、样式等危险元素及其内容，去除不在白名单中的标签与属性
+/// （包括所有事件处理属性和 style），丢弃 javascript: 等不安全的链接，并补全未闭合的元素
+pub(crate) fn sanitize_html(html: &str) -> String {
+  let mut output = String::with_capacity(html.len());
+  let mut open: Vec<String> = Vec::new();
+  let mut rest = html;
+  while let Some(ch) = rest.chars().next() {
+    match ch {
+      '<' if rest.starts_with("<!--") => {
+        rest = rest[4..].find("-->").map_or("", |end| &rest[4 + end + 3..]);
+      }
+      '<' if rest[1..].starts_with(['!', '?']) => {
+        rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+      }
+      '<' => match parse_tag(rest) {
+        Some(tag) => {
+          rest = &rest[tag.len..];
+          if tag.closing {
+            // 只关闭已打开的元素，同时关闭其内部未闭合的元素
+            if let Some(index) = open.iter().rposition(|name| *name == tag.name) {
+              for name in open.drain(index..).rev() {
+                output.push_str(&format!("</{name}>"));
+              }
+            }
+          } else if DROPPED_ELEMENTS.contains(&tag.name.as_str()) {
+            rest = skip_element(rest, &tag.name);
+          } else if ALLOWED_ELEMENTS.contains(&tag.name.as_str()) {
+            write_start_tag(&mut output, &tag);
+            if !VOID_ELEMENTS.contains(&tag.name.as_str()) {
+              open.push(tag.name);
+            }
+          }
+        }
+        None => {
+          output.push_str("&lt;");
+          rest = &rest[1..];
+        }
+      },
+      '>' => {
+        output.push_str("&gt;");
+        rest = &rest[1..];
+      }
+      _ => {
+        output.push(ch);
+        rest = &rest[ch.len_utf8()..];
+      }
+    }
+  }
+  for name in open.iter().rev() {
+    output.push_str(&format!("</{name}>"));
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sanitize_keeps_non_ascii_tags_and_attributes() {
+    assert_eq!(sanitize_html("<p é>hi</p>"), "<p>hi</p>");
+    assert_eq!(
+      sanitize_html("<p title=\"标题\" 中文>文本</p>"),
+      "<p title=\"标题\">文本</p>"
+    );
+    assert_eq!(sanitize_html("<xscript中>x"), "x");
+  }
+
+  #[test]
+  fn sanitize_escapes_unterminated_tags() {
+    assert_eq!(sanitize_html("<a中'script"), "&lt;a中'script");
+    assert_eq!(sanitize_html("<p title=\"x"), "&lt;p title=\"x");
+    assert_eq!(sanitize_html("a <"), "a &lt;");
+  }
+
+  #[test]
+  fn sanitize_drops_entity_obfuscated_javascript_urls() {
+    for href in [
+      "javascript:alert(1)",
+      "&#106;avascript:alert(1)",
+      "&#x6A;avascript:alert(1)",
+      "java&#x09;script:alert(1)",
+      " JaVaScRiPt:alert(1)",
+    ] {
+      assert_eq!(
+        sanitize_html(&format!("<a href=\"{href}\">x</a>")),
+        "<a>x</a>",
+        "{href}"
+      );
+    }
+    // 缺少分号的引用不解码，& 被转义后浏览器同样不会解码
+    assert_eq!(
+      sanitize_html("<a href=\"&#106avascript:alert(1)\">x</a>"),
+      "<a href=\"&amp;#106avascript:alert(1)\">x</a>"
+    );
+  }
+
+  #[test]
+  fn first_data_image_src_skips_non_ascii_markup() {
+    let html = "<p é>图片</p><img 中 src=\"data:image/png;base64,AAAA\">";
+    assert_eq!(
+      first_data_image_src(html).as_deref(),
+      Some("data:image/png;base64,AAAA")
+    );
+    assert_eq!(
+      first_data_image_src("<img src=\"https://example.com/a.png\">"),
+      None
+    );
+  }
+}
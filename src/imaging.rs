@@ -0,0 +1,591 @@
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::metadata::Orientation;
+use image::{AnimationDecoder, DynamicImage, ImageDecoder, ImageReader, ImageResult};
+use qrcode::types::{Color, QrError};
+use qrcode::QrCode;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+/// 根据文件头识别图片的 MIME 类型
+pub(crate) fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+  if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+    return Some("image/png");
+  }
+  if bytes.starts_with(b"\xff\xd8\xff") {
+    return Some("image/jpeg");
+  }
+  if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+    return Some("image/gif");
+  }
+  if bytes.starts_with(b"BM") {
+    return Some("image/bmp");
+  }
+  if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+    return Some("image/webp");
+  }
+  if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+    return Some("image/tiff");
+  }
+  if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+    return match &bytes[8..12] {
+      b"heic" | b"heix" | b"hevc" | b"hevx" | b"heim" | b"heis" => Some("image/heic"),
+      b"mif1" | b"msf1" => Some("image/heif"),
+      b"avif" | b"avis" => Some("image/avif"),
+      _ => None,
+    };
+  }
+  None
+}
+
+/// 获取图片 MIME 类型对应的文件扩展名
+pub(crate) fn image_mime_extension(mime: &str) -> &'static str {
+  match mime {
+    "image/jpeg" => "jpg",
+    "image/gif" => "gif",
+    "image/bmp" => "bmp",
+    "image/webp" => "webp",
+    "image/tiff" => "tiff",
+    "image/heic" => "heic",
+    "image/heif" => "heif",
+    "image/avif" => "avif",
+    _ => "png",
+  }
+}
+
+/// 解码 HEIC/HEIF 图片的主图像为 RGBA 图片
+#[cfg(feature = "heic")]
+pub(crate) fn decode_heic(bytes: &[u8]) -> Result<DynamicImage, String> {
+  use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+  let lib_heif = LibHeif::new();
+  let context = HeifContext::read_from_bytes(bytes).map_err(|e| e.to_string())?;
+  let handle = context.primary_image_handle().map_err(|e| e.to_string())?;
+  let image = lib_heif
+    .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgba), None)
+    .map_err(|e| e.to_string())?;
+  let plane = image
+    .planes()
+    .interleaved
+    .ok_or_else(|| "Decoded HEIC image has no interleaved plane".to_string())?;
+
+  // 每行数据可能带有对齐填充，按 stride 逐行拷贝有效像素
+  let row_len = plane.width as usize * 4;
+  let mut pixels = Vec::with_capacity(row_len * plane.height as usize);
+  for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+    pixels.extend_from_slice(row.get(..row_len).ok_or("Decoded HEIC row is truncated")?);
+  }
+  image::RgbaImage::from_raw(plane.width, plane.height, pixels)
+    .map(DynamicImage::ImageRgba8)
+    .ok_or_else(|| "Decoded HEIC image has an invalid size".to_string())
+}
+
+/// 未启用 `heic` 特性时无法解码 HEIC/HEIF 图片
+#[cfg(not(feature = "heic"))]
+pub(crate) fn decode_heic(_bytes: &[u8]) -> Result<DynamicImage, String> {
+  Err("HEIC support is not compiled in; rebuild with the `heic` feature".to_string())
+}
+
+/// 解码图片并按 EXIF 方向信息旋转或翻转，使其正向显示；没有方向信息或无法读取时保持原样
+pub(crate) fn decode_oriented(bytes: &[u8]) -> ImageResult<DynamicImage> {
+  let mut decoder = ImageReader::new(Cursor::new(bytes))
+    .with_guessed_format()?
+    .into_decoder()?;
+  let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+  let mut image = DynamicImage::from_decoder(decoder)?;
+  image.apply_orientation(orientation);
+  Ok(image)
+}
+
+/// 二维码每个模块的像素边长
+const QR_MODULE_SIZE: u32 = 8;
+/// 二维码四周空白区域的模块数（规范要求至少 4 个）
+const QR_QUIET_ZONE: u32 = 4;
+
+/// 将数据编码为黑白二维码图片，数据超出二维码容量时返回错误
+pub(crate) fn render_qr_code(data: &[u8]) -> Result<DynamicImage, QrError> {
+  let code = QrCode::new(data)?;
+  let modules = code.width() as u32;
+  let colors = code.to_colors();
+  let size = (modules + QR_QUIET_ZONE * 2) * QR_MODULE_SIZE;
+  let image = image::GrayImage::from_fn(size, size, |x, y| {
+    let (column, row) = (x / QR_MODULE_SIZE, y / QR_MODULE_SIZE);
+    let is_dark = (QR_QUIET_ZONE..QR_QUIET_ZONE + modules).contains(&column)
+      && (QR_QUIET_ZONE..QR_QUIET_ZONE + modules).contains(&row)
+      && colors[((row - QR_QUIET_ZONE) * modules + column - QR_QUIET_ZONE) as usize] == Color::Dark;
+    image::Luma([if is_dark { 0 } else { 255 }])
+  });
+  Ok(DynamicImage::ImageLuma8(image))
+}
+
+/// 动画图片的帧信息
+pub(crate) struct AnimationSummary {
+  /// 帧数，静态图片为 1
+  pub frame_count: u32,
+  /// 所有帧的总时长（毫秒），静态图片为 0
+  pub total_duration_ms: f64,
+}
+
+impl AnimationSummary {
+  fn still() -> Self {
+    AnimationSummary {
+      frame_count: 1,
+      total_duration_ms: 0.0,
+    }
+  }
+
+  pub(crate) fn is_animated(&self) -> bool {
+    self.frame_count > 1
+  }
+}
+
+fn summarize_frames<'a>(decoder: impl AnimationDecoder<'a>) -> ImageResult<AnimationSummary> {
+  let mut frame_count = 0u32;
+  let mut total_duration_ms = 0.0;
+  for frame in decoder.into_frames() {
+    let (numer, denom) = frame?.delay().numer_denom_ms();
+    frame_count += 1;
+    if denom > 0 {
+      total_duration_ms += f64::from(numer) / f64::from(denom);
+    }
+  }
+
+  if frame_count == 0 {
+    return Ok(AnimationSummary::still());
+  }
+  Ok(AnimationSummary {
+    frame_count,
+    total_duration_ms,
+  })
+}
+
+/// 分析图片的动画帧信息，支持 GIF、APNG 和 WebP，其他格式视为静态图片
+pub(crate) fn analyze_animation(bytes: &[u8]) -> ImageResult<AnimationSummary> {
+  match sniff_image_mime(bytes) {
+    Some("image/gif") => summarize_frames(GifDecoder::new(Cursor::new(bytes))?),
+    Some("image/png") => {
+      let decoder = PngDecoder::new(Cursor::new(bytes))?;
+      if decoder.is_apng()? {
+        summarize_frames(decoder.apng()?)
+      } else {
+        Ok(AnimationSummary::still())
+      }
+    }
+    Some("image/webp") => {
+      let decoder = WebPDecoder::new(Cursor::new(bytes))?;
+      if decoder.has_animation() {
+        summarize_frames(decoder)
+      } else {
+        Ok(AnimationSummary::still())
+      }
+    }
+    _ => Ok(AnimationSummary::still()),
+  }
+}
+
+/// 检查 TIFF 数据是否包含多个页面（IFD）
+pub(crate) fn is_multi_page_tiff(bytes: &[u8]) -> bool {
+  let little_endian = match bytes.get(0..4) {
+    Some(b"II*\0") => true,
+    Some(b"MM\0*") => false,
+    _ => return false,
+  };
+  // 偏移量来自文件内容，使用 checked 运算避免构造的数据导致溢出
+  let read_u16 = |offset: usize| {
+    bytes.get(offset..offset.checked_add(2)?).map(|b| {
+      let raw = [b[0], b[1]];
+      if little_endian {
+        u16::from_le_bytes(raw)
+      } else {
+        u16::from_be_bytes(raw)
+      }
+    })
+  };
+  let read_u32 = |offset: usize| {
+    bytes.get(offset..offset.checked_add(4)?).map(|b| {
+      let raw = [b[0], b[1], b[2], b[3]];
+      if little_endian {
+        u32::from_le_bytes(raw)
+      } else {
+        u32::from_be_bytes(raw)
+      }
+    })
+  };
+
+  let Some(first_ifd) = read_u32(4) else {
+    return false;
+  };
+  let Some(entry_count) = read_u16(first_ifd as usize) else {
+    return false;
+  };
+  let Some(next_ifd_offset) = (entry_count as usize)
+    .checked_mul(12)
+    .and_then(|entries_size| entries_size.checked_add(2))
+    .and_then(|ifd_size| ifd_size.checked_add(first_ifd as usize))
+  else {
+    return false;
+  };
+  matches!(read_u32(next_ifd_offset), Some(next) if next != 0)
+}
+
+/// 计算完全透明（alpha 为 0）的像素占全部像素的比例，没有 alpha 通道的图片返回 0
+pub(crate) fn transparency_ratio(image: &DynamicImage) -> f64 {
+  let pixel_count = u64::from(image.width()) * u64::from(image.height());
+  if !image.color().has_alpha() || pixel_count == 0 {
+    return 0.0;
+  }
+  let transparent = match image {
+    DynamicImage::ImageRgba8(buffer) => buffer.pixels().filter(|p| p.0[3] == 0).count(),
+    DynamicImage::ImageLumaA8(buffer) => buffer.pixels().filter(|p| p.0[1] == 0).count(),
+    _ => image.to_rgba16().pixels().filter(|p| p.0[3] == 0).count(),
+  };
+  transparent as f64 / pixel_count as f64
+}
+
+/// 将图片按 alpha 混合到纯色背景上，返回不含 alpha 通道的 RGB 图片
+pub(crate) fn flatten_onto(image: &DynamicImage, background: [u8; 3]) -> DynamicImage {
+  let rgba = image.to_rgba8();
+  let flattened = image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+    let [r, g, b, a] = rgba.get_pixel(x, y).0;
+    let blend = |foreground: u8, background: u8| {
+      let alpha = u32::from(a);
+      ((u32::from(foreground) * alpha + u32::from(background) * (255 - alpha) + 127) / 255) as u8
+    };
+    image::Rgb([
+      blend(r, background[0]),
+      blend(g, background[1]),
+      blend(b, background[2]),
+    ])
+  });
+  DynamicImage::ImageRgb8(flattened)
+}
+
+/// 差异哈希的边长，哈希共 DIFFERENCE_HASH_SIZE² 位
+const DIFFERENCE_HASH_SIZE: u32 = 16;
+
+/// 计算图片的差异哈希（dHash）：缩放为 (N+1)×N 的灰度图，逐行比较相邻像素的亮度
+fn difference_hash(image: &DynamicImage) -> Vec<bool> {
+  let gray = image
+    .resize_exact(
+      DIFFERENCE_HASH_SIZE + 1,
+      DIFFERENCE_HASH_SIZE,
+      image::imageops::FilterType::Triangle,
+    )
+    .to_luma8();
+  let mut bits = Vec::with_capacity((DIFFERENCE_HASH_SIZE * DIFFERENCE_HASH_SIZE) as usize);
+  for y in 0..DIFFERENCE_HASH_SIZE {
+    for x in 0..DIFFERENCE_HASH_SIZE {
+      bits.push(gray.get_pixel(x, y).0[0] < gray.get_pixel(x + 1, y).0[0]);
+    }
+  }
+  bits
+}
+
+/// 基于差异哈希计算两张图片的相似度（0.0–1.0），1.0 表示视觉上相同
+/// 对缩放、轻微压缩失真和色彩调整不敏感，适合识别近似重复的图片
+pub(crate) fn perceptual_similarity(a: &DynamicImage, b: &DynamicImage) -> f64 {
+  let (a, b) = (difference_hash(a), difference_hash(b));
+  let matching = a.iter().zip(&b).filter(|(x, y)| x == y).count();
+  matching as f64 / a.len() as f64
+}
+
+/// 计算主色调时缩略图的最大边长
+const DOMINANT_COLOR_SAMPLE_SIZE: u32 = 64;
+
+/// 计算图片的主色调：缩小后按每通道 4 位量化统计像素数量，取数量最多的颜色区间的平均色
+/// 近乎透明的像素不参与统计，全部透明时对所有像素取平均
+pub(crate) fn dominant_color(image: &DynamicImage) -> [u8; 3] {
+  let sample = image
+    .thumbnail(DOMINANT_COLOR_SAMPLE_SIZE, DOMINANT_COLOR_SAMPLE_SIZE)
+    .to_rgba8();
+
+  // 每个区间累计 [像素数, R 之和, G 之和, B 之和]
+  let mut buckets = vec![[0u64; 4]; 4096];
+  let mut all = [0u64; 4];
+  for pixel in sample.pixels() {
+    let [r, g, b, a] = pixel.0;
+    let channels = [1, u64::from(r), u64::from(g), u64::from(b)];
+    for (total, value) in all.iter_mut().zip(channels) {
+      *total += value;
+    }
+    if a < 16 {
+      continue;
+    }
+    let index = (usize::from(r >> 4) << 8) | (usize::from(g >> 4) << 4) | usize::from(b >> 4);
+    for (total, value) in buckets[index].iter_mut().zip(channels) {
+      *total += value;
+    }
+  }
+
+  let best = buckets
+    .iter()
+    .max_by_key(|bucket| bucket[0])
+    .filter(|bucket| bucket[0] > 0)
+    .unwrap_or(&all);
+  let count = best[0].max(1);
+  [
+    (best[1] / count) as u8,
+    (best[2] / count) as u8,
+    (best[3] / count) as u8,
+  ]
+}
+
+/// 将图片切分为 rows × cols 个图块，按行优先顺序返回
+/// 尺寸不能整除时按比例划分边界，余下的像素分散到各图块，相邻图块的尺寸至多相差 1 像素
+/// 调用方需保证 rows 与 cols 不为 0 且不超过图片的高度与宽度
+pub(crate) fn split_tiles(image: &DynamicImage, rows: u32, cols: u32) -> Vec<DynamicImage> {
+  let boundary = |index: u32, count: u32, length: u32| {
+    (u64::from(index) * u64::from(length) / u64::from(count)) as u32
+  };
+  let mut tiles = Vec::with_capacity(rows as usize * cols as usize);
+  for row in 0..rows {
+    let (top, bottom) = (
+      boundary(row, rows, image.height()),
+      boundary(row + 1, rows, image.height()),
+    );
+    for col in 0..cols {
+      let (left, right) = (
+        boundary(col, cols, image.width()),
+        boundary(col + 1, cols, image.width()),
+      );
+      tiles.push(image.crop_imm(left, top, right - left, bottom - top));
+    }
+  }
+  tiles
+}
+
+/// 将图片数据写入目录，目录不存在时自动创建
+/// 文件名为 `<timestamp>.<扩展名>`，扩展名按数据的文件头识别（无法识别时为 png），而不是固定为 png；
+/// 同名文件已存在时依次尝试 `<timestamp>-1`、`<timestamp>-2` 等名称，不会覆盖已有文件
+pub(crate) fn save_image_file(
+  dir: &Path,
+  image: &[u8],
+  timestamp: u128,
+) -> std::io::Result<PathBuf> {
+  std::fs::create_dir_all(dir)?;
+  let extension = image_mime_extension(sniff_image_mime(image).unwrap_or("image/png"));
+
+  let mut attempt = 0u32;
+  loop {
+    let name = if attempt == 0 {
+      format!("{timestamp}.{extension}")
+    } else {
+      format!("{timestamp}-{attempt}.{extension}")
+    };
+    let path = dir.join(name);
+    match std::fs::OpenOptions::new()
+      .write(true)
+      .create_new(true)
+      .open(&path)
+    {
+      Ok(mut file) => {
+        file.write_all(image)?;
+        return Ok(path);
+      }
+      Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+      Err(e) => return Err(e),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::codecs::gif::GifEncoder;
+  use image::{Delay, Frame, ImageFormat, Rgb, RgbImage, Rgba, RgbaImage};
+
+  fn encode(image: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image
+      .write_to(&mut Cursor::new(&mut bytes), format)
+      .unwrap();
+    bytes
+  }
+
+  /// 构造只有一个空 IFD 的 TIFF 头，next_ifd 为下一个 IFD 的偏移量
+  fn tiff_header(next_ifd: u32) -> Vec<u8> {
+    let mut bytes = b"II*\0".to_vec();
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&next_ifd.to_le_bytes());
+    bytes
+  }
+
+  #[test]
+  fn sniffs_image_mime_types() {
+    assert_eq!(
+      sniff_image_mime(b"\x89PNG\r\n\x1a\n...."),
+      Some("image/png")
+    );
+    assert_eq!(sniff_image_mime(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(sniff_image_mime(b"GIF89a"), Some("image/gif"));
+    assert_eq!(sniff_image_mime(b"RIFF\0\0\0\0WEBP"), Some("image/webp"));
+    assert_eq!(sniff_image_mime(b"MM\0*"), Some("image/tiff"));
+    assert_eq!(sniff_image_mime(b"\0\0\0\x18ftypheic"), Some("image/heic"));
+    assert_eq!(sniff_image_mime(b"\0\0\0\x18ftypmif1"), Some("image/heif"));
+    assert_eq!(sniff_image_mime(b"\0\0\0\x18ftypavif"), Some("image/avif"));
+    assert_eq!(sniff_image_mime(b"\0\0\0\x18ftypisom"), None);
+    assert_eq!(sniff_image_mime(b"RIFF"), None);
+    assert_eq!(image_mime_extension("image/jpeg"), "jpg");
+    assert_eq!(image_mime_extension("image/x-unknown"), "png");
+  }
+
+  #[test]
+  fn detects_multi_page_tiff() {
+    assert!(!is_multi_page_tiff(&tiff_header(0)));
+    assert!(is_multi_page_tiff(&tiff_header(18)));
+    // 偏移量越界或数据截断时视为单页
+    let mut out_of_range = tiff_header(0);
+    out_of_range[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(!is_multi_page_tiff(&out_of_range));
+    assert!(!is_multi_page_tiff(&tiff_header(18)[..12]));
+    assert!(!is_multi_page_tiff(b"not a tiff"));
+  }
+
+  #[test]
+  fn analyzes_animation_frames() {
+    let mut gif = Vec::new();
+    {
+      let mut encoder = GifEncoder::new(&mut gif);
+      for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+        let frame = Frame::from_parts(
+          RgbaImage::from_pixel(2, 2, Rgba(color)),
+          0,
+          0,
+          Delay::from_numer_denom_ms(100, 1),
+        );
+        encoder.encode_frame(frame).unwrap();
+      }
+    }
+    let animation = analyze_animation(&gif).unwrap();
+    assert!(animation.is_animated());
+    assert_eq!(animation.frame_count, 2);
+    assert_eq!(animation.total_duration_ms, 200.0);
+
+    let png = encode(
+      &DynamicImage::ImageRgb8(RgbImage::new(2, 2)),
+      ImageFormat::Png,
+    );
+    let still = analyze_animation(&png).unwrap();
+    assert!(!still.is_animated());
+    assert_eq!(still.total_duration_ms, 0.0);
+    assert!(!analyze_animation(b"not an image").unwrap().is_animated());
+  }
+
+  #[test]
+  fn measures_transparency_and_flattens_onto_background() {
+    let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(2, 2, |x, y| {
+      Rgba([255, 0, 0, [[0, 128], [255, 255]][y as usize][x as usize]])
+    }));
+    assert_eq!(transparency_ratio(&image), 0.25);
+    assert_eq!(
+      transparency_ratio(&DynamicImage::ImageRgb8(RgbImage::new(2, 2))),
+      0.0
+    );
+
+    let flattened = flatten_onto(&image, [255, 255, 255]).to_rgb8();
+    assert_eq!(flattened.get_pixel(0, 0), &Rgb([255, 255, 255]));
+    assert_eq!(flattened.get_pixel(1, 0), &Rgb([255, 127, 127]));
+    assert_eq!(flattened.get_pixel(0, 1), &Rgb([255, 0, 0]));
+  }
+
+  #[test]
+  fn scores_perceptual_similarity() {
+    let gradient = DynamicImage::ImageRgb8(RgbImage::from_fn(64, 64, |x, _| {
+      Rgb([(x * 4) as u8, (x * 4) as u8, (x * 4) as u8])
+    }));
+    let mut reversed = gradient.clone();
+    reversed.invert();
+    let scaled = gradient.resize_exact(32, 32, image::imageops::FilterType::Triangle);
+    assert_eq!(perceptual_similarity(&gradient, &gradient), 1.0);
+    assert!(perceptual_similarity(&gradient, &scaled) > 0.9);
+    assert!(perceptual_similarity(&gradient, &reversed) < 0.5);
+  }
+
+  #[test]
+  fn picks_the_most_common_color() {
+    let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(64, 64, |x, _| {
+      if x < 48 {
+        Rgba([200, 30, 60, 255])
+      } else {
+        Rgba([0, 0, 255, 255])
+      }
+    }));
+    assert_eq!(dominant_color(&image), [200, 30, 60]);
+    // 全部透明时对所有像素取平均
+    let transparent = DynamicImage::ImageRgba8(RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 0])));
+    assert_eq!(dominant_color(&transparent), [10, 20, 30]);
+  }
+
+  #[test]
+  fn splits_tiles_with_balanced_sizes() {
+    let tiles = split_tiles(&DynamicImage::ImageRgb8(RgbImage::new(5, 3)), 2, 2);
+    let sizes: Vec<_> = tiles
+      .iter()
+      .map(|tile| (tile.width(), tile.height()))
+      .collect();
+    assert_eq!(sizes, [(2, 1), (3, 1), (2, 2), (3, 2)]);
+  }
+
+  #[test]
+  fn applies_exif_orientation() {
+    let jpeg = encode(
+      &DynamicImage::ImageRgb8(RgbImage::new(4, 2)),
+      ImageFormat::Jpeg,
+    );
+    // 大端 TIFF 头，IFD 中只有 Orientation（0x0112）= 6，即顺时针旋转 90°
+    let mut exif =
+      b"Exif\0\0MM\0*\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0\x06\0\0\0\0\0\0".to_vec();
+    let mut oriented = jpeg[..2].to_vec();
+    oriented.extend_from_slice(&[0xff, 0xe1]);
+    oriented.extend_from_slice(&(exif.len() as u16 + 2).to_be_bytes());
+    oriented.append(&mut exif);
+    oriented.extend_from_slice(&jpeg[2..]);
+
+    let image = decode_oriented(&oriented).unwrap();
+    assert_eq!((image.width(), image.height()), (2, 4));
+    let plain = decode_oriented(&jpeg).unwrap();
+    assert_eq!((plain.width(), plain.height()), (4, 2));
+  }
+
+  #[test]
+  fn save_image_file_appends_a_suffix_on_collision() {
+    let dir = std::env::temp_dir().join(format!("save-image-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let png = encode(
+      &DynamicImage::ImageRgb8(RgbImage::new(1, 1)),
+      ImageFormat::Png,
+    );
+    let jpeg = encode(
+      &DynamicImage::ImageRgb8(RgbImage::new(1, 1)),
+      ImageFormat::Jpeg,
+    );
+
+    let first = save_image_file(&dir, &png, 1700000000000).unwrap();
+    let second = save_image_file(&dir, &png, 1700000000000).unwrap();
+    let third = save_image_file(&dir, &png, 1700000000000).unwrap();
+    assert_eq!(first, dir.join("1700000000000.png"));
+    assert_eq!(second, dir.join("1700000000000-1.png"));
+    assert_eq!(third, dir.join("1700000000000-2.png"));
+    // 扩展名按数据识别，不同扩展名的文件不算冲突
+    assert_eq!(
+      save_image_file(&dir, &jpeg, 1700000000000).unwrap(),
+      dir.join("1700000000000.jpg")
+    );
+    assert_eq!(std::fs::read(&second).unwrap(), png);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn renders_qr_code_with_quiet_zone() {
+    let image = render_qr_code(b"https://example.com").unwrap().to_luma8();
+    assert_eq!(image.width(), image.height());
+    assert_eq!(image.width() % QR_MODULE_SIZE, 0);
+    // 四周空白区域为白色，定位图案左上角为黑色
+    let quiet = QR_QUIET_ZONE * QR_MODULE_SIZE;
+    assert_eq!(image.get_pixel(quiet - 1, quiet - 1).0, [255]);
+    assert_eq!(image.get_pixel(quiet, quiet).0, [0]);
+    assert!(render_qr_code(&vec![b'a'; 4000]).is_err());
+  }
+}
@@ -0,0 +1,92 @@
+use whatlang::Lang;
+
+/// 参与语言检测的最少字符数（不含空白），更短的文本不做检测
+const MIN_DETECTION_CHARS: usize = 5;
+/// 采用检测结果所需的最低置信度；whatlang 的 is_reliable 阈值对短句过于严格
+const MIN_CONFIDENCE: f64 = 0.15;
+
+/// 将 whatlang 的语言转换为 ISO 639-1 代码
+fn iso_639_1(lang: Lang) -> &'static str {
+  match lang {
+    Lang::Epo => "eo",
+    Lang::Eng => "en",
+    Lang::Rus => "ru",
+    Lang::Cmn => "zh",
+    Lang::Spa => "es",
+    Lang::Por => "pt",
+    Lang::Ita => "it",
+    Lang::Ben => "bn",
+    Lang::Fra => "fr",
+    Lang::Deu => "de",
+    Lang::Ukr => "uk",
+    Lang::Kat => "ka",
+    Lang::Ara => "ar",
+    Lang::Hin => "hi",
+    Lang::Jpn => "ja",
+    Lang::Heb => "he",
+    Lang::Yid => "yi",
+    Lang::Pol => "pl",
+    Lang::Amh => "am",
+    Lang::Jav => "jv",
+    Lang::Kor => "ko",
+    Lang::Nob => "nb",
+    Lang::Dan => "da",
+    Lang::Swe => "sv",
+    Lang::Fin => "fi",
+    Lang::Tur => "tr",
+    Lang::Nld => "nl",
+    Lang::Hun => "hu",
+    Lang::Ces => "cs",
+    Lang::Ell => "el",
+    Lang::Bul => "bg",
+    Lang::Bel => "be",
+    Lang::Mar => "mr",
+    Lang::Kan => "kn",
+    Lang::Ron => "ro",
+    Lang::Slv => "sl",
+    Lang::Hrv => "hr",
+    Lang::Srp => "sr",
+    Lang::Mkd => "mk",
+    Lang::Lit => "lt",
+    Lang::Lav => "lv",
+    Lang::Est => "et",
+    Lang::Tam => "ta",
+    Lang::Vie => "vi",
+    Lang::Urd => "ur",
+    Lang::Tha => "th",
+    Lang::Guj => "gu",
+    Lang::Uzb => "uz",
+    Lang::Pan => "pa",
+    Lang::Aze => "az",
+    Lang::Ind => "id",
+    Lang::Tel => "te",
+    Lang::Pes => "fa",
+    Lang::Mal => "ml",
+    Lang::Ori => "or",
+    Lang::Mya => "my",
+    Lang::Nep => "ne",
+    Lang::Sin => "si",
+    Lang::Khm => "km",
+    Lang::Tuk => "tk",
+    Lang::Aka => "ak",
+    Lang::Zul => "zu",
+    Lang::Sna => "sn",
+    Lang::Afr => "af",
+    Lang::Lat => "la",
+    Lang::Slk => "sk",
+    Lang::Cat => "ca",
+    Lang::Tgl => "tl",
+    Lang::Hye => "hy",
+  }
+}
+
+/// 检测文本的语言，返回 ISO 639-1 代码；文本过短或置信度过低时返回 None
+pub(crate) fn detect_language(text: &str) -> Option<&'static str> {
+  let significant_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+  if significant_chars < MIN_DETECTION_CHARS {
+    return None;
+  }
+  whatlang::detect(text)
+    .filter(|info| info.confidence() >= MIN_CONFIDENCE)
+    .map(|info| iso_639_1(info.lang()))
+}
@@ -6,14 +6,34 @@ use clipboard_rs::{
   Clipboard, ClipboardContext, ClipboardHandler, ClipboardWatcher, ClipboardWatcherContext,
   ContentFormat,
 };
+use image::codecs::jpeg::JpegEncoder;
+use image::DynamicImage;
 use napi::bindgen_prelude::*;
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
-use std::sync::OnceLock;
+use sha2::{Digest, Sha256};
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
 use std::thread;
-
+use std::time::Duration;
+
+mod archive;
+mod html;
+mod imaging;
+mod language;
+mod lock;
+#[cfg(target_os = "macos")]
+mod macos;
+mod rtf;
+mod test_mode;
 #[cfg(target_os = "linux")]
 mod wayland;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "linux")]
+mod x11;
 
 pub(crate) fn is_debug_logging_enabled() -> bool {
   static ENABLED: OnceLock<bool> = OnceLock::new();
@@ -66,6 +86,102 @@ pub fn is_wayland_clipboard_available() -> bool {
   }
 }
 
+/// 剪贴板健康状态，用于诊断无显示服务、无头 CI 等环境下的可用性
+#[napi(object)]
+pub struct HealthStatus {
+  /// 剪贴板后端是否可用
+  pub available: bool,
+  /// 是否可以读取剪贴板
+  pub can_read: bool,
+  /// 是否可以写入剪贴板（根据后端初始化结果推断，不会实际写入）
+  pub can_write: bool,
+  /// 当前使用的后端（wayland、x11、windows、macos）
+  pub backend: String,
+  /// 检测失败时的错误信息
+  pub error: Option<String>,
+}
+
+fn clipboard_backend_name() -> &'static str {
+  if is_wayland_environment() {
+    "wayland"
+  } else if cfg!(target_os = "windows") {
+    "windows"
+  } else if cfg!(target_os = "macos") {
+    "macos"
+  } else {
+    "x11"
+  }
+}
+
+/// 检测剪贴板是否可用
+///
+/// 尝试创建剪贴板上下文并读取可用格式列表，不会修改剪贴板内容
+#[napi]
+pub fn check_clipboard_health() -> HealthStatus {
+  let backend = clipboard_backend_name().to_string();
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    if !wayland::is_wayland_clipboard_available() {
+      return HealthStatus {
+        available: false,
+        can_read: false,
+        can_write: false,
+        backend,
+        error: Some("Wayland clipboard is unavailable in current environment".to_string()),
+      };
+    }
+
+    return match wayland::get_available_formats() {
+      Ok(_) => HealthStatus {
+        available: true,
+        can_read: true,
+        can_write: true,
+        backend,
+        error: None,
+      },
+      Err(e) => HealthStatus {
+        available: true,
+        can_read: false,
+        can_write: true,
+        backend,
+        error: Some(format!("Failed to get available formats: {e}")),
+      },
+    };
+  }
+
+  let context = match ClipboardContext::new() {
+    Ok(context) => context,
+    Err(e) => {
+      return HealthStatus {
+        available: false,
+        can_read: false,
+        can_write: false,
+        backend,
+        error: Some(format!("Failed to create clipboard context: {e}")),
+      }
+    }
+  };
+
+  let _lock = lock::acquire();
+  match context.available_formats() {
+    Ok(_) => HealthStatus {
+      available: true,
+      can_read: true,
+      can_write: true,
+      backend,
+      error: None,
+    },
+    Err(e) => HealthStatus {
+      available: true,
+      can_read: false,
+      can_write: true,
+      backend,
+      error: Some(format!("Failed to get available formats: {e}")),
+    },
+  }
+}
+
 /// 图片数据结构，包含图片的详细信息
 #[napi(object)]
 pub struct ImageData {
@@ -79,8 +195,22 @@ pub struct ImageData {
   pub data: Buffer,
 }
 
+/// 按指定格式编码的图片数据
+#[napi(object)]
+pub struct EncodedImage {
+  /// 请求的编码格式，与传入的格式描述相同
+  pub format: String,
+  /// 编码后的图片数据
+  pub data: Buffer,
+  /// 数据大小（字节）
+  pub size: u32,
+}
+
 /// 剪贴板数据结构，包含所有可用格式的数据
+/// 同时作为 setContents 等写入接口的参数；previous*、isLikelyScreenshot、selection、origin、partial
+/// 与 savedImage* 只出现在读取结果和监听器事件中，写入时被忽略
 #[napi(object)]
+#[derive(Default)]
 pub struct ClipboardData {
   /// 可用的格式列表
   pub available_formats: Vec<String>,
@@ -94,20 +224,683 @@ pub struct ClipboardData {
   pub image: Option<ImageData>,
   /// 文件列表
   pub files: Option<Vec<String>>,
+  /// 变化前的纯文本内容（仅监听器事件，首个事件为空）
+  pub previous_text: Option<String>,
+  /// 变化前的 RTF 富文本内容（仅监听器事件，首个事件为空）
+  pub previous_rtf: Option<String>,
+  /// 变化前的 HTML 内容（仅监听器事件，首个事件为空）
+  pub previous_html: Option<String>,
+  /// 变化前的图片数据（仅监听器事件，首个事件为空）
+  pub previous_image: Option<ImageData>,
+  /// 变化前的文件列表（仅监听器事件，首个事件为空）
+  pub previous_files: Option<Vec<String>>,
+  /// 是否可能为截图（仅在监听选项开启 detectScreenshots 时提供）
+  pub is_likely_screenshot: Option<bool>,
+  /// 事件来源的选区：clipboard 或 primary（仅监听器事件）
+  pub selection: Option<String>,
+  /// 变化的来源（仅监听器事件，尽力判断）：复制时剪贴板所有者属于前台窗口所在进程为 `user`，
+  /// 否则为 `programmatic`；平台没有可用信号（macOS、Wayland、PRIMARY 选区）时为 `unknown`
+  pub origin: Option<String>,
+  /// 是否因超出时间预算而只读取了部分格式，未读取的格式为空（仅 getFullClipboardDataBudgetedAsync）
+  pub partial: Option<bool>,
+  /// 自动保存的图片文件路径（仅监听选项开启 autoSaveImagesDir 且事件包含图片时提供）
+  pub saved_image_path: Option<String>,
+  /// 自动保存图片失败的原因，此时 savedImagePath 为空
+  pub saved_image_error: Option<String>,
+}
+
+/// 规范化 hasFormat 等接口接受的格式名称（`rich_text` 视为 `rtf`），不支持的格式返回 InvalidArg
+fn normalize_format_query(format: &str) -> Result<&'static str> {
+  match format {
+    "text" => Ok("text"),
+    "html" => Ok("html"),
+    "rtf" | "rich_text" => Ok("rtf"),
+    "image" => Ok("image"),
+    "files" => Ok("files"),
+    _ => Err(Error::new(
+      Status::InvalidArg,
+      format!("Unsupported format: {format}"),
+    )),
+  }
+}
+
+/// 压缩图片到指定大小时依次尝试的 JPEG 质量
+const BUDGET_JPEG_QUALITIES: [u8; 5] = [85, 70, 55, 40, 25];
+/// 压缩图片时每轮缩小的比例及允许的最小边长（像素）
+const BUDGET_SCALE_STEP: f64 = 0.75;
+const BUDGET_MIN_DIMENSION: u32 = 16;
+
+/// 等待格式出现时的轮询间隔
+const WAIT_FOR_FORMAT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 固定文本时检查剪贴板变化的间隔
+const PIN_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// setTextUntilReplaced 检查剪贴板是否被替换的间隔
+const REPLACE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 各平台 HTML 数据的原生格式名称
+#[cfg(target_os = "windows")]
+const NATIVE_HTML_FORMAT: &str = "HTML Format";
+#[cfg(target_os = "macos")]
+const NATIVE_HTML_FORMAT: &str = "public.html";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const NATIVE_HTML_FORMAT: &str = "text/html";
+
+/// 图片 MIME 类型对应的平台原生图片格式名称，平台没有对应格式时返回 None
+#[cfg(target_os = "windows")]
+fn native_image_format(mime: &str) -> Option<&'static str> {
+  match mime {
+    "image/png" => Some("PNG"),
+    "image/jpeg" => Some("JFIF"),
+    "image/gif" => Some("GIF"),
+    _ => None,
+  }
+}
+#[cfg(target_os = "macos")]
+fn native_image_format(mime: &str) -> Option<&'static str> {
+  match mime {
+    "image/png" => Some("public.png"),
+    "image/jpeg" => Some("public.jpeg"),
+    "image/tiff" => Some("public.tiff"),
+    "image/gif" => Some("com.compuserve.gif"),
+    "image/bmp" => Some("com.microsoft.bmp"),
+    "image/heic" => Some("public.heic"),
+    _ => None,
+  }
+}
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn native_image_format(mime: &'static str) -> Option<&'static str> {
+  // X11 与 Wayland 直接使用 MIME 类型作为格式名称
+  Some(mime)
+}
+
+/// 剪贴板格式及其数据大小
+#[napi(object)]
+pub struct FormatSize {
+  /// 格式名称
+  pub format: String,
+  /// 数据大小（字节）
+  pub size: f64,
+}
+
+/// 剪贴板格式及其内容哈希
+#[napi(object)]
+pub struct FormatHash {
+  /// 格式名称
+  pub format: String,
+  /// 内容的 SHA-256 十六进制字符串
+  pub hash: String,
+}
+
+/// 标准格式名称（text、rtf、html、image、files）
+const STANDARD_FORMATS: [&str; 5] = ["text", "rtf", "html", "image", "files"];
+
+fn is_standard_format_name(format: &str) -> bool {
+  STANDARD_FORMATS.contains(&format)
+}
+
+/// 校验并去重标准格式名称列表，空列表表示全部标准格式
+fn parse_standard_formats(formats: &[String]) -> Result<Vec<&'static str>> {
+  if formats.is_empty() {
+    return Ok(STANDARD_FORMATS.to_vec());
+  }
+
+  let mut parsed = Vec::new();
+  for format in formats {
+    let standard = STANDARD_FORMATS
+      .iter()
+      .find(|standard| **standard == format.as_str())
+      .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unsupported format: {format}")))?;
+    if !parsed.contains(standard) {
+      parsed.push(*standard);
+    }
+  }
+  Ok(parsed)
+}
+
+/// 将平台原生格式名称映射为标准格式名称（text、rtf、html、image、files）
+/// 无法映射的格式原样返回，X11 的 TARGETS 等元数据目标返回 None
+fn canonical_format_name(native: &str) -> Option<String> {
+  let normalized = native.trim().to_ascii_lowercase();
+  let canonical = match normalized.as_str() {
+    "targets" | "timestamp" | "multiple" | "save_targets" | "delete" => return None,
+    "text/plain"
+    | "text/plain;charset=utf-8"
+    | "utf8_string"
+    | "string"
+    | "text"
+    | "compound_text"
+    | "public.utf8-plain-text"
+    | "public.plain-text"
+    | "nsstringpboardtype"
+    | "cf_unicodetext"
+    | "cf_text"
+    | "cf_oemtext" => "text",
+    "text/html" | "public.html" | "html format" | "apple html pasteboard type" => "html",
+    "text/rtf"
+    | "text/richtext"
+    | "application/rtf"
+    | "application/x-rtf"
+    | "public.rtf"
+    | "rich text format"
+    | "nextstep rich text format v1.0 pasteboard type" => "rtf",
+    "public.png"
+    | "public.tiff"
+    | "public.jpeg"
+    | "public.heic"
+    | "public.heif"
+    | "nstiffpboardtype"
+    | "cf_dib"
+    | "cf_dibv5"
+    | "cf_bitmap"
+    | "png"
+    | "application/x-qt-image" => "image",
+    "text/uri-list"
+    | "x-special/gnome-copied-files"
+    | "x-special/nautilus-clipboard"
+    | "public.file-url"
+    | "nsfilenamespboardtype"
+    | "cf_hdrop" => "files",
+    other if other.starts_with("image/") => "image",
+    _ => return Some(native.to_string()),
+  };
+  Some(canonical.to_string())
+}
+
+/// 统计剪贴板数据中标准格式的大小
+/// 图片按 PNG 编码后的大小计算，文件列表按换行拼接后的大小计算
+fn standard_format_sizes(data: &ClipboardData) -> Vec<FormatSize> {
+  let mut sizes = Vec::new();
+  if let Some(text) = &data.text {
+    sizes.push(FormatSize {
+      format: "text".to_string(),
+      size: text.len() as f64,
+    });
+  }
+  if let Some(rtf) = &data.rtf {
+    sizes.push(FormatSize {
+      format: "rtf".to_string(),
+      size: rtf.len() as f64,
+    });
+  }
+  if let Some(html) = &data.html {
+    sizes.push(FormatSize {
+      format: "html".to_string(),
+      size: html.len() as f64,
+    });
+  }
+  if let Some(image) = &data.image {
+    sizes.push(FormatSize {
+      format: "image".to_string(),
+      size: image.data.len() as f64,
+    });
+  }
+  if let Some(files) = &data.files {
+    sizes.push(FormatSize {
+      format: "files".to_string(),
+      size: files.join("\n").len() as f64,
+    });
+  }
+  sizes
+}
+
+/// 获取系统剪贴板序列号，剪贴板内容每次变化时序列号都会改变
+/// Windows 使用剪贴板序列号，macOS 使用 NSPasteboard 的 changeCount，其他平台以及测试模式下返回 None
+fn clipboard_sequence_number() -> Option<u32> {
+  if test_mode::is_enabled() {
+    return None;
+  }
+
+  #[cfg(target_os = "windows")]
+  {
+    clipboard_win::raw::seq_num().map(|seq| seq.get())
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    Some(macos::general_pasteboard_change_count())
+  }
+
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  {
+    None
+  }
+}
+
+/// 带语言标记的文本
+#[napi(object)]
+pub struct LocalizedText {
+  /// 语言标记，例如 `en-US`、`zh-CN`
+  pub locale: String,
+  /// 文本内容
+  pub text: String,
+}
+
+/// 多语言文本使用的自定义格式前缀，完整格式为 `<前缀><locale>`
+const LOCALIZED_TEXT_FORMAT_PREFIX: &str = "application/x-clipboard-text;locale=";
+
+/// 获取指定语言文本对应的自定义格式名称
+fn localized_text_format(locale: &str) -> Result<String> {
+  let is_valid = !locale.is_empty()
+    && locale
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+  if !is_valid {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Invalid locale: {locale}"),
+    ));
+  }
+  Ok(format!("{LOCALIZED_TEXT_FORMAT_PREFIX}{locale}"))
+}
+
+/// 记录复制来源的自定义格式
+const SOURCE_FORMAT: &str = "application/x-clip-source";
+
+/// 记录同步标记（例如设备 ID）的自定义格式
+const MARKER_FORMAT: &str = "application/x-clip-marker";
+/// 附带应用自定义元数据（通常为 JSON 字符串）的自定义格式
+const METADATA_FORMAT: &str = "application/x-clip-meta";
+
+/// 保存文件 zip 归档的自定义格式
+const ZIP_ARCHIVE_FORMAT: &str = "application/zip";
+
+/// setWithPriority 中按顺序写入的一项内容
+#[napi(object)]
+pub struct PrioritizedContent {
+  /// 格式名称：text、html、rtf、image、files，其他名称作为自定义格式写入
+  pub format: String,
+  /// 文本内容；files 格式为每行一个文件路径，自定义格式按 UTF-8 写入
+  pub value: Option<String>,
+  /// 二进制内容，image 格式必须使用该字段，自定义格式可以使用该字段代替 value
+  pub data: Option<Buffer>,
+}
+
+/// 按顺序写入剪贴板的一项内容
+pub(crate) enum OrderedContent {
+  Text(String),
+  Html(String),
+  Rtf(String),
+  Image(Vec<u8>),
+  Files(Vec<String>),
+  Other(String, Vec<u8>),
+}
+
+impl OrderedContent {
+  /// 将 JS 传入的内容转换为对应的格式，缺少所需字段时返回错误
+  fn from_prioritized(content: PrioritizedContent) -> Result<Self> {
+    let PrioritizedContent {
+      format,
+      value,
+      data,
+    } = content;
+    let missing = |field: &str| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Format {format} requires a {field}"),
+      )
+    };
+    match format.as_str() {
+      "text" => Ok(OrderedContent::Text(value.ok_or_else(|| missing("value"))?)),
+      "html" => Ok(OrderedContent::Html(value.ok_or_else(|| missing("value"))?)),
+      "rtf" | "rich_text" => Ok(OrderedContent::Rtf(value.ok_or_else(|| missing("value"))?)),
+      "image" => Ok(OrderedContent::Image(
+        data.ok_or_else(|| missing("data buffer"))?.to_vec(),
+      )),
+      "files" => {
+        let files: Vec<String> = value
+          .ok_or_else(|| missing("value"))?
+          .lines()
+          .map(str::trim)
+          .filter(|line| !line.is_empty())
+          .map(str::to_string)
+          .collect();
+        Ok(OrderedContent::Files(files))
+      }
+      _ => {
+        let bytes = match (data, value) {
+          (Some(data), _) => data.to_vec(),
+          (None, Some(value)) => value.into_bytes(),
+          (None, None) => return Err(missing("value or data buffer")),
+        };
+        Ok(OrderedContent::Other(format, bytes))
+      }
+    }
+  }
+
+  /// 用于检测重复格式的名称
+  fn format_key(&self) -> &str {
+    match self {
+      OrderedContent::Text(_) => "text",
+      OrderedContent::Html(_) => "html",
+      OrderedContent::Rtf(_) => "rtf",
+      OrderedContent::Image(_) => "image",
+      OrderedContent::Files(_) => "files",
+      OrderedContent::Other(format, _) => format,
+    }
+  }
+}
+
+/// 可直接映射为 JS Blob/File 的图片数据
+#[napi(object)]
+pub struct BlobData {
+  /// 图片的实际 MIME 类型，例如 `image/png`
+  pub mime_type: String,
+  /// 图片数据
+  pub data: Buffer,
+  /// 建议的文件名，例如 `clipboard-1700000000000.png`
+  pub suggested_name: String,
+}
+
+/// 尽量保留原始格式的剪贴板图片
+#[napi(object)]
+pub struct NativeImage {
+  /// 图片数据的 MIME 类型，例如 `image/jpeg`
+  pub mime_type: String,
+  /// 图片数据
+  pub data: Buffer,
+  /// 图片宽度（像素）
+  pub width: u32,
+  /// 图片高度（像素）
+  pub height: u32,
+  /// 无法获取原始数据而转码为 PNG 时为 true
+  pub was_transcoded: bool,
+}
+
+/// 剪贴板中图片的源数据
+struct SourceImage {
+  /// 图片字节数据
+  bytes: Vec<u8>,
+  /// 无法获取原始数据时为 true，此时 bytes 为平台解码后重新编码的 PNG
+  transcoded: bool,
+}
+
+/// Windows 标准剪贴板格式名称对应的格式 ID，也支持 `#<id>` 形式的数字格式
+/// 基于句柄的格式（CF_BITMAP 等）无法以字节形式写入，不在此列
+#[cfg(target_os = "windows")]
+fn windows_standard_format_id(format: &str) -> Option<u32> {
+  if let Some(id) = format.strip_prefix('#') {
+    return id.parse().ok();
+  }
+
+  match format.to_ascii_uppercase().as_str() {
+    "CF_TEXT" => Some(1),
+    "CF_SYLK" => Some(4),
+    "CF_DIF" => Some(5),
+    "CF_TIFF" => Some(6),
+    "CF_OEMTEXT" => Some(7),
+    "CF_DIB" => Some(8),
+    "CF_PENDATA" => Some(10),
+    "CF_RIFF" => Some(11),
+    "CF_WAVE" => Some(12),
+    "CF_UNICODETEXT" => Some(13),
+    "CF_HDROP" => Some(15),
+    "CF_LOCALE" => Some(16),
+    "CF_DIBV5" => Some(17),
+    _ => None,
+  }
+}
+
+/// 以 Windows 原生格式写入字节数据，不做任何转换
+#[cfg(target_os = "windows")]
+fn set_windows_native_format(format: &str, data: &[u8]) -> std::result::Result<(), String> {
+  if matches!(
+    format.to_ascii_uppercase().as_str(),
+    "CF_BITMAP" | "CF_METAFILEPICT" | "CF_PALETTE" | "CF_ENHMETAFILE"
+  ) {
+    return Err(format!(
+      "{format} is a handle-based format and cannot be written as bytes"
+    ));
+  }
+
+  let format_id = windows_standard_format_id(format)
+    .or_else(|| clipboard_win::register_format(format).map(|id| id.get()))
+    .ok_or_else(|| format!("Unable to resolve clipboard format: {format}"))?;
+  let _lock = lock::acquire();
+  let _clipboard = clipboard_win::Clipboard::new_attempts(10)
+    .map_err(|e| format!("Failed to open clipboard: {e}"))?;
+  clipboard_win::raw::set(format_id, data).map_err(|e| e.to_string())
+}
+
+/// 剪贴板中某个格式未经转换的原始数据
+#[napi(object)]
+pub struct RawFormat {
+  /// 标准格式映射为 text、rtf、html、image、files，其他格式与 platformName 相同
+  pub format: String,
+  /// 平台原生格式标识，例如 Windows 的 `CF_DIB`、macOS 的 `public.png`、Linux 的 MIME 类型
+  pub platform_name: String,
+  /// 原始字节
+  pub data: Buffer,
+}
+
+/// 按类别分组的文件列表
+#[napi(object)]
+pub struct FileGroup {
+  /// 文件类别：image、video、audio、document、archive 或 other
+  pub category: String,
+  /// 属于该类别的文件路径，保持剪贴板中的顺序
+  pub paths: Vec<String>,
+}
+
+/// 文件类别及其对应的扩展名（小写），按分组结果的输出顺序排列
+const FILE_CATEGORIES: [(&str, &[&str]); 5] = [
+  (
+    "image",
+    &[
+      "png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff", "svg", "ico", "heic", "heif",
+      "avif",
+    ],
+  ),
+  (
+    "video",
+    &[
+      "mp4", "mov", "avi", "mkv", "webm", "wmv", "flv", "m4v", "mpg", "mpeg",
+    ],
+  ),
+  (
+    "audio",
+    &["mp3", "wav", "flac", "aac", "ogg", "m4a", "wma", "opus"],
+  ),
+  (
+    "document",
+    &[
+      "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "rtf", "txt", "md",
+      "csv", "pages", "numbers", "key",
+    ],
+  ),
+  (
+    "archive",
+    &[
+      "zip", "rar", "7z", "tar", "gz", "tgz", "bz2", "xz", "zst", "dmg", "iso",
+    ],
+  ),
+];
+
+/// 根据扩展名判断文件类别，无法识别时返回 other
+fn file_category(path: &str) -> &'static str {
+  let Some(extension) = std::path::Path::new(path)
+    .extension()
+    .and_then(|extension| extension.to_str())
+  else {
+    return "other";
+  };
+  let extension = extension.to_ascii_lowercase();
+  FILE_CATEGORIES
+    .iter()
+    .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+    .map_or("other", |(category, _)| category)
+}
+
+/// 剪贴板图片信息，不包含图片数据
+#[napi(object)]
+pub struct ImageInfo {
+  /// 图片宽度（像素）
+  pub width: u32,
+  /// 图片高度（像素）
+  pub height: u32,
+  /// PNG 编码后的大小（字节）
+  pub png_size: u32,
+}
+
+/// 当前剪贴板内容支持的粘贴方式
+#[napi(object)]
+pub struct PasteCapabilities {
+  /// 包含纯文本
+  pub can_paste_text: bool,
+  /// 包含图片
+  pub can_paste_image: bool,
+  /// 包含文件列表
+  pub can_paste_files: bool,
+  /// 文本是单个 http(s) 链接，可以作为链接粘贴
+  pub can_paste_as_link: bool,
+  /// 包含 HTML，可以转换为 Markdown 粘贴
+  pub can_paste_as_markdown: bool,
+  /// 包含文本、HTML 或 RTF，可以作为无格式文本粘贴
+  pub can_paste_plain: bool,
+}
+
+/// 检查文本是否为单个 http(s) 链接
+fn is_web_link(text: &str) -> bool {
+  let text = text.trim();
+  let rest = match text.get(..8) {
+    Some(prefix) if prefix.eq_ignore_ascii_case("https://") => &text[8..],
+    _ => match text.get(..7) {
+      Some(prefix) if prefix.eq_ignore_ascii_case("http://") => &text[7..],
+      _ => return false,
+    },
+  };
+  !rest.is_empty() && !rest.starts_with('/') && !text.contains(char::is_whitespace)
+}
+
+/// 文本及其检测到的语言
+#[napi(object)]
+pub struct TextWithLang {
+  /// 文本内容
+  pub text: String,
+  /// ISO 639-1 语言代码，例如 `en`、`zh`；文本过短或无法确定时为空
+  pub language: Option<String>,
+}
+
+/// 剪贴板文本的结构统计
+#[napi(object)]
+pub struct TextStats {
+  /// 字符数（按 Unicode 标量值计数，与 JS 字符串的 length 不同）
+  pub char_count: u32,
+  /// 行数，末尾的换行不产生新行，空文本为 0
+  pub line_count: u32,
+  /// 按空白分隔的单词数
+  pub word_count: u32,
+  /// 是否不超过一行
+  pub is_single_line: bool,
+  /// 是否为空或只包含空白
+  pub is_whitespace_only: bool,
+}
+
+/// 图片的动画帧信息
+#[napi(object)]
+pub struct AnimationInfo {
+  /// 帧数，静态图片为 1
+  pub frame_count: u32,
+  /// 所有帧的总时长（毫秒），静态图片为 0
+  pub total_duration_ms: f64,
+  /// 是否为多帧动画
+  pub is_animated: bool,
+}
+
+/// RGB 颜色
+#[napi(object)]
+pub struct RgbColor {
+  pub r: u8,
+  pub g: u8,
+  pub b: u8,
+}
+
+/// 带透明度的 RGB 颜色
+#[napi(object)]
+pub struct RgbaColor {
+  pub r: u8,
+  pub g: u8,
+  pub b: u8,
+  /// 不透明度（0.0–1.0），默认为 1.0
+  pub a: Option<f64>,
+}
+
+/// setColor 写入的表示形式
+#[napi(object)]
+#[derive(Default)]
+pub struct ColorFormats {
+  /// 是否写入 `#RRGGBB` 或 `rgba(...)` 形式的纯文本，默认为 true
+  pub text: Option<bool>,
+  /// 是否写入以该颜色为背景的 `<span>` HTML 色块，默认为 true
+  pub html: Option<bool>,
+  /// 是否写入该颜色的 1×1 图片，默认为 false
+  pub image: Option<bool>,
+}
+
+/// 与 DOM ImageData 结构相同的像素数据，data 为逐行排列的非预乘 RGBA 像素
+#[napi(object)]
+pub struct CanvasImageData {
+  pub data: Uint8ClampedArray,
+  pub width: u32,
+  pub height: u32,
+}
+
+/// 写入剪贴板后记录的状态标记，用于判断剪贴板内容是否仍是这次写入的内容
+#[napi(object)]
+#[derive(Clone)]
+pub struct ClipboardToken {
+  /// 写入后的系统剪贴板序列号，仅 Windows 与 macOS 提供
+  pub sequence: Option<u32>,
+  /// 写入文本的指纹，系统不提供序列号时使用
+  pub fingerprint: Option<String>,
+}
+
+/// 缓存的 PNG 编码结果，仅在剪贴板序列号未变化时有效
+struct CachedPng {
+  sequence: u32,
+  bytes: Vec<u8>,
 }
 
 /// 剪贴板管理器，提供跨平台的剪贴板操作功能
+/// 同一进程内的所有实例（包括各 worker 线程中创建的实例）、快速操作函数以及监听器读取剪贴板时
+/// 通过进程级锁串行访问剪贴板（Wayland 环境同样如此），一次读取多个格式的操作在同一次加锁中完成，
+/// 不会被本进程其他线程的写入打断；锁不能阻止其他进程修改剪贴板
 #[napi]
 pub struct ClipboardManager {
   context: Option<ClipboardContext>,
+  png_cache: Mutex<Option<CachedPng>>,
+  /// 固定文本监控线程的停止通道
+  pin_stop: Mutex<Option<std::sync::mpsc::Sender<()>>>,
+  /// setTextUntilReplaced 检测线程的停止通道
+  replace_watch_stop: Mutex<Option<std::sync::mpsc::Sender<()>>>,
+}
+
+/// 持有进程级剪贴板锁的上下文引用，离开作用域时解锁
+struct ContextGuard<'a> {
+  context: &'a ClipboardContext,
+  _lock: lock::ClipboardLockGuard,
+}
+
+impl std::ops::Deref for ContextGuard<'_> {
+  type Target = ClipboardContext;
+
+  fn deref(&self) -> &ClipboardContext {
+    self.context
+  }
 }
 
-fn get_context_or_error(context: Option<&ClipboardContext>) -> Result<&ClipboardContext> {
-  context.ok_or_else(|| {
+/// 获取剪贴板上下文并持有进程级剪贴板锁，使不同线程中的管理器实例串行访问剪贴板
+fn get_context_or_error(context: Option<&ClipboardContext>) -> Result<ContextGuard<'_>> {
+  let context = context.ok_or_else(|| {
     Error::new(
       Status::GenericFailure,
       "Clipboard context is unavailable in current environment".to_string(),
     )
+  })?;
+  Ok(ContextGuard {
+    context,
+    _lock: lock::acquire(),
   })
 }
 
@@ -121,6 +914,153 @@ fn image_to_png_bytes(image_data: &RustImageData, err_prefix: &str) -> Result<Ve
   Ok(png_data.get_bytes().to_vec())
 }
 
+/// 图片编码方式
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ImageEncoding {
+  /// 无损 PNG（默认）
+  Png,
+  /// 指定质量（1-100）的 JPEG
+  Jpeg(u8),
+  /// 无损 WebP
+  WebP,
+}
+
+impl ImageEncoding {
+  /// 解析 `png`、`jpeg:<quality>` 或 `webp` 形式的编码描述
+  fn parse(mode: &str) -> Result<Self> {
+    let normalized = mode.trim().to_ascii_lowercase();
+    if normalized == "png" {
+      return Ok(ImageEncoding::Png);
+    }
+    if normalized == "webp" {
+      return Ok(ImageEncoding::WebP);
+    }
+
+    if let Some(quality) = normalized
+      .strip_prefix("jpeg:")
+      .or_else(|| normalized.strip_prefix("jpg:"))
+    {
+      return quality
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|quality| (1..=100).contains(quality))
+        .map(ImageEncoding::Jpeg)
+        .ok_or_else(|| {
+          Error::new(
+            Status::InvalidArg,
+            format!("Invalid JPEG quality in image mode: {mode}"),
+          )
+        });
+    }
+
+    Err(Error::new(
+      Status::InvalidArg,
+      format!("Unsupported image mode: {mode}"),
+    ))
+  }
+
+  /// 转换为 parse 接受的编码描述
+  fn describe(self) -> String {
+    match self {
+      ImageEncoding::Png => "png".to_string(),
+      ImageEncoding::Jpeg(quality) => format!("jpeg:{quality}"),
+      ImageEncoding::WebP => "webp".to_string(),
+    }
+  }
+}
+
+fn dynamic_image_to_jpeg_bytes(
+  image: &DynamicImage,
+  quality: u8,
+  err_prefix: &str,
+) -> Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  JpegEncoder::new_with_quality(&mut bytes, quality)
+    .encode_image(&image.to_rgb8())
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("{err_prefix}: failed to convert image to JPEG: {e}"),
+      )
+    })?;
+  Ok(bytes)
+}
+
+/// 将图片编码为无损 WebP，编码器只支持 8 位 RGB/RGBA，统一转换为 RGBA
+fn dynamic_image_to_webp_bytes(image: &DynamicImage, err_prefix: &str) -> Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  DynamicImage::ImageRgba8(image.to_rgba8())
+    .write_to(
+      &mut std::io::Cursor::new(&mut bytes),
+      image::ImageFormat::WebP,
+    )
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("{err_prefix}: failed to convert image to WebP: {e}"),
+      )
+    })?;
+  Ok(bytes)
+}
+
+/// 按指定编码方式编码解码后的图片
+fn encode_dynamic_image(
+  image: &DynamicImage,
+  encoding: ImageEncoding,
+  err_prefix: &str,
+) -> Result<Vec<u8>> {
+  match encoding {
+    ImageEncoding::Png => dynamic_image_to_png_bytes(image, err_prefix),
+    ImageEncoding::Jpeg(quality) => dynamic_image_to_jpeg_bytes(image, quality, err_prefix),
+    ImageEncoding::WebP => dynamic_image_to_webp_bytes(image, err_prefix),
+  }
+}
+
+/// 按指定编码方式编码图片
+pub(crate) fn encode_rust_image(
+  image_data: &RustImageData,
+  encoding: ImageEncoding,
+  err_prefix: &str,
+) -> Result<Vec<u8>> {
+  if encoding == ImageEncoding::Png {
+    return image_to_png_bytes(image_data, err_prefix);
+  }
+  let dynamic_image = image_data.get_dynamic_image().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("{err_prefix}: failed to decode image: {e}"),
+    )
+  })?;
+  encode_dynamic_image(&dynamic_image, encoding, err_prefix)
+}
+
+/// 解码图片字节数据
+fn decode_image_bytes(image_data: &[u8], err_prefix: &str) -> Result<DynamicImage> {
+  image::load_from_memory(image_data).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("{err_prefix}: failed to create image from bytes: {e}"),
+    )
+  })
+}
+
+fn dynamic_image_to_png_bytes(image: &DynamicImage, err_prefix: &str) -> Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  image
+    .write_to(
+      &mut std::io::Cursor::new(&mut bytes),
+      image::ImageFormat::Png,
+    )
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("{err_prefix}: failed to convert image to PNG: {e}"),
+      )
+    })?;
+  Ok(bytes)
+}
+
 fn image_bytes_to_png_bytes(image_data: &[u8], err_prefix: &str) -> Result<Vec<u8>> {
   let rust_image = RustImageData::from_bytes(image_data).map_err(|e| {
     Error::new(
@@ -150,140 +1090,531 @@ fn image_bytes_to_image_data(image_data: &[u8], err_prefix: &str) -> Result<Imag
   })
 }
 
-#[napi]
-impl ClipboardManager {
-  /// 创建新的剪贴板管理器实例
-  #[napi(constructor)]
-  pub fn new() -> Result<Self> {
-    if is_wayland_environment() {
-      return Ok(ClipboardManager { context: None });
-    }
-
-    let context = ClipboardContext::new().map_err(|e| {
+/// 测试模式下从快照读取格式：未启用测试模式时返回 None，快照中没有该格式时返回错误
+fn read_test_snapshot<T>(
+  read: impl FnOnce(&test_mode::Snapshot) -> Option<T>,
+  err_prefix: &str,
+) -> Option<Result<T>> {
+  test_mode::read(read).map(|value| {
+    value.ok_or_else(|| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to create clipboard context: {e}"),
+        format!("{err_prefix}: format is not present in the test snapshot"),
       )
-    })?;
-
-    Ok(ClipboardManager {
-      context: Some(context),
     })
-  }
-
-  /// 获取剪贴板中的纯文本内容
-  #[napi]
-  pub fn get_text(&self) -> Result<String> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::get_text()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")));
+  })
+}
+
+/// 测试模式下将快照转换为完整的剪贴板数据，未启用测试模式时返回 None
+fn test_snapshot_data() -> Option<Result<ClipboardData>> {
+  let (mut data, image) = test_mode::read(|snapshot| (snapshot.to_data(), snapshot.image.clone()))?;
+  Some(
+    image
+      .map(|bytes| image_bytes_to_image_data(&bytes, "Failed to get clipboard data"))
+      .transpose()
+      .map(|image| {
+        data.image = image;
+        data
+      }),
+  )
+}
+
+/// 读取图片字节数据的尺寸，只解析文件头
+fn image_bytes_dimensions(image_data: &[u8], err_prefix: &str) -> Result<(u32, u32)> {
+  image::ImageReader::new(std::io::Cursor::new(image_data))
+    .with_guessed_format()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("{err_prefix}: {e}")))?
+    .into_dimensions()
+    .map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("{err_prefix}: failed to read image dimensions: {e}"),
+      )
+    })
+}
+
+/// 计算剪贴板内容的指纹（SHA-256 十六进制字符串）
+/// 各格式按固定顺序参与计算，文件列表排序后计算，保证结果与格式顺序无关
+fn clipboard_fingerprint(data: &ClipboardData) -> String {
+  let mut hasher = Sha256::new();
+  let mut update = |tag: &str, bytes: &[u8]| {
+    hasher.update(tag.as_bytes());
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+  };
+
+  if let Some(text) = &data.text {
+    update("text", text.as_bytes());
+  }
+  if let Some(html) = &data.html {
+    update("html", html.as_bytes());
+  }
+  if let Some(rtf) = &data.rtf {
+    update("rtf", rtf.as_bytes());
+  }
+  if let Some(image) = &data.image {
+    update("image", image.data.as_ref());
+  }
+  if let Some(files) = &data.files {
+    let mut sorted_files = files.clone();
+    sorted_files.sort();
+    update("files", sorted_files.join("\n").as_bytes());
+  }
+
+  hasher
+    .finalize()
+    .iter()
+    .map(|byte| format!("{byte:02x}"))
+    .collect()
+}
+
+/// 分别计算剪贴板数据中每个标准格式的内容哈希（SHA-256 十六进制字符串）
+/// 图片按 PNG 编码后的数据计算，文件列表排序后计算，保证结果与文件顺序无关
+fn format_hashes(mut data: ClipboardData) -> Vec<FormatHash> {
+  if let Some(files) = &mut data.files {
+    files.sort();
+  }
+  clipboard_data_to_entries(data)
+    .into_iter()
+    .map(|(format, bytes)| FormatHash {
+      format,
+      hash: Sha256::digest(&bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect(),
+    })
+    .collect()
+}
+
+/// 将剪贴板数据的标准格式转换为归档条目，图片使用 ImageData 中的编码数据
+fn clipboard_data_to_entries(data: ClipboardData) -> Vec<(String, Vec<u8>)> {
+  let mut entries = Vec::new();
+  if let Some(text) = data.text {
+    entries.push(("text".to_string(), text.into_bytes()));
+  }
+  if let Some(rtf) = data.rtf {
+    entries.push(("rtf".to_string(), rtf.into_bytes()));
+  }
+  if let Some(html) = data.html {
+    entries.push(("html".to_string(), html.into_bytes()));
+  }
+  if let Some(image) = data.image {
+    entries.push(("image".to_string(), image.data.to_vec()));
+  }
+  if let Some(files) = data.files {
+    entries.push(("files".to_string(), files.join("\n").into_bytes()));
+  }
+  entries
+}
+
+/// 将归档条目还原为剪贴板数据，非标准格式的条目原样返回，条目内容无效时返回 InvalidArg 错误
+fn entries_to_clipboard_data(
+  entries: Vec<(String, Vec<u8>)>,
+  err_prefix: &str,
+) -> Result<(ClipboardData, Vec<(String, Vec<u8>)>)> {
+  let into_text = |name: &str, bytes: Vec<u8>| {
+    String::from_utf8(bytes).map_err(|_| {
+      Error::new(
+        Status::InvalidArg,
+        format!("{err_prefix}: entry {name} is not valid UTF-8"),
+      )
+    })
+  };
+
+  let mut contents = ClipboardData::default();
+  let mut extra = Vec::new();
+  for (name, bytes) in entries {
+    match name.as_str() {
+      "text" => contents.text = Some(into_text(&name, bytes)?),
+      "rtf" => contents.rtf = Some(into_text(&name, bytes)?),
+      "html" => contents.html = Some(into_text(&name, bytes)?),
+      "files" => {
+        let files = into_text(&name, bytes)?;
+        contents.files = Some(files.lines().map(str::to_string).collect());
+      }
+      "image" => {
+        let (width, height) = image_bytes_dimensions(&bytes, err_prefix)?;
+        contents.image = Some(ImageData {
+          width,
+          height,
+          size: bytes.len() as u32,
+          data: Buffer::from(bytes),
+        });
+      }
+      _ => extra.push((name, bytes)),
     }
+  }
+  Ok((contents, extra))
+}
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .get_text()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
+/// 计算只包含文本的剪贴板内容的指纹
+fn text_fingerprint(text: &str) -> String {
+  clipboard_fingerprint(&ClipboardData {
+    text: Some(text.to_string()),
+    ..Default::default()
+  })
+}
+
+/// 按指定次数重试剪贴板写入，每次失败后等待 delay，全部失败时返回最后一次的错误
+fn retry_clipboard_write<F>(attempts: u32, delay: Duration, mut write: F) -> Result<()>
+where
+  F: FnMut() -> std::result::Result<(), String>,
+{
+  let mut last_error = String::new();
+  for attempt in 1..=attempts {
+    match write() {
+      Ok(()) => return Ok(()),
+      Err(e) => {
+        last_error = e;
+        if attempt < attempts {
+          thread::sleep(delay);
+        }
+      }
+    }
   }
 
-  /// 设置剪贴板中的纯文本内容
-  #[napi]
-  pub fn set_text(&self, text: String) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::set_text(text)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")));
+  Err(Error::new(
+    Status::GenericFailure,
+    format!("Failed to set text after {attempts} attempts: {last_error}"),
+  ))
+}
+
+/// `text/uri-list` 中的条目，本地文件与远程 URL 分开存放
+#[napi(object)]
+pub struct UriList {
+  /// 本地文件路径（由 `file://` URI 解码得到）
+  pub files: Vec<String>,
+  /// 其他 URI，例如 `http(s)://` 链接
+  pub urls: Vec<String>,
+}
+
+/// 解码 URI 中的百分号编码，非法的编码原样保留
+fn percent_decode(text: &str) -> String {
+  let bytes = text.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut index = 0;
+  while index < bytes.len() {
+    // from_str_radix 接受 `+` 前缀，先确认两个字符都是十六进制数字
+    let hex = bytes
+      .get(index + 1..index + 3)
+      .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match (bytes[index], hex) {
+      (b'%', Some(byte)) => {
+        decoded.push(byte);
+        index += 3;
+      }
+      (byte, _) => {
+        decoded.push(byte);
+        index += 1;
+      }
     }
+  }
+  String::from_utf8_lossy(&decoded).into_owned()
+}
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .set_text(text)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
+/// 将本地 `file://` URI 转换为文件路径，远程主机或非 file URI 返回 None
+fn file_uri_to_path(uri: &str) -> Option<String> {
+  let scheme = uri.get(..7)?;
+  if !scheme.eq_ignore_ascii_case("file://") {
+    return None;
   }
 
-  /// 获取剪贴板中的 HTML 内容
-  #[napi]
-  pub fn get_html(&self) -> Result<String> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::get_html()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")));
+  let rest = &uri[7..];
+  let path_start = rest.find('/')?;
+  let host = &rest[..path_start];
+  if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+    return None;
+  }
+
+  let path = percent_decode(&rest[path_start..]);
+  // Windows 盘符路径形如 file:///C:/dir，去掉盘符前的斜杠
+  let path_bytes = path.as_bytes();
+  if path_bytes.len() >= 3 && path_bytes[1].is_ascii_alphabetic() && path_bytes[2] == b':' {
+    return Some(path[1..].to_string());
+  }
+  Some(path)
+}
+
+/// 计算路径占用的字节数，无法读取元数据的路径计为 0
+/// 目录只在 recursive 为 true 时累加其内容，递归时不进入指向目录的符号链接，避免循环
+fn path_total_size(path: &std::path::Path, recursive: bool) -> u64 {
+  let Ok(metadata) = std::fs::metadata(path) else {
+    return 0;
+  };
+  if !metadata.is_dir() {
+    return metadata.len();
+  }
+  if !recursive {
+    return 0;
+  }
+  let Ok(entries) = std::fs::read_dir(path) else {
+    return 0;
+  };
+  entries
+    .flatten()
+    .filter(|entry| {
+      !entry.file_type().is_ok_and(|kind| kind.is_symlink()) || !entry.path().is_dir()
+    })
+    .map(|entry| path_total_size(&entry.path(), true))
+    .sum()
+}
+
+/// 解析 `text/uri-list` 内容，忽略空行和以 `#` 开头的注释行
+fn parse_uri_list(payload: &str) -> UriList {
+  let mut uri_list = UriList {
+    files: Vec::new(),
+    urls: Vec::new(),
+  };
+  for line in payload.lines().map(str::trim) {
+    if line.is_empty() || line.starts_with('#') {
+      continue;
     }
+    match file_uri_to_path(line) {
+      Some(path) => uri_list.files.push(path),
+      None => uri_list.urls.push(line.to_string()),
+    }
+  }
+  uri_list
+}
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .get_html()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")))
+/// 判断文本是否为绝对路径形式：POSIX 绝对路径、Windows 盘符路径或 UNC 路径
+fn is_absolute_path_like(text: &str) -> bool {
+  let bytes = text.as_bytes();
+  let is_drive_path = bytes.len() >= 3
+    && bytes[0].is_ascii_alphabetic()
+    && bytes[1] == b':'
+    && matches!(bytes[2], b'\\' | b'/');
+  let is_unc_path = text.starts_with("\\\\") && text.len() > 2;
+  text.starts_with('/') || is_drive_path || is_unc_path
+}
+
+/// 转义 HTML 特殊字符
+fn escape_html(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      _ => escaped.push(c),
+    }
   }
+  escaped
+}
 
-  /// 设置剪贴板中的 HTML 内容
-  #[napi]
-  pub fn set_html(&self, html: String) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::set_html(html)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")));
+/// 解码 base64 编码的 data URL 数据，不是 base64 编码时返回 None
+fn decode_base64_data_url(url: &str) -> Option<Vec<u8>> {
+  let (header, payload) = url.split_once(',')?;
+  if !header.to_ascii_lowercase().ends_with(";base64") {
+    return None;
+  }
+  let payload: String = payload.chars().filter(|c| !c.is_whitespace()).collect();
+  BASE64_STANDARD.decode(payload).ok()
+}
+
+/// 按指定 shell 的规则将文本转义为单个参数
+/// bash 与 powershell 使用单引号，cmd 使用双引号并打断 `%VAR%` 形式的变量展开；cmd 的命令行不能包含换行
+/// cmd 的结果只适用于交互式 `cmd.exe` 提示符：批处理文件中 `%%` 会被解析为单个 `%`，
+/// 且未转义 `!`，启用延迟展开（`cmd /V:ON`）时 `!VAR!` 仍会被展开
+fn shell_escape(text: &str, shell: &str) -> Result<String> {
+  match shell.trim().to_ascii_lowercase().as_str() {
+    "bash" => Ok(format!("'{}'", text.replace('\'', r"'\''"))),
+    // PowerShell 将弯引号同样视为单引号，需要一并重复
+    "powershell" => {
+      let mut escaped = String::with_capacity(text.len() + 2);
+      escaped.push('\'');
+      for ch in text.chars() {
+        if matches!(ch, '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}') {
+          escaped.push(ch);
+        }
+        escaped.push(ch);
+      }
+      escaped.push('\'');
+      Ok(escaped)
+    }
+    "cmd" => {
+      if text.contains(['\r', '\n']) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "Text containing line breaks cannot be escaped for cmd".to_string(),
+        ));
+      }
+      // 交互式提示符中未定义的 `%%` 原样保留，随后的 `%cd:~,%` 展开为空字符串，
+      // 使原来的 `%` 之后的内容无法组成变量引用；双引号内 `&`、`|` 等字符不需要转义
+      Ok(format!(
+        "\"{}\"",
+        text.replace('"', "\"\"").replace('%', "%%cd:~,%")
+      ))
     }
+    _ => Err(Error::new(
+      Status::InvalidArg,
+      format!("Unsupported shell: {shell}"),
+    )),
+  }
+}
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .set_html(html)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")))
+/// 将代码包装为 `<pre><code>` HTML 片段，指定语言时添加 `language-<语言>` 样式类
+fn code_to_html(code: &str, language: Option<&str>) -> Result<String> {
+  let class = match language
+    .map(str::trim)
+    .filter(|language| !language.is_empty())
+  {
+    Some(language) => {
+      let is_valid = language
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+' | '#' | '.'));
+      if !is_valid {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Invalid code language: {language}"),
+        ));
+      }
+      format!(" class=\"language-{}\"", escape_html(language))
+    }
+    None => String::new(),
+  };
+  Ok(format!(
+    "<pre><code{class}>{}</code></pre>",
+    escape_html(code)
+  ))
+}
+
+/// 将文本包装为带样式类的 `<span>` HTML 片段，样式类不能为空或包含引号、尖括号
+fn styled_text_to_html(text: &str, css_class: &str) -> Result<String> {
+  let css_class = css_class.trim();
+  if css_class.is_empty() || css_class.contains(['"', '\'', '<', '>']) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Invalid CSS class: {css_class}"),
+    ));
   }
+  Ok(format!(
+    "<span class=\"{css_class}\">{}</span>",
+    escape_html(text)
+  ))
+}
 
-  /// 获取剪贴板中的富文本内容
-  #[napi]
-  pub fn get_rich_text(&self) -> Result<String> {
-    #[cfg(target_os = "linux")]
+/// 将路径转换为剪贴板文件列表使用的字符串（Windows 下去除 `\\?\` 前缀）
+fn path_to_clipboard_string(path: &std::path::Path) -> Result<String> {
+  let path_str = path.to_str().ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("Path is not valid UTF-8: {}", path.display()),
+    )
+  })?;
+
+  #[cfg(target_os = "windows")]
+  if let Some(stripped) = path_str.strip_prefix(r"\\?\") {
+    return Ok(stripped.to_string());
+  }
+
+  Ok(path_str.to_string())
+}
+
+#[napi]
+impl ClipboardManager {
+  /// 创建新的剪贴板管理器实例
+  #[napi(constructor)]
+  pub fn new() -> Result<Self> {
     if is_wayland_environment() {
-      return wayland::get_rich_text().map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to get rich text: {e}"),
-        )
+      return Ok(ClipboardManager {
+        context: None,
+        png_cache: Mutex::new(None),
+        pin_stop: Mutex::new(None),
+        replace_watch_stop: Mutex::new(None),
       });
     }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context.get_rich_text().map_err(|e| {
+    let context = ClipboardContext::new().map_err(|e| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to get rich text: {e}"),
+        format!("Failed to create clipboard context: {e}"),
       )
+    })?;
+
+    Ok(ClipboardManager {
+      context: Some(context),
+      png_cache: Mutex::new(None),
+      pin_stop: Mutex::new(None),
+      replace_watch_stop: Mutex::new(None),
     })
   }
 
-  /// 设置剪贴板中的富文本内容
-  #[napi]
-  pub fn set_rich_text(&self, text: String) -> Result<()> {
+  /// 读取完整的剪贴板数据
+  fn read_full_data(&self) -> Result<ClipboardData> {
+    if let Some(result) = test_snapshot_data() {
+      return result;
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      return wayland::set_rich_text(text).map_err(|e| {
+      return wayland::get_full_clipboard_data().map_err(|e| {
         Error::new(
           Status::GenericFailure,
-          format!("Failed to set rich text: {e}"),
+          format!("Failed to get clipboard data: {e}"),
         )
       });
     }
 
     let context = get_context_or_error(self.context.as_ref())?;
-    context.set_rich_text(text).map_err(|e| {
+    Ok(get_clipboard_data(&context))
+  }
+
+  /// 读取剪贴板 HTML 的原始字节，不做解码
+  fn read_raw_html(&self) -> Result<Vec<u8>> {
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_html_bytes()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .get_buffer(NATIVE_HTML_FORMAT)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")))
+  }
+
+  /// 读取以 UTF-8 文本保存的自定义格式，格式不存在时返回 None
+  /// 读取失败或内容不是有效的 UTF-8 时返回错误，而不是当作格式不存在
+  fn read_custom_text_format(&self, format: &str) -> Result<Option<String>> {
+    // 检查与读取之间持有进程级锁，避免格式在两者之间被本进程的写入移除
+    let _lock = lock::acquire();
+    if !self
+      .get_available_formats()?
+      .iter()
+      .any(|available| available == format)
+    {
+      return Ok(None);
+    }
+
+    let buffer = self.get_buffer(format.to_string())?;
+    String::from_utf8(buffer.to_vec()).map(Some).map_err(|e| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to set rich text: {e}"),
+        format!("Format {format} is not valid UTF-8: {e}"),
       )
     })
   }
 
-  /// 获取剪贴板中的图片数据（以 base64 编码返回）
-  #[napi]
-  pub fn get_image_base64(&self) -> Result<String> {
+  /// 读取剪贴板图片并编码为 PNG，返回宽度、高度和 PNG 数据
+  fn read_png_image(&self) -> Result<(u32, u32, Vec<u8>)> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.image.clone(), "Failed to get image")
+    {
+      let image = image_bytes_to_image_data(&result?, "Failed to get image")?;
+      return Ok((image.width, image.height, image.data.to_vec()));
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
       let image_data = wayland::get_image_raw()
         .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-      let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
-      return Ok(BASE64_STANDARD.encode(png_bytes));
+      let image = image_bytes_to_image_data(&image_data, "Failed to get image")?;
+      return Ok((image.width, image.height, image.data.to_vec()));
     }
 
     let context = get_context_or_error(self.context.as_ref())?;
@@ -291,465 +1622,3400 @@ impl ClipboardManager {
       .get_image()
       .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
 
+    let (width, height) = image_data.get_size();
     let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-    Ok(BASE64_STANDARD.encode(png_bytes))
+    Ok((width, height, png_bytes))
   }
 
-  /// 获取剪贴板中的图片详细信息（包含宽度、高度、大小和原始数据）
-  #[napi]
-  pub fn get_image_data(&self) -> Result<ImageData> {
+  /// 取出缓存的 PNG 数据，剪贴板已变化或平台不支持序列号时返回 None
+  fn take_cached_png(&self) -> Option<Vec<u8>> {
+    let sequence = clipboard_sequence_number()?;
+    let mut cache = self
+      .png_cache
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache
+      .take()
+      .filter(|cached| cached.sequence == sequence)
+      .map(|cached| cached.bytes)
+  }
+
+  /// 读取剪贴板中图片的源数据
+  /// 优先按平台原生格式读取未经转换的字节，无法获取时回退为 PNG 编码的图片
+  fn read_source_image(&self) -> Result<SourceImage> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.image.clone(), "Failed to get image")
+    {
+      return result.map(|bytes| SourceImage {
+        bytes,
+        transcoded: false,
+      });
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      let image_data = wayland::get_image_raw()
+      let bytes = wayland::get_image_raw()
         .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-      return image_bytes_to_image_data(&image_data, "Failed to get image");
+      return Ok(SourceImage {
+        bytes,
+        transcoded: false,
+      });
     }
 
     let context = get_context_or_error(self.context.as_ref())?;
+    if let Ok(native_formats) = context.available_formats() {
+      for format in native_formats
+        .iter()
+        .filter(|format| canonical_format_name(format).as_deref() == Some("image"))
+      {
+        if let Ok(bytes) = context.get_buffer(format) {
+          if imaging::sniff_image_mime(&bytes).is_some() {
+            return Ok(SourceImage {
+              bytes,
+              transcoded: false,
+            });
+          }
+        }
+      }
+    }
+
     let image_data = context
       .get_image()
       .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-
-    let (width, height) = image_data.get_size();
-    let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-    Ok(ImageData {
-      width,
-      height,
-      size: png_bytes.len() as u32,
-      data: Buffer::from(png_bytes),
+    Ok(SourceImage {
+      bytes: image_to_png_bytes(&image_data, "Failed to get image")?,
+      transcoded: true,
     })
   }
 
-  /// 从 base64 编码的图片数据设置剪贴板图片
+  /// 获取剪贴板中的纯文本内容
   #[napi]
-  pub fn set_image_base64(&self, base64_data: String) -> Result<()> {
-    let image_data = BASE64_STANDARD
-      .decode(base64_data)
-      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid base64 data: {e}")))?;
-
-    let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to create image from bytes: {e}"),
-      )
-    })?;
+  pub fn get_text(&self) -> Result<String> {
+    if let Some(result) = read_test_snapshot(|snapshot| snapshot.text.clone(), "Failed to get text")
+    {
+      return result;
+    }
 
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
-      return wayland::set_image_raw(png_bytes)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+      return wayland::get_text()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")));
     }
 
     let context = get_context_or_error(self.context.as_ref())?;
     context
-      .set_image(rust_image)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+      .get_text()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
   }
 
-  /// 从原始字节数据设置剪贴板图片
+  /// 设置剪贴板中的纯文本内容
   #[napi]
-  pub fn set_image_raw(&self, image_data: Buffer) -> Result<()> {
-    let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to create image from bytes: {e}"),
-      )
-    })?;
+  pub fn set_text(&self, text: String) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        text: Some(text),
+        ..Default::default()
+      });
+      return Ok(());
+    }
 
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
-      return wayland::set_image_raw(png_bytes)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+      return wayland::set_text(text)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")));
     }
 
     let context = get_context_or_error(self.context.as_ref())?;
     context
-      .set_image(rust_image)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+      .set_text(text)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
   }
 
-  /// 获取剪贴板中的图片原始数据（Buffer）
+  /// 设置纯文本内容，Windows 下同时显式写入 CF_UNICODETEXT 与 CF_TEXT，兼容只读取其中一种格式的旧程序
+  /// 其他平台与 setText 相同
   #[napi]
-  pub fn get_image_raw(&self) -> Result<Buffer> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      let image_data = wayland::get_image_raw()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-      let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
-      return Ok(Buffer::from(png_bytes));
+  pub fn set_text_wide(&self, text: String) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    if !test_mode::is_enabled() {
+      return windows::set_text_wide(&text)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")));
     }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    let image_data = context
-      .get_image()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+    self.set_text(text)
+  }
 
-    let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-    Ok(Buffer::from(png_bytes))
+  /// 去除末尾的所有换行后设置文本，粘贴到终端时最后一行命令不会被自动执行
+  #[napi]
+  pub fn set_text_for_terminal(&self, text: String) -> Result<()> {
+    self.set_text(text.trim_end_matches(['\r', '\n']).to_string())
   }
 
-  /// 获取剪贴板中的文件列表
+  /// 设置文本并确保以换行结尾，粘贴到终端时最后一行命令会被立即执行；已以换行结尾的文本保持不变
   #[napi]
-  pub fn get_files(&self) -> Result<Vec<String>> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::get_files()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")));
+  pub fn set_text_with_exec(&self, mut text: String) -> Result<()> {
+    if !text.ends_with('\n') {
+      text.push('\n');
     }
-
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .get_files()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")))
+    self.set_text(text)
   }
 
-  /// 设置剪贴板中的文件列表
+  /// 读取 UTF-8 文本文件并设置为剪贴板文本，返回文件的字节数
+  /// 文件读取失败返回 GenericFailure 错误，超过 max_bytes 或不是有效的 UTF-8 时返回 InvalidArg 错误
   #[napi]
-  pub fn set_files(&self, files: Vec<String>) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::set_files(files)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")));
+  pub fn set_text_from_file(&self, path: String, max_bytes: Option<f64>) -> Result<u32> {
+    if let Some(max_bytes) = max_bytes {
+      if !max_bytes.is_finite() || max_bytes < 0.0 {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Invalid byte limit: {max_bytes}"),
+        ));
+      }
     }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .set_files(files)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")))
+    let read_error = |e: std::io::Error| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to read file {path}: {e}"),
+      )
+    };
+    let file = std::fs::File::open(&path).map_err(read_error)?;
+    // 最多多读取一个字节，用于判断文件是否超过上限，避免将过大的文件整体读入内存
+    let limit = max_bytes.map_or(u64::MAX, |max_bytes| (max_bytes as u64).saturating_add(1));
+    let mut bytes = Vec::new();
+    file
+      .take(limit)
+      .read_to_end(&mut bytes)
+      .map_err(read_error)?;
+    if let Some(max_bytes) = max_bytes.filter(|max_bytes| bytes.len() as f64 > *max_bytes) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("File {path} exceeds the limit of {max_bytes} bytes"),
+      ));
+    }
+    let size = u32::try_from(bytes.len())
+      .map_err(|_| Error::new(Status::InvalidArg, format!("File {path} is too large")))?;
+    let text = String::from_utf8(bytes).map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("File {path} is not valid UTF-8: {e}"),
+      )
+    })?;
+
+    self.set_text(text)?;
+    Ok(size)
   }
 
-  /// 设置剪贴板中的自定义格式数据
+  /// 设置文本并在指定时长内保持不变：后台线程检测到剪贴板被修改时重新写入该文本
+  /// 这是尽力而为的保持，并非系统级锁定，其他应用仍可短暂修改剪贴板
+  /// 再次调用会替换之前的固定，调用 unpin 或管理器被回收时提前结束
   #[napi]
-  pub fn set_buffer(&self, format: String, buffer: Buffer) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::set_buffer(format, buffer.to_vec())
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")));
+  pub fn pin_text(&self, text: String, duration_ms: f64) -> Result<()> {
+    if !duration_ms.is_finite() || duration_ms < 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid pin duration: {duration_ms}"),
+      ));
     }
+    let duration = Duration::from_secs_f64(duration_ms / 1000.0);
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context
-      .set_buffer(&format, buffer.to_vec())
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")))
+    self.unpin();
+    self.set_text(text.clone())?;
+
+    let monitor = ClipboardManager::new()?;
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    thread::spawn(move || {
+      let deadline = std::time::Instant::now() + duration;
+      loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+          return;
+        }
+        match stop_rx.recv_timeout(remaining.min(PIN_CHECK_INTERVAL)) {
+          Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+          _ => return,
+        }
+        if monitor.get_text().ok().as_deref() != Some(text.as_str()) {
+          let _ = monitor.set_text(text.clone());
+        }
+      }
+    });
+
+    *self
+      .pin_stop
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(stop_tx);
+    Ok(())
   }
 
-  /// 获取剪贴板中的自定义格式数据
+  /// 提前结束 pinText 的固定，没有固定时无副作用
   #[napi]
-  pub fn get_buffer(&self, format: String) -> Result<Buffer> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      let data = wayland::get_buffer(format)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
-      return Ok(Buffer::from(data));
+  pub fn unpin(&self) {
+    let stop_tx = self
+      .pin_stop
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+      .take();
+    if let Some(stop_tx) = stop_tx {
+      let _ = stop_tx.send(());
     }
+  }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    let data = context
-      .get_buffer(&format)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
-    Ok(Buffer::from(data))
+  /// 设置纯文本内容，并返回记录写入后剪贴板状态的标记，配合 isStillCurrent 使用
+  #[napi]
+  pub fn set_text_tracked(&self, text: String) -> Result<ClipboardToken> {
+    let fingerprint = text_fingerprint(&text);
+    self.set_text(text)?;
+    Ok(match clipboard_sequence_number() {
+      Some(sequence) => ClipboardToken {
+        sequence: Some(sequence),
+        fingerprint: None,
+      },
+      None => ClipboardToken {
+        sequence: None,
+        fingerprint: Some(fingerprint),
+      },
+    })
   }
 
-  /// 设置剪贴板中的复合内容（可同时设置多种格式）
+  /// 判断剪贴板是否仍是 setTextTracked 写入的内容
+  /// Windows 与 macOS 下比较序列号，任何写入都视为变化；其他平台比较文本指纹，再次复制相同文本仍视为未变化
   #[napi]
-  pub fn set_contents(&self, contents: ClipboardData) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::set_contents(contents).map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to set contents: {e}"),
-        )
-      });
+  pub fn is_still_current(&self, token: ClipboardToken) -> Result<bool> {
+    if let Some(sequence) = token.sequence {
+      return Ok(clipboard_sequence_number() == Some(sequence));
     }
+    let Some(fingerprint) = token.fingerprint else {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Clipboard token is empty".to_string(),
+      ));
+    };
+    Ok(
+      self
+        .get_text()
+        .is_ok_and(|text| text_fingerprint(&text) == fingerprint),
+    )
+  }
 
-    use clipboard_rs::ClipboardContent;
-
-    let mut clipboard_contents = Vec::new();
-
-    if let Some(text) = contents.text {
-      clipboard_contents.push(ClipboardContent::Text(text));
+  /// 设置纯文本，并在剪贴板内容被其他内容替换后调用一次 on_replaced 回调
+  /// 后台线程按固定间隔检查剪贴板，检测到替换或管理器被回收后结束；
+  /// 再次调用会取消之前的检测，且不触发之前的回调
+  #[napi]
+  pub fn set_text_until_replaced(
+    &self,
+    text: String,
+    on_replaced: Option<Function<(), ()>>,
+  ) -> Result<()> {
+    let previous_stop = self
+      .replace_watch_stop
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+      .take();
+    if let Some(stop_tx) = previous_stop {
+      let _ = stop_tx.send(());
     }
 
-    if let Some(html) = contents.html {
-      clipboard_contents.push(ClipboardContent::Html(html));
-    }
+    let token = self.set_text_tracked(text)?;
+    let Some(on_replaced) = on_replaced else {
+      return Ok(());
+    };
+    let tsfn = on_replaced
+      .build_threadsafe_function()
+      .build_callback(|ctx| Ok(ctx.value))?;
 
-    if let Some(rtf) = contents.rtf {
-      clipboard_contents.push(ClipboardContent::Rtf(rtf));
-    }
+    let monitor = ClipboardManager::new()?;
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    thread::spawn(move || loop {
+      match stop_rx.recv_timeout(REPLACE_CHECK_INTERVAL) {
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+        _ => return,
+      }
+      // 读取失败（例如剪贴板被其他进程短暂占用）时视为未替换，下次继续检查
+      if matches!(monitor.is_still_current(token.clone()), Ok(false)) {
+        tsfn.call((), ThreadsafeFunctionCallMode::NonBlocking);
+        return;
+      }
+    });
 
-    if let Some(image_data) = contents.image {
-      let rust_image = RustImageData::from_bytes(image_data.data.as_ref()).map_err(|e| {
+    *self
+      .replace_watch_stop
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(stop_tx);
+    Ok(())
+  }
+
+  /// 设置多语言文本
+  /// 每个语言版本写入独立的自定义格式，第一个版本同时作为默认纯文本
+  #[napi]
+  pub fn set_text_localized(&self, variants: Vec<LocalizedText>) -> Result<()> {
+    let default_text = variants
+      .first()
+      .map(|variant| variant.text.clone())
+      .ok_or_else(|| {
         Error::new(
-          Status::GenericFailure,
-          format!("Failed to create image from bytes: {e}"),
+          Status::InvalidArg,
+          "At least one localized text variant is required".to_string(),
         )
       })?;
 
-      clipboard_contents.push(ClipboardContent::Image(rust_image));
+    let mut extra = Vec::with_capacity(variants.len());
+    for variant in variants {
+      extra.push((
+        localized_text_format(&variant.locale)?,
+        variant.text.into_bytes(),
+      ));
     }
 
-    if let Some(files) = contents.files {
-      clipboard_contents.push(ClipboardContent::Files(files));
+    self.set_contents_with_extra(
+      ClipboardData {
+        text: Some(default_text),
+        ..Default::default()
+      },
+      extra,
+    )
+  }
+
+  /// 获取指定语言的文本，不存在该语言版本时返回默认纯文本
+  #[napi]
+  pub fn get_text_localized(&self, locale: String) -> Result<String> {
+    let format = localized_text_format(&locale)?;
+    if let Ok(buffer) = self.get_buffer(format) {
+      if let Ok(text) = String::from_utf8(buffer.to_vec()) {
+        return Ok(text);
+      }
     }
+    self.get_text()
+  }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context.set(clipboard_contents).map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to set contents: {e}"),
-      )
-    })
+  /// 设置纯文本，并在同一次写入中将来源标识写入自定义格式 `application/x-clip-source`
+  #[napi]
+  pub fn set_text_with_source(&self, text: String, source: String) -> Result<()> {
+    self.set_contents_with_extra(
+      ClipboardData {
+        text: Some(text),
+        ..Default::default()
+      },
+      vec![(SOURCE_FORMAT.to_string(), source.into_bytes())],
+    )
   }
 
-  /// 检查剪贴板是否包含指定格式的内容
+  /// 获取 setTextWithSource 写入的来源标识，剪贴板中没有来源标识时返回 null
   #[napi]
-  pub fn has_format(&self, format: String) -> Result<bool> {
-    let normalized_format = match format.as_str() {
-      "text" => "text",
-      "html" => "html",
-      "rtf" | "rich_text" => "rtf",
-      "image" => "image",
-      "files" => "files",
-      _ => {
-        return Err(Error::new(
-          Status::InvalidArg,
-          format!("Unsupported format: {format}"),
-        ))
-      }
-    };
+  pub fn get_source(&self) -> Result<Option<String>> {
+    self.read_custom_text_format(SOURCE_FORMAT)
+  }
 
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::has_format(normalized_format).map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to check format: {e}"),
-        )
-      });
-    }
+  /// 设置复合内容，并在同一次写入中将标记写入自定义格式 `application/x-clip-marker`
+  /// 多设备同步时可写入设备 ID，再通过 getMarker 忽略自己写入的变化，避免回环
+  #[napi]
+  pub fn set_contents_marked(&self, contents: ClipboardData, marker: String) -> Result<()> {
+    self.set_contents_with_extra(
+      contents,
+      vec![(MARKER_FORMAT.to_string(), marker.into_bytes())],
+    )
+  }
 
-    let content_format = match normalized_format {
-      "text" => ContentFormat::Text,
-      "html" => ContentFormat::Html,
-      "rtf" => ContentFormat::Rtf,
-      "image" => ContentFormat::Image,
-      "files" => ContentFormat::Files,
-      _ => unreachable!("normalized format should only be known values"),
-    };
+  /// 获取 setContentsMarked 写入的标记，剪贴板中没有标记时返回 null
+  #[napi]
+  pub fn get_marker(&self) -> Result<Option<String>> {
+    self.read_custom_text_format(MARKER_FORMAT)
+  }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    Ok(context.has(content_format))
+  /// 设置纯文本，并在同一次写入中将元数据写入自定义格式 `application/x-clip-meta`
+  /// 元数据通常为 JSON 字符串，原样保存不做校验；其他应用仍只看到普通文本
+  #[napi]
+  pub fn set_with_metadata(&self, text: String, metadata: String) -> Result<()> {
+    self.set_contents_with_extra(
+      ClipboardData {
+        text: Some(text),
+        ..Default::default()
+      },
+      vec![(METADATA_FORMAT.to_string(), metadata.into_bytes())],
+    )
   }
 
-  /// 获取剪贴板中所有可用的格式
+  /// 获取 setWithMetadata 写入的元数据，剪贴板中没有元数据时返回 null
   #[napi]
-  pub fn get_available_formats(&self) -> Result<Vec<String>> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::get_available_formats().map_err(|e| {
+  pub fn get_metadata(&self) -> Result<Option<String>> {
+    self.read_custom_text_format(METADATA_FORMAT)
+  }
+
+  /// 设置只供指定应用读取的文本，返回是否实际限定了读取范围
+  /// macOS 上 bundle_id 不为空时写入以 bundle_id 命名的私有剪贴板（命名 NSPasteboard），通用剪贴板保持不变，
+  /// 目标应用需通过 `NSPasteboard(name:)` 读取；这种隔离只依赖名称约定，知道名称的应用仍可读取
+  /// Windows 和 Linux 没有按应用限定剪贴板的机制，此时以及 bundle_id 为空时与 setText 相同并返回 false
+  #[napi]
+  pub fn set_text_scoped(&self, text: String, bundle_id: Option<String>) -> Result<bool> {
+    #[cfg(target_os = "macos")]
+    if let Some(bundle_id) = bundle_id.as_deref().filter(|id| !id.trim().is_empty()) {
+      macos::set_named_pasteboard_text(bundle_id, &text).map_err(|e| {
         Error::new(
           Status::GenericFailure,
-          format!("Failed to get available formats: {e}"),
+          format!("Failed to set scoped text: {e}"),
         )
-      });
+      })?;
+      return Ok(true);
     }
+    #[cfg(not(target_os = "macos"))]
+    let _ = bundle_id;
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context.available_formats().map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to get available formats: {e}"),
-      )
-    })
+    self.set_text(text)?;
+    Ok(false)
   }
 
-  /// 清空剪贴板
+  /// 获取剪贴板文本并检测其语言，检测为尽力而为，文本过短或无法确定时 language 为 null
   #[napi]
-  pub fn clear(&self) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return wayland::clear().map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to clear clipboard: {e}"),
-        )
-      });
-    }
+  pub fn get_text_with_language(&self) -> Result<TextWithLang> {
+    let text = self.get_text()?;
+    let language = language::detect_language(&text).map(str::to_string);
+    Ok(TextWithLang { text, language })
+  }
 
-    let context = get_context_or_error(self.context.as_ref())?;
-    context.clear().map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to clear clipboard: {e}"),
-      )
+  /// 获取剪贴板文本的字符数、行数、单词数等结构统计，只返回统计结果而不传输文本
+  #[napi]
+  pub fn get_text_stats(&self) -> Result<TextStats> {
+    let text = self.get_text()?;
+    let line_count = text.lines().count() as u32;
+    Ok(TextStats {
+      char_count: text.chars().count() as u32,
+      line_count,
+      word_count: text.split_whitespace().count() as u32,
+      is_single_line: line_count <= 1,
+      is_whitespace_only: text.trim().is_empty(),
     })
   }
 
-  /// 异步获取剪贴板文本内容
+  /// 按调用方给定的格式顺序（text、html、rtf）读取文本，HTML 与 RTF 会转换为纯文本
+  /// 返回第一个存在且不为空白的结果，都没有时返回 null；包含不支持的格式名称时返回 InvalidArg 错误
   #[napi]
-  pub async fn get_text_async(&self) -> Result<String> {
-    #[cfg(target_os = "linux")]
-    if is_wayland_environment() {
-      return tokio::task::spawn_blocking(move || {
-        wayland::get_text()
-          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
+  pub fn get_preferred_text(&self, precedence: Vec<String>) -> Result<Option<String>> {
+    let formats = precedence
+      .iter()
+      .map(|format| match normalize_format_query(format) {
+        Ok(format @ ("text" | "html" | "rtf")) => Ok(format),
+        _ => Err(Error::new(
+          Status::InvalidArg,
+          format!("Unsupported text format: {format}"),
+        )),
       })
-      .await
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
-    }
+      .collect::<Result<Vec<_>>>()?;
 
-    let context = ClipboardContext::new().map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to create clipboard context: {e}"),
-      )
-    })?;
+    for format in formats {
+      if !self.has_format(format.to_string())? {
+        continue;
+      }
+      let text = match format {
+        "text" => self.get_text().ok(),
+        "html" => self
+          .get_html_utf8()
+          .ok()
+          .map(|html| html::html_to_text(&html)),
+        _ => self.get_rich_text().ok().map(|rtf| rtf::rtf_to_text(&rtf)),
+      };
+      if let Some(text) = text.filter(|text| !text.trim().is_empty()) {
+        return Ok(Some(text));
+      }
+    }
+    Ok(None)
+  }
 
-    tokio::task::spawn_blocking(move || {
-      context
-        .get_text()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
-    })
-    .await
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  /// 获取按指定 shell（bash、powershell、cmd）规则转义后的文本，可作为单个参数直接粘贴到命令行
+  /// cmd 的结果只适用于交互式 `cmd.exe` 提示符，不适用于批处理文件，也不转义延迟展开使用的 `!`
+  /// 不支持的 shell 名称或无法转义的文本（例如 cmd 下包含换行）返回 InvalidArg 错误
+  #[napi]
+  pub fn get_text_shell_escaped(&self, shell: String) -> Result<String> {
+    let text = self.get_text()?;
+    shell_escape(&text, &shell)
   }
 
-  /// 异步设置剪贴板文本内容
+  /// 获取去除首尾空白后的文本，剪贴板没有文本或文本为空白时返回 null
   #[napi]
-  pub async fn set_text_async(&self, text: String) -> Result<()> {
+  pub fn get_text_trimmed(&self) -> Result<Option<String>> {
+    if !self.has_format("text".to_string())? {
+      return Ok(None);
+    }
+
+    let text = self.get_text()?;
+    let trimmed = text.trim();
+    Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+  }
+
+  /// 将剪贴板文本的 UTF-8 字节写入调用方提供的 Buffer，返回写入的字节数
+  /// 文本大于 Buffer 容量时返回 InvalidArg 错误，Buffer 内容保持不变
+  #[napi]
+  pub fn get_text_into(&self, mut buffer: Buffer) -> Result<u32> {
+    let text = self.get_text()?;
+    let bytes = text.as_bytes();
+    if bytes.len() > buffer.len() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Buffer is too small: text needs {} bytes but buffer has {}",
+          bytes.len(),
+          buffer.len()
+        ),
+      ));
+    }
+
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    Ok(bytes.len() as u32)
+  }
+
+  /// 剪贴板文本为已存在的绝对路径时返回该路径（去除首尾空白），否则返回 null
+  /// 支持 POSIX 绝对路径、Windows 盘符路径和 UNC 路径
+  #[napi]
+  pub fn get_text_as_path(&self) -> Result<Option<String>> {
+    let text = match self.get_text() {
+      Ok(text) => text,
+      Err(_) => return Ok(None),
+    };
+
+    let candidate = text.trim();
+    if candidate.contains(['\n', '\r']) || !is_absolute_path_like(candidate) {
+      return Ok(None);
+    }
+    Ok(
+      std::fs::metadata(candidate)
+        .ok()
+        .map(|_| candidate.to_string()),
+    )
+  }
+
+  /// 获取剪贴板中的 HTML 内容
+  #[napi]
+  pub fn get_html(&self) -> Result<String> {
+    if let Some(result) = read_test_snapshot(|snapshot| snapshot.html.clone(), "Failed to get HTML")
+    {
+      return result;
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      return tokio::task::spawn_blocking(move || {
-        wayland::set_text(text)
-          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
-      })
-      .await
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+      return wayland::get_html()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")));
     }
 
-    let context = ClipboardContext::new().map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to create clipboard context: {e}"),
-      )
-    })?;
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .get_html()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")))
+  }
 
-    tokio::task::spawn_blocking(move || {
-      context
-        .set_text(text)
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
-    })
-    .await
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  /// 获取剪贴板中的 HTML 内容，并将其字符编码统一转换为 UTF-8
+  /// 依次根据 BOM、meta charset 声明与内容特征检测原始编码，同时去除 Windows 的 CF_HTML 头部
+  #[napi]
+  pub fn get_html_utf8(&self) -> Result<String> {
+    if let Some(result) = read_test_snapshot(|snapshot| snapshot.html.clone(), "Failed to get HTML")
+    {
+      return result;
+    }
+
+    Ok(html::decode_html(&self.read_raw_html()?))
   }
 
-  /// 异步获取剪贴板图片数据（以 base64 编码返回）
+  /// 获取剪贴板中的 HTML 内容并清理为可安全渲染的 HTML
+  /// 仅保留白名单中的元素与属性，移除脚本、事件处理属性以及 javascript: 等危险链接
   #[napi]
-  pub async fn get_image_base64_async(&self) -> Result<String> {
+  pub fn get_html_sanitized(&self) -> Result<String> {
+    Ok(html::sanitize_html(&self.get_html_utf8()?))
+  }
+
+  /// 获取剪贴板 HTML 通过 BOM 或 `<meta charset>`、`content-type` meta 标签声明的编码名称，不解码内容
+  /// 没有声明时返回 null；剪贴板中没有 HTML 时返回错误
+  #[napi]
+  pub fn get_html_charset(&self) -> Result<Option<String>> {
+    let raw = if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.html.clone(), "Failed to get HTML")
+    {
+      result?.into_bytes()
+    } else {
+      self.read_raw_html()?
+    };
+    Ok(html::declared_charset(&raw).map(str::to_string))
+  }
+
+  /// 检查剪贴板中的纯文本与 HTML 的文本内容是否一致
+  /// 去除 HTML 标签后与纯文本比较，比较时合并连续空白；任一格式不存在时返回错误
+  #[napi]
+  pub fn check_text_html_consistency(&self) -> Result<bool> {
+    let text = self.get_text()?;
+    let html = self.get_html()?;
+    let fragment = html::cf_html_fragment(html.as_bytes())
+      .map(String::from_utf8_lossy)
+      .unwrap_or_else(|| html.as_str().into());
+    Ok(
+      html::normalize_whitespace(&html::html_to_text(&fragment))
+        == html::normalize_whitespace(&text),
+    )
+  }
+
+  /// 设置剪贴板中的 HTML 内容
+  #[napi]
+  pub fn set_html(&self, html: String) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        html: Some(html),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      return tokio::task::spawn_blocking(move || {
-        let image_data = wayland::get_image_raw()
-          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-        let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
-        Ok(BASE64_STANDARD.encode(png_bytes))
-      })
-      .await
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+      return wayland::set_html(html)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")));
     }
 
-    let context = ClipboardContext::new().map_err(|e| {
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_html(html)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")))
+  }
+
+  /// 获取剪贴板中的富文本内容
+  #[napi]
+  pub fn get_rich_text(&self) -> Result<String> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.rtf.clone(), "Failed to get rich text")
+    {
+      return result;
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_rich_text().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get rich text: {e}"),
+        )
+      });
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.get_rich_text().map_err(|e| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to create clipboard context: {e}"),
+        format!("Failed to get rich text: {e}"),
       )
-    })?;
-
-    tokio::task::spawn_blocking(move || {
-      let image_data = context
-        .get_image()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-      let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-      Ok(BASE64_STANDARD.encode(png_bytes))
     })
-    .await
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
   }
 
-  /// 异步获取剪贴板图片详细信息（包含宽度、高度、大小和原始数据）
+  /// 获取 RTF 富文本中的纯文本：跳过控制字与字体表等不显示的组，解码转义字符
+  /// 剪贴板中没有 RTF 内容时返回错误
   #[napi]
-  pub async fn get_image_data_async(&self) -> Result<ImageData> {
+  pub fn get_rtf_as_text(&self) -> Result<String> {
+    Ok(rtf::rtf_to_text(&self.get_rich_text()?))
+  }
+
+  /// 设置剪贴板中的富文本内容
+  #[napi]
+  pub fn set_rich_text(&self, text: String) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        rtf: Some(text),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
     #[cfg(target_os = "linux")]
     if is_wayland_environment() {
-      return tokio::task::spawn_blocking(move || {
-        let image_data = wayland::get_image_raw()
-          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-        image_bytes_to_image_data(&image_data, "Failed to get image")
-      })
-      .await
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+      return wayland::set_rich_text(text).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to set rich text: {e}"),
+        )
+      });
     }
 
-    let context = ClipboardContext::new().map_err(|e| {
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.set_rich_text(text).map_err(|e| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to create clipboard context: {e}"),
+        format!("Failed to set rich text: {e}"),
       )
-    })?;
+    })
+  }
 
-    tokio::task::spawn_blocking(move || {
-      let image_data = context
-        .get_image()
-        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+  /// 设置代码片段：HTML 格式为转义后的 `<pre><code>` 片段，纯文本格式为原始代码
+  /// 指定 language 时为 code 元素添加 `language-<语言>` 样式类
+  #[napi]
+  pub fn set_code(&self, text: String, language: Option<String>) -> Result<()> {
+    let html = code_to_html(&text, language.as_deref())?;
+    self.set_contents(ClipboardData {
+      text: Some(text),
+      html: Some(html),
+      ..Default::default()
+    })
+  }
 
-      let (width, height) = image_data.get_size();
-      let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-      Ok(ImageData {
-        width,
-        height,
-        size: png_bytes.len() as u32,
-        data: Buffer::from(png_bytes),
+  /// 设置带样式类的文本：HTML 格式为转义后的 `<span class="...">` 片段，纯文本格式为原始文本
+  #[napi]
+  pub fn set_styled_text(&self, text: String, css_class: String) -> Result<()> {
+    let html = styled_text_to_html(&text, &css_class)?;
+    self.set_contents(ClipboardData {
+      text: Some(text),
+      html: Some(html),
+      ..Default::default()
+    })
+  }
+
+  /// 将文本以 separator（默认为换行）分隔追加到剪贴板现有文本之后，现有文本的最后一段已是该文本时不追加
+  /// 剪贴板没有文本时直接写入；返回是否写入了剪贴板，写入后剪贴板只包含文本格式
+  #[napi]
+  pub fn append_text_unique(&self, text: String, separator: Option<String>) -> Result<bool> {
+    if text.is_empty() {
+      return Ok(false);
+    }
+    let separator = separator.unwrap_or_else(|| "\n".to_string());
+
+    // 读取与写入在同一次加锁中完成，避免其他线程在两者之间修改剪贴板
+    let _lock = lock::acquire();
+    let current = if self.has_format("text".to_string())? {
+      self.get_text()?
+    } else {
+      String::new()
+    };
+    if current.is_empty() {
+      self.set_text(text)?;
+      return Ok(true);
+    }
+    if current == text || current.ends_with(&format!("{separator}{text}")) {
+      return Ok(false);
+    }
+    self.set_text(format!("{current}{separator}{text}"))?;
+    Ok(true)
+  }
+
+  /// 在同一次写入中以多种形式设置颜色：纯文本（不透明时为 `#RRGGBB`，否则为 `rgba(...)`）、
+  /// HTML 色块以及可选的 1×1 图片；formats 控制包含哪些形式，至少需要包含一种
+  #[napi]
+  pub fn set_color(&self, color: RgbaColor, formats: Option<ColorFormats>) -> Result<()> {
+    let alpha = color.a.unwrap_or(1.0);
+    if !(0.0..=1.0).contains(&alpha) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid alpha value: {alpha}"),
+      ));
+    }
+    let formats = formats.unwrap_or_default();
+    let (include_text, include_html, include_image) = (
+      formats.text.unwrap_or(true),
+      formats.html.unwrap_or(true),
+      formats.image.unwrap_or(false),
+    );
+    if !include_text && !include_html && !include_image {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "At least one color format must be included".to_string(),
+      ));
+    }
+
+    let RgbaColor { r, g, b, .. } = color;
+    let css = if alpha == 1.0 {
+      format!("#{r:02X}{g:02X}{b:02X}")
+    } else {
+      format!("rgba({r}, {g}, {b}, {})", (alpha * 1000.0).round() / 1000.0)
+    };
+    let image = if include_image {
+      let pixel =
+        image::RgbaImage::from_pixel(1, 1, image::Rgba([r, g, b, (alpha * 255.0).round() as u8]));
+      let bytes =
+        dynamic_image_to_png_bytes(&DynamicImage::ImageRgba8(pixel), "Failed to set color")?;
+      Some(ImageData {
+        width: 1,
+        height: 1,
+        size: bytes.len() as u32,
+        data: Buffer::from(bytes),
       })
+    } else {
+      None
+    };
+
+    self.set_contents(ClipboardData {
+      html: include_html.then(|| format!("<span style=\"background:{css}\">{css}</span>")),
+      text: include_text.then_some(css),
+      image,
+      ..Default::default()
     })
-    .await
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
   }
-}
 
-// 便利的静态函数，用于快速操作剪贴板
+  /// 获取剪贴板中的图片数据（以 base64 编码返回）
+  #[napi]
+  pub fn get_image_base64(&self) -> Result<String> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.image.clone(), "Failed to get image")
+    {
+      let png_bytes = image_bytes_to_png_bytes(&result?, "Failed to get image")?;
+      return Ok(BASE64_STANDARD.encode(png_bytes));
+    }
 
-/// 快速获取剪贴板文本内容
-#[napi]
-pub fn get_clipboard_text() -> Result<String> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::get_text()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")));
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let image_data = wayland::get_image_raw()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+      let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
+      return Ok(BASE64_STANDARD.encode(png_bytes));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let image_data = context
+      .get_image()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+    let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+    Ok(BASE64_STANDARD.encode(png_bytes))
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 获取剪贴板中的图片详细信息（包含宽度、高度、大小和原始数据）
+  #[napi]
+  pub fn get_image_data(&self) -> Result<ImageData> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.image.clone(), "Failed to get image")
+    {
+      return image_bytes_to_image_data(&result?, "Failed to get image");
+    }
 
-  context
-    .get_text()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
-}
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let image_data = wayland::get_image_raw()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+      return image_bytes_to_image_data(&image_data, "Failed to get image");
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let image_data = context
+      .get_image()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+    let (width, height) = image_data.get_size();
+    let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+    Ok(ImageData {
+      width,
+      height,
+      size: png_bytes.len() as u32,
+      data: Buffer::from(png_bytes),
+    })
+  }
+
+  /// 严格模式获取剪贴板中的图片详细信息
+  /// 仅当图片为普通的单帧栅格图片时返回，动画、多页或无法识别的图片返回错误而不是静默转换为单帧 PNG
+  #[napi]
+  pub fn get_image_strict(&self) -> Result<ImageData> {
+    let source = self.read_source_image()?;
+    if source.transcoded {
+      // 平台只提供了解码后的位图，本身就是单帧栅格图片
+      return image_bytes_to_image_data(&source.bytes, "Failed to get image strictly");
+    }
+
+    let reject = |reason: String| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get image strictly: {reason}"),
+      )
+    };
+
+    let mime_type = imaging::sniff_image_mime(&source.bytes)
+      .ok_or_else(|| reject("unsupported image format".to_string()))?;
+    if mime_type == "image/tiff" && imaging::is_multi_page_tiff(&source.bytes) {
+      return Err(reject("multi-page TIFF image".to_string()));
+    }
+
+    let animation = imaging::analyze_animation(&source.bytes)
+      .map_err(|e| reject(format!("unsupported {mime_type} image: {e}")))?;
+    if animation.is_animated() {
+      return Err(reject(format!(
+        "animated {mime_type} image with {} frames",
+        animation.frame_count
+      )));
+    }
+
+    image_bytes_to_image_data(&source.bytes, "Failed to get image strictly")
+  }
+
+  /// 获取按 EXIF 方向信息旋转或翻转后的剪贴板图片，使手机照片等带方向标记的图片正向显示
+  /// 没有方向信息（包括平台只提供解码后的位图）或无法解码原始数据时与 getImageData 相同
+  #[napi]
+  pub fn get_image_oriented(&self) -> Result<ImageData> {
+    let source = self.read_source_image()?;
+    let Ok(image) = imaging::decode_oriented(&source.bytes) else {
+      return self.get_image_data();
+    };
+    let png_bytes = dynamic_image_to_png_bytes(&image, "Failed to get image")?;
+    Ok(ImageData {
+      width: image.width(),
+      height: image.height(),
+      size: png_bytes.len() as u32,
+      data: Buffer::from(png_bytes),
+    })
+  }
+
+  /// 从剪贴板 HTML 中提取第一张以 data URL 内嵌的图片，适用于只复制了 HTML 而没有单独图片格式的网页内容
+  /// 剪贴板中没有 HTML 或 HTML 中没有内嵌图片时返回 null，内嵌数据无法解码时返回错误
+  #[napi]
+  pub fn extract_image_from_html(&self) -> Result<Option<ImageData>> {
+    if !self.has_format("html".to_string())? {
+      return Ok(None);
+    }
+    let Some(src) = html::first_data_image_src(&self.get_html()?) else {
+      return Ok(None);
+    };
+    let bytes = decode_base64_data_url(&src).ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        "Failed to extract image from HTML: data URL is not valid base64".to_string(),
+      )
+    })?;
+    image_bytes_to_image_data(&bytes, "Failed to extract image from HTML").map(Some)
+  }
+
+  /// 获取剪贴板图片的动画帧信息，支持 GIF、APNG 和 WebP，其他格式视为静态图片
+  /// 平台只提供解码后的位图时同样视为静态图片，剪贴板中没有图片时返回错误
+  #[napi]
+  pub fn get_image_animation_info(&self) -> Result<AnimationInfo> {
+    let source = self.read_source_image()?;
+    let animation = imaging::analyze_animation(&source.bytes).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get image animation info: {e}"),
+      )
+    })?;
+    Ok(AnimationInfo {
+      frame_count: animation.frame_count,
+      total_duration_ms: animation.total_duration_ms,
+      is_animated: animation.is_animated(),
+    })
+  }
+
+  /// 从 base64 编码的图片数据设置剪贴板图片
+  #[napi]
+  pub fn set_image_base64(&self, base64_data: String) -> Result<()> {
+    let image_data = BASE64_STANDARD
+      .decode(base64_data)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid base64 data: {e}")))?;
+
+    let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create image from bytes: {e}"),
+      )
+    })?;
+
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        image: Some(image_data),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
+      return wayland::set_image_raw(png_bytes)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_image(rust_image)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+  }
+
+  /// 从原始字节数据设置剪贴板图片
+  #[napi]
+  pub fn set_image_raw(&self, image_data: Buffer) -> Result<()> {
+    let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create image from bytes: {e}"),
+      )
+    })?;
+
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        image: Some(image_data.to_vec()),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
+      return wayland::set_image_raw(png_bytes)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_image(rust_image)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+  }
+
+  /// 将多张图片分别作为独立的剪贴板项目写入，便于一次粘贴多张图片（例如粘贴到 Keynote）
+  /// 仅 macOS 支持多项目剪贴板，此时图片统一转换为 PNG 并返回 true；其他平台只写入第一张图片并返回 false
+  /// 测试模式的快照只能保存一张图片，macOS 上同样只写入第一张并返回 false；images 为空时返回 InvalidArg 错误
+  #[napi]
+  pub fn set_images(&self, images: Vec<Buffer>) -> Result<bool> {
+    if images.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "No images to set".to_string(),
+      ));
+    }
+
+    #[cfg(target_os = "macos")]
+    if !test_mode::is_enabled() {
+      let png_images = images
+        .iter()
+        .map(|image| image_bytes_to_png_bytes(image, "Failed to set images"))
+        .collect::<Result<Vec<_>>>()?;
+      let _lock = lock::acquire();
+      macos::set_general_pasteboard_images(&png_images)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set images: {e}")))?;
+      return Ok(true);
+    }
+
+    let mut images = images;
+    self.set_image_raw(images.swap_remove(0))?;
+    Ok(false)
+  }
+
+  /// 将已编码的图片字节按文件头识别的 MIME 类型原样写入平台对应的图片格式，不解码也不重新编码
+  /// Windows 写入 PNG、JFIF、GIF 注册格式（只读取 CF_DIB 的旧程序无法粘贴），macOS 写入对应的 UTI，Linux 写入 MIME 类型；
+  /// 平台没有对应格式时回退为 setImageRaw 的解码写入，无法识别的数据返回 InvalidArg 错误
+  #[napi]
+  pub fn set_image_bytes_native(&self, buffer: Buffer) -> Result<()> {
+    let mime = imaging::sniff_image_mime(&buffer)
+      .ok_or_else(|| Error::new(Status::InvalidArg, "Unrecognized image data".to_string()))?;
+    match native_image_format(mime) {
+      // 测试模式的快照只保存标准格式，按普通图片写入
+      Some(format) if !test_mode::is_enabled() => self.set_buffer(format.to_string(), buffer),
+      _ => self.set_image_raw(buffer),
+    }
+  }
+
+  /// 检查数据能否被解码为图片，不会修改剪贴板
+  #[napi]
+  pub fn is_valid_image(image_data: Buffer) -> bool {
+    RustImageData::from_bytes(&image_data).is_ok()
+  }
+
+  /// 将解码后的图片设置到剪贴板
+  fn set_dynamic_image(&self, image: DynamicImage) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        image: Some(dynamic_image_to_png_bytes(&image, "Failed to set image")?),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let png_bytes = dynamic_image_to_png_bytes(&image, "Failed to set image")?;
+      return wayland::set_image_raw(png_bytes)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_image(RustImageData::from_dynamic_image(image))
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+  }
+
+  /// 裁剪图片到指定矩形区域后设置到剪贴板
+  /// 矩形超出图片范围时返回 InvalidArg 错误
+  #[napi]
+  pub fn set_image_cropped(
+    &self,
+    image_data: Buffer,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+  ) -> Result<()> {
+    let image = decode_image_bytes(&image_data, "Failed to set image")?;
+    let in_bounds = |offset: u32, length: u32, limit: u32| {
+      length > 0 && offset.checked_add(length).is_some_and(|end| end <= limit)
+    };
+    if !in_bounds(x, width, image.width()) || !in_bounds(y, height, image.height()) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Crop rectangle ({x}, {y}, {width}x{height}) exceeds image bounds ({}x{})",
+          image.width(),
+          image.height()
+        ),
+      ));
+    }
+
+    self.set_dynamic_image(image.crop_imm(x, y, width, height))
+  }
+
+  /// 将图片按原比例缩放到画布范围内，居中放置在指定尺寸的透明画布上后设置到剪贴板
+  #[napi]
+  pub fn set_image_padded(
+    &self,
+    image_data: Buffer,
+    canvas_width: u32,
+    canvas_height: u32,
+  ) -> Result<()> {
+    if canvas_width == 0 || canvas_height == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid canvas size: {canvas_width}x{canvas_height}"),
+      ));
+    }
+
+    let image = decode_image_bytes(&image_data, "Failed to set image")?;
+    let scaled = image
+      .resize(
+        canvas_width,
+        canvas_height,
+        image::imageops::FilterType::Lanczos3,
+      )
+      .to_rgba8();
+    let mut canvas = image::RgbaImage::new(canvas_width, canvas_height);
+    image::imageops::overlay(
+      &mut canvas,
+      &scaled,
+      i64::from((canvas_width - scaled.width()) / 2),
+      i64::from((canvas_height - scaled.height()) / 2),
+    );
+
+    self.set_dynamic_image(DynamicImage::ImageRgba8(canvas))
+  }
+
+  /// 设置剪贴板图片，最长边超过 max_dimension 时按原比例缩小到该尺寸，否则原样设置
+  #[napi]
+  pub fn set_image_capped(&self, image_data: Buffer, max_dimension: u32) -> Result<()> {
+    if max_dimension == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "maxDimension must be greater than 0".to_string(),
+      ));
+    }
+
+    let (width, height) = image_bytes_dimensions(&image_data, "Failed to set image")?;
+    if width.max(height) <= max_dimension {
+      return self.set_image_raw(image_data);
+    }
+    let image = decode_image_bytes(&image_data, "Failed to set image")?;
+    self.set_dynamic_image(image.resize(
+      max_dimension,
+      max_dimension,
+      image::imageops::FilterType::Lanczos3,
+    ))
+  }
+
+  /// 将图片合成到指定颜色的纯色背景上并去除透明通道后设置到剪贴板
+  /// 适用于粘贴到不支持透明度的程序（如旧版 Office），避免透明区域显示为黑色
+  #[napi]
+  pub fn set_image_flattened(&self, image_data: Buffer, background: RgbColor) -> Result<()> {
+    let image = decode_image_bytes(&image_data, "Failed to set image")?;
+    let flattened = imaging::flatten_onto(&image, [background.r, background.g, background.b]);
+    self.set_dynamic_image(flattened)
+  }
+
+  /// 从 canvas 的 ImageData（`{ data, width, height }`）直接设置剪贴板图片，无需先在 JS 中编码为 PNG
+  /// data 的长度必须等于 width × height × 4，否则返回 InvalidArg 错误
+  #[napi]
+  pub fn set_image_from_canvas(&self, image: CanvasImageData) -> Result<()> {
+    let expected = u64::from(image.width) * u64::from(image.height) * 4;
+    if image.width == 0 || image.height == 0 || image.data.len() as u64 != expected {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Canvas data length {} does not match {}x{} RGBA pixels",
+          image.data.len(),
+          image.width,
+          image.height
+        ),
+      ));
+    }
+
+    let pixels = image::RgbaImage::from_raw(image.width, image.height, image.data.to_vec())
+      .ok_or_else(|| Error::new(Status::InvalidArg, "Invalid canvas image data".to_string()))?;
+    self.set_dynamic_image(DynamicImage::ImageRgba8(pixels))
+  }
+
+  /// 将文本生成二维码图片并设置到剪贴板，未指定 data 时使用剪贴板中的当前文本
+  /// 没有可用文本或文本超出二维码容量时返回 InvalidArg 错误
+  #[napi]
+  pub fn set_qr_from_text(&self, data: Option<String>) -> Result<()> {
+    let data = match data {
+      Some(data) => data,
+      None => self.get_text().map_err(|e| {
+        Error::new(
+          Status::InvalidArg,
+          format!("No text available for QR code: {}", e.reason),
+        )
+      })?,
+    };
+    if data.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "No text available for QR code".to_string(),
+      ));
+    }
+
+    let image = imaging::render_qr_code(data.as_bytes()).map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Failed to generate QR code: {e}"),
+      )
+    })?;
+    self.set_dynamic_image(image)
+  }
+
+  /// 创建分块写入图片的写入器，数据在原生侧累积，调用 finish 后解码并设置到剪贴板
+  /// max_size 为允许的最大字节数，默认 64 MiB，超出时写入失败并丢弃已接收的数据
+  #[napi]
+  pub fn set_image_from_stream(&self, max_size: Option<u32>) -> Result<ImageStreamWriter> {
+    Ok(ImageStreamWriter {
+      manager: ClipboardManager::new()?,
+      buffer: Some(Vec::new()),
+      max_size: max_size.map_or(DEFAULT_IMAGE_STREAM_MAX_SIZE, |size| size as usize),
+    })
+  }
+
+  /// 获取剪贴板中的图片原始数据（Buffer）
+  #[napi]
+  pub fn get_image_raw(&self) -> Result<Buffer> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.image.clone(), "Failed to get image")
+    {
+      let png_bytes = image_bytes_to_png_bytes(&result?, "Failed to get image")?;
+      return Ok(Buffer::from(png_bytes));
+    }
+
+    if let Some(png_bytes) = self.take_cached_png() {
+      return Ok(Buffer::from(png_bytes));
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let image_data = wayland::get_image_raw()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+      let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
+      return Ok(Buffer::from(png_bytes));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let image_data = context
+      .get_image()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+    let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+    Ok(Buffer::from(png_bytes))
+  }
+
+  /// 获取剪贴板图片的尺寸及 PNG 编码后的大小，图片数据保留在原生侧
+  /// 支持剪贴板序列号的平台（Windows 与 macOS）会缓存编码结果，剪贴板未变化时下一次 getImageRaw 直接复用
+  #[napi]
+  pub fn get_image_info(&self) -> Result<ImageInfo> {
+    let sequence = clipboard_sequence_number();
+    let (width, height, png_bytes) = self.read_png_image()?;
+    let info = ImageInfo {
+      width,
+      height,
+      png_size: png_bytes.len() as u32,
+    };
+
+    let mut cache = self
+      .png_cache
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *cache = sequence.map(|sequence| CachedPng {
+      sequence,
+      bytes: png_bytes,
+    });
+    Ok(info)
+  }
+
+  /// 以 Blob 兼容的结构获取剪贴板图片，MIME 类型反映图片的实际源格式
+  /// 无法获取源格式数据时返回 PNG 编码的图片
+  #[napi]
+  pub fn get_image_as_blob(&self) -> Result<BlobData> {
+    let source = self.read_source_image()?;
+    let mime_type = imaging::sniff_image_mime(&source.bytes).unwrap_or("image/png");
+    let timestamp = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map(|elapsed| elapsed.as_millis())
+      .unwrap_or(0);
+
+    Ok(BlobData {
+      mime_type: mime_type.to_string(),
+      suggested_name: format!(
+        "clipboard-{timestamp}.{}",
+        imaging::image_mime_extension(mime_type)
+      ),
+      data: Buffer::from(source.bytes),
+    })
+  }
+
+  /// 获取剪贴板图片的原始数据及其 MIME 类型和尺寸
+  /// 平台提供原始格式数据时原样返回，否则返回 PNG 编码的图片并将 wasTranscoded 设为 true
+  /// 无法直接解析尺寸的 HEIC/HEIF 图片通过 libheif 解码后转码，编译时未启用 `heic` 特性时返回错误
+  #[napi]
+  pub fn get_image_native(&self) -> Result<NativeImage> {
+    let source = self.read_source_image()?;
+    if let Some(mime_type) = imaging::sniff_image_mime(&source.bytes) {
+      // 无法解析尺寸的格式（如 HEIC）在下方解码后转码为 PNG
+      if let Ok((width, height)) = image_bytes_dimensions(&source.bytes, "Failed to get image") {
+        return Ok(NativeImage {
+          mime_type: mime_type.to_string(),
+          data: Buffer::from(source.bytes),
+          width,
+          height,
+          was_transcoded: source.transcoded,
+        });
+      }
+    }
+
+    let image = match imaging::sniff_image_mime(&source.bytes) {
+      Some("image/heic" | "image/heif") => imaging::decode_heic(&source.bytes).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to decode HEIC image: {e}"),
+        )
+      })?,
+      _ => decode_image_bytes(&source.bytes, "Failed to get image")?,
+    };
+    let png_bytes = dynamic_image_to_png_bytes(&image, "Failed to get image")?;
+    Ok(NativeImage {
+      mime_type: "image/png".to_string(),
+      data: Buffer::from(png_bytes),
+      width: image.width(),
+      height: image.height(),
+      was_transcoded: true,
+    })
+  }
+
+  /// 获取剪贴板图片的主色调，缩小图片后统计得到，剪贴板中没有图片时返回错误
+  #[napi]
+  pub fn get_image_dominant_color(&self) -> Result<RgbColor> {
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    let [r, g, b] = imaging::dominant_color(&image);
+    Ok(RgbColor { r, g, b })
+  }
+
+  /// 获取剪贴板图片中完全透明像素所占的比例（0.0–1.0），没有 alpha 通道的图片返回 0
+  /// 剪贴板中没有图片时返回错误
+  #[napi]
+  pub fn get_image_transparency_ratio(&self) -> Result<f64> {
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    Ok(imaging::transparency_ratio(&image))
+  }
+
+  /// 将剪贴板图片切分为 rows × cols 个尺寸相近的图块，按行优先顺序返回各图块的 PNG 数据
+  /// rows 或 cols 为 0、或超过图片的高度或宽度时返回 InvalidArg 错误
+  #[napi]
+  pub fn get_image_tiles(&self, rows: u32, cols: u32) -> Result<Vec<ImageData>> {
+    if rows == 0 || cols == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("rows and cols must be greater than 0, got {rows}x{cols}"),
+      ));
+    }
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    if rows > image.height() || cols > image.width() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Cannot split a {}x{} image into {rows} rows and {cols} columns",
+          image.width(),
+          image.height()
+        ),
+      ));
+    }
+
+    imaging::split_tiles(&image, rows, cols)
+      .iter()
+      .map(|tile| {
+        let png_bytes = dynamic_image_to_png_bytes(tile, "Failed to get image tiles")?;
+        Ok(ImageData {
+          width: tile.width(),
+          height: tile.height(),
+          size: png_bytes.len() as u32,
+          data: Buffer::from(png_bytes),
+        })
+      })
+      .collect()
+  }
+
+  /// 获取剪贴板图片并转换为 PNG，HEIC/HEIF 图片（例如从 macOS 照片复制）先通过 libheif 解码
+  /// 其他格式按常规方式解码；需要解码 HEIC 但编译时未启用 `heic` 特性时返回错误
+  #[napi]
+  pub fn get_image_decoding_heic(&self) -> Result<ImageData> {
+    let source = self.read_source_image()?;
+    if !matches!(
+      imaging::sniff_image_mime(&source.bytes),
+      Some("image/heic" | "image/heif")
+    ) {
+      return image_bytes_to_image_data(&source.bytes, "Failed to get image");
+    }
+
+    let image = imaging::decode_heic(&source.bytes).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to decode HEIC image: {e}"),
+      )
+    })?;
+    let png_bytes = dynamic_image_to_png_bytes(&image, "Failed to get image")?;
+    Ok(ImageData {
+      width: image.width(),
+      height: image.height(),
+      size: png_bytes.len() as u32,
+      data: Buffer::from(png_bytes),
+    })
+  }
+
+  /// 比较剪贴板图片与给定图片的视觉相似度（0.0–1.0），基于感知哈希，对缩放和压缩失真不敏感
+  /// 剪贴板中没有图片或给定数据不是图片时返回错误
+  #[napi]
+  pub fn compare_image_to(&self, buffer: Buffer) -> Result<f64> {
+    let other = decode_image_bytes(&buffer, "Failed to compare image")?;
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    Ok(imaging::perceptual_similarity(&image, &other))
+  }
+
+  /// 将剪贴板图片解码一次后分别编码为多种格式，格式描述为 `png`、`jpeg:<quality>` 或 `webp`
+  /// 结果与 formats 的顺序一致；格式描述无效时在读取剪贴板之前返回 InvalidArg 错误
+  #[napi]
+  pub fn get_image_encodings(&self, formats: Vec<String>) -> Result<Vec<EncodedImage>> {
+    if formats.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "At least one image format is required".to_string(),
+      ));
+    }
+    let encodings = formats
+      .iter()
+      .map(|format| ImageEncoding::parse(format))
+      .collect::<Result<Vec<_>>>()?;
+
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    formats
+      .into_iter()
+      .zip(encodings)
+      .map(|(format, encoding)| {
+        let bytes = encode_dynamic_image(&image, encoding, "Failed to get image")?;
+        Ok(EncodedImage {
+          format,
+          size: bytes.len() as u32,
+          data: Buffer::from(bytes),
+        })
+      })
+      .collect()
+  }
+
+  /// 获取压缩到指定字节数以内的 JPEG 图片
+  /// 依次降低 JPEG 质量，仍超出时按比例缩小图片后重试，最小质量和尺寸下仍无法满足时返回错误
+  #[napi]
+  pub fn get_image_under_size(&self, max_bytes: f64) -> Result<Buffer> {
+    if !max_bytes.is_finite() || max_bytes <= 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid byte budget: {max_bytes}"),
+      ));
+    }
+
+    let source = self.read_source_image()?;
+    let image = decode_image_bytes(&source.bytes, "Failed to get image")?;
+    let mut scale = 1.0;
+    let mut smallest = usize::MAX;
+    loop {
+      let width = (f64::from(image.width()) * scale).round() as u32;
+      let height = (f64::from(image.height()) * scale).round() as u32;
+      if scale < 1.0 && (width < BUDGET_MIN_DIMENSION || height < BUDGET_MIN_DIMENSION) {
+        break;
+      }
+
+      let scaled = if scale < 1.0 {
+        image.resize_exact(width, height, image::imageops::FilterType::Triangle)
+      } else {
+        image.clone()
+      };
+      for quality in BUDGET_JPEG_QUALITIES {
+        let bytes = dynamic_image_to_jpeg_bytes(&scaled, quality, "Failed to get image")?;
+        if bytes.len() as f64 <= max_bytes {
+          return Ok(Buffer::from(bytes));
+        }
+        smallest = smallest.min(bytes.len());
+      }
+      scale *= BUDGET_SCALE_STEP;
+    }
+
+    Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "Unable to encode image under {max_bytes} bytes, smallest result was {smallest} bytes"
+      ),
+    ))
+  }
+
+  /// 获取剪贴板中的文件列表
+  #[napi]
+  pub fn get_files(&self) -> Result<Vec<String>> {
+    if let Some(result) =
+      read_test_snapshot(|snapshot| snapshot.files.clone(), "Failed to get files")
+    {
+      return result;
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_files()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .get_files()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")))
+  }
+
+  /// 读取并解析剪贴板中的 `text/uri-list` 格式，将本地文件路径与远程 URL 分开返回
+  #[napi]
+  pub fn get_uri_list(&self) -> Result<UriList> {
+    let payload = self.get_buffer("text/uri-list".to_string())?;
+    Ok(parse_uri_list(&String::from_utf8_lossy(&payload)))
+  }
+
+  /// 获取规范化的文件路径：转换为绝对路径、解析符号链接并统一分隔符，`file://` URI 会先转换为路径
+  /// strict 为 false（默认）时跳过无法解析的路径（例如已被删除的文件），为 true 时遇到无法解析的路径返回错误
+  #[napi]
+  pub fn get_files_canonical(&self, strict: Option<bool>) -> Result<Vec<String>> {
+    let strict = strict.unwrap_or(false);
+    let mut canonical = Vec::new();
+    for file in self.get_files()? {
+      let path = file_uri_to_path(&file).unwrap_or_else(|| file.clone());
+      match std::fs::canonicalize(&path) {
+        Ok(resolved) => canonical.push(path_to_clipboard_string(&resolved)?),
+        Err(e) if strict => {
+          return Err(Error::new(
+            Status::GenericFailure,
+            format!("Failed to resolve path {file}: {e}"),
+          ));
+        }
+        Err(_) => {}
+      }
+    }
+    Ok(canonical)
+  }
+
+  /// 获取去除重复项后的文件列表，按首次出现的顺序保留原始路径
+  /// 比较时解析符号链接并规范化路径，无法解析的路径（例如已被删除的文件）按原始字符串比较
+  #[napi]
+  pub fn get_files_deduplicated(&self) -> Result<Vec<String>> {
+    let mut seen = HashSet::new();
+    let mut files = self.get_files()?;
+    files.retain(|file| {
+      let path = file_uri_to_path(file).unwrap_or_else(|| file.clone());
+      let key = std::fs::canonicalize(&path).unwrap_or_else(|_| path.into());
+      seen.insert(key)
+    });
+    Ok(files)
+  }
+
+  /// 获取剪贴板中所有文件的总字节数，无法读取的文件（例如已被删除）计为 0
+  /// recursive 为 true 时累加目录中的全部内容，为 false（默认）时目录计为 0
+  #[napi]
+  pub fn get_files_total_size(&self, recursive: Option<bool>) -> Result<f64> {
+    let recursive = recursive.unwrap_or(false);
+    let total: u64 = self
+      .get_files()?
+      .iter()
+      .map(|file| {
+        let path = file_uri_to_path(file).unwrap_or_else(|| file.clone());
+        path_total_size(std::path::Path::new(&path), recursive)
+      })
+      .sum();
+    Ok(total as f64)
+  }
+
+  /// 将文件与目录（递归包含其内容）打包为 zip 归档，以 `application/zip` 自定义格式写入剪贴板
+  /// 适用于只能传递文本与二进制数据、无法传递原生文件引用的剪贴板同步通道
+  #[napi]
+  pub fn set_files_as_archive(&self, files: Vec<String>) -> Result<()> {
+    if files.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "No files to archive".to_string(),
+      ));
+    }
+    let paths: Vec<std::path::PathBuf> = files.iter().map(std::path::PathBuf::from).collect();
+    let archive = archive::zip_paths(&paths).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to archive files: {e}"),
+      )
+    })?;
+    self.set_buffer(ZIP_ARCHIVE_FORMAT.to_string(), Buffer::from(archive))
+  }
+
+  /// 将剪贴板中 `application/zip` 格式的归档解压到 dest_dir（不存在时自动创建），已有的同名文件会被覆盖
+  /// 返回解压出的顶层文件与目录路径，即打包时选择的各项
+  #[napi]
+  pub fn get_files_from_archive(&self, dest_dir: String) -> Result<Vec<String>> {
+    let archive = self.get_buffer(ZIP_ARCHIVE_FORMAT.to_string())?;
+    let paths = archive::unzip_to(&archive, std::path::Path::new(&dest_dir)).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to extract files from archive: {e}"),
+      )
+    })?;
+    paths
+      .iter()
+      .map(|path| path_to_clipboard_string(path))
+      .collect()
+  }
+
+  /// 获取按扩展名分组的文件列表，类别依次为 image、video、audio、document、archive、other
+  /// 只返回包含文件的类别
+  #[napi]
+  pub fn get_files_grouped(&self) -> Result<Vec<FileGroup>> {
+    let mut groups: Vec<FileGroup> = FILE_CATEGORIES
+      .iter()
+      .map(|(category, _)| *category)
+      .chain(std::iter::once("other"))
+      .map(|category| FileGroup {
+        category: category.to_string(),
+        paths: Vec::new(),
+      })
+      .collect();
+    for path in self.get_files()? {
+      let category = file_category(&path);
+      if let Some(group) = groups.iter_mut().find(|group| group.category == category) {
+        group.paths.push(path);
+      }
+    }
+    groups.retain(|group| !group.paths.is_empty());
+    Ok(groups)
+  }
+
+  /// 设置剪贴板中的文件列表
+  #[napi]
+  pub fn set_files(&self, files: Vec<String>) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot {
+        files: Some(files),
+        ..Default::default()
+      });
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::set_files(files)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_files(files)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")))
+  }
+
+  /// 以基础目录加相对路径的方式设置剪贴板中的文件列表
+  /// 每个相对路径都会拼接到 base_dir 后规范化，解析到 base_dir 之外的路径会被拒绝
+  #[napi]
+  pub fn set_files_relative(&self, base_dir: String, relative_paths: Vec<String>) -> Result<()> {
+    let base = std::fs::canonicalize(&base_dir).map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Invalid base directory {base_dir}: {e}"),
+      )
+    })?;
+    if !base.is_dir() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Base directory is not a directory: {base_dir}"),
+      ));
+    }
+
+    let mut files = Vec::with_capacity(relative_paths.len());
+    for relative_path in relative_paths {
+      let resolved = std::fs::canonicalize(base.join(&relative_path)).map_err(|e| {
+        Error::new(
+          Status::InvalidArg,
+          format!("Invalid relative path {relative_path}: {e}"),
+        )
+      })?;
+      if !resolved.starts_with(&base) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Path escapes base directory: {relative_path}"),
+        ));
+      }
+      files.push(path_to_clipboard_string(&resolved)?);
+    }
+
+    self.set_files(files)
+  }
+
+  /// 设置剪贴板中的自定义格式数据
+  #[napi]
+  pub fn set_buffer(&self, format: String, buffer: Buffer) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::set_buffer(format, buffer.to_vec())
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context
+      .set_buffer(&format, buffer.to_vec())
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")))
+  }
+
+  /// 以原始字节写入指定格式的数据，不做任何转换
+  /// platform_native 为 true 时 format 视为平台原生格式标识（如 Windows 的 `CF_DIB`、macOS 的 `public.tiff`），
+  /// 为 false 时与 setBuffer 相同，按自定义格式名称写入。macOS 与 Linux 的格式名称本身即为原生标识
+  #[napi]
+  pub fn set_raw_format(&self, format: String, data: Buffer, platform_native: bool) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    if platform_native {
+      return set_windows_native_format(&format, &data).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to set raw format: {e}"),
+        )
+      });
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = platform_native;
+
+    self.set_buffer(format, data)
+  }
+
+  /// 获取剪贴板中的自定义格式数据
+  #[napi]
+  pub fn get_buffer(&self, format: String) -> Result<Buffer> {
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let data = wayland::get_buffer(format)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
+      return Ok(Buffer::from(data));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let data = context
+      .get_buffer(&format)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
+    Ok(Buffer::from(data))
+  }
+
+  /// 读取自定义格式数据并按 chunk_size 字节切分，便于通过有消息大小限制的通道传输
+  /// 最后一块可能不足 chunk_size，数据为空时返回空数组；chunk_size 为 0 时返回 InvalidArg 错误
+  #[napi]
+  pub fn get_buffer_chunks(&self, format: String, chunk_size: u32) -> Result<Vec<Buffer>> {
+    if chunk_size == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "chunkSize must be greater than 0".to_string(),
+      ));
+    }
+    let data = self.get_buffer(format)?;
+    Ok(
+      data
+        .chunks(chunk_size as usize)
+        .map(|chunk| Buffer::from(chunk.to_vec()))
+        .collect(),
+    )
+  }
+
+  /// 按顺序拼接各块数据后写入自定义格式，与 getBufferChunks 配合使用
+  #[napi]
+  pub fn set_buffer_from_chunks(&self, format: String, chunks: Vec<Buffer>) -> Result<()> {
+    let mut data = Vec::with_capacity(chunks.iter().map(|chunk| chunk.len()).sum());
+    for chunk in &chunks {
+      data.extend_from_slice(chunk);
+    }
+    self.set_buffer(format, Buffer::from(data))
+  }
+
+  /// 获取剪贴板中指定格式数据的前 n 个字节，适用于大数据的类型嗅探
+  /// Wayland 下只从管道读取所需字节，其他平台读取完整数据后截断
+  #[napi]
+  pub fn peek_format(&self, format: String, n: u32) -> Result<Buffer> {
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let data = wayland::peek_buffer(format, n as usize).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to peek format: {e}"),
+        )
+      })?;
+      return Ok(Buffer::from(data));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let mut data = context.get_buffer(&format).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to peek format: {e}"),
+      )
+    })?;
+    data.truncate(n as usize);
+    Ok(Buffer::from(data))
+  }
+
+  /// 设置剪贴板中的复合内容（可同时设置多种格式）
+  #[napi]
+  pub fn set_contents(&self, contents: ClipboardData) -> Result<()> {
+    self.set_contents_with_extra(contents, Vec::new())
+  }
+
+  /// 设置复合内容，并在同一次写入中附加若干自定义格式数据
+  fn set_contents_with_extra(
+    &self,
+    contents: ClipboardData,
+    extra: Vec<(String, Vec<u8>)>,
+  ) -> Result<()> {
+    // 测试模式的快照只保存标准格式，附加的自定义格式不会保留
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot::from_data(contents));
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::set_contents_with_extra(contents, extra).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to set contents: {e}"),
+        )
+      });
+    }
+
+    use clipboard_rs::ClipboardContent;
+
+    let mut clipboard_contents = Vec::new();
+
+    if let Some(text) = contents.text {
+      clipboard_contents.push(ClipboardContent::Text(text));
+    }
+
+    if let Some(html) = contents.html {
+      clipboard_contents.push(ClipboardContent::Html(html));
+    }
+
+    if let Some(rtf) = contents.rtf {
+      clipboard_contents.push(ClipboardContent::Rtf(rtf));
+    }
+
+    if let Some(image_data) = contents.image {
+      let rust_image = RustImageData::from_bytes(image_data.data.as_ref()).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to create image from bytes: {e}"),
+        )
+      })?;
+
+      clipboard_contents.push(ClipboardContent::Image(rust_image));
+    }
+
+    if let Some(files) = contents.files {
+      clipboard_contents.push(ClipboardContent::Files(files));
+    }
+
+    for (format, data) in extra {
+      clipboard_contents.push(ClipboardContent::Other(format, data));
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.set(clipboard_contents).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to set contents: {e}"),
+      )
+    })
+  }
+
+  /// 按给定顺序写入多种格式，先写入的格式优先提供给粘贴方
+  /// 与 setContents 的固定顺序不同，可以让 HTML 等格式先于纯文本被提供；同一格式出现多次时返回错误
+  #[napi]
+  pub fn set_with_priority(&self, contents: Vec<PrioritizedContent>) -> Result<()> {
+    let mut entries = Vec::with_capacity(contents.len());
+    let mut seen = HashSet::new();
+    for content in contents {
+      let entry = OrderedContent::from_prioritized(content)?;
+      if !seen.insert(entry.format_key().to_string()) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Duplicate format: {}", entry.format_key()),
+        ));
+      }
+      entries.push(entry);
+    }
+
+    // 测试模式的快照只保存标准格式，不保留顺序与自定义格式
+    if test_mode::is_enabled() {
+      let mut snapshot = test_mode::Snapshot::default();
+      for entry in entries {
+        match entry {
+          OrderedContent::Text(text) => snapshot.text = Some(text),
+          OrderedContent::Html(html) => snapshot.html = Some(html),
+          OrderedContent::Rtf(rtf) => snapshot.rtf = Some(rtf),
+          OrderedContent::Image(data) => snapshot.image = Some(data),
+          OrderedContent::Files(files) => snapshot.files = Some(files),
+          OrderedContent::Other(..) => {}
+        }
+      }
+      test_mode::replace(snapshot);
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::set_ordered_contents(entries).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to set contents: {e}"),
+        )
+      });
+    }
+
+    use clipboard_rs::ClipboardContent;
+
+    let mut clipboard_contents = Vec::with_capacity(entries.len());
+    for entry in entries {
+      clipboard_contents.push(match entry {
+        OrderedContent::Text(text) => ClipboardContent::Text(text),
+        OrderedContent::Html(html) => ClipboardContent::Html(html),
+        OrderedContent::Rtf(rtf) => ClipboardContent::Rtf(rtf),
+        OrderedContent::Image(data) => {
+          ClipboardContent::Image(RustImageData::from_bytes(&data).map_err(|e| {
+            Error::new(
+              Status::GenericFailure,
+              format!("Failed to create image from bytes: {e}"),
+            )
+          })?)
+        }
+        OrderedContent::Files(files) => ClipboardContent::Files(files),
+        OrderedContent::Other(format, data) => ClipboardContent::Other(format, data),
+      });
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.set(clipboard_contents).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to set contents: {e}"),
+      )
+    })
+  }
+
+  /// 设置复合内容后读回可用格式，确认 expected_formats 中的格式都已写入，缺少任一格式时返回错误并列出缺少的格式
+  /// 期望格式可以是标准格式名称（text、html 等）或平台原生格式名称
+  #[napi]
+  pub fn set_contents_expecting(
+    &self,
+    contents: ClipboardData,
+    expected_formats: Vec<String>,
+  ) -> Result<()> {
+    // 写入与读回之间持有进程级锁，避免读到其他线程随后写入的内容
+    let _lock = lock::acquire();
+    self.set_contents(contents)?;
+    let available = self.get_available_formats()?;
+    let missing: Vec<&str> = expected_formats
+      .iter()
+      .filter(|expected| {
+        let standard = normalize_format_query(expected).ok();
+        !available.iter().any(|format| {
+          format == *expected
+            || standard.is_some_and(|standard| {
+              canonical_format_name(format).is_some_and(|canonical| canonical == standard)
+            })
+        })
+      })
+      .map(String::as_str)
+      .collect();
+    if missing.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::new(
+        Status::GenericFailure,
+        format!(
+          "Clipboard is missing expected formats after write: {}",
+          missing.join(", ")
+        ),
+      ))
+    }
+  }
+
+  /// 检查剪贴板是否包含指定格式的内容
+  #[napi]
+  pub fn has_format(&self, format: String) -> Result<bool> {
+    let normalized_format = normalize_format_query(&format)?;
+    if let Some(has) = test_mode::read(|snapshot| snapshot.has(normalized_format)) {
+      return Ok(has);
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::has_format(normalized_format).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to check format: {e}"),
+        )
+      });
+    }
+
+    let content_format = match normalized_format {
+      "text" => ContentFormat::Text,
+      "html" => ContentFormat::Html,
+      "rtf" => ContentFormat::Rtf,
+      "image" => ContentFormat::Image,
+      "files" => ContentFormat::Files,
+      _ => unreachable!("normalized format should only be known values"),
+    };
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    Ok(context.has(content_format))
+  }
+
+  /// 获取剪贴板中所有可用的格式
+  #[napi]
+  pub fn get_available_formats(&self) -> Result<Vec<String>> {
+    if let Some(formats) = test_mode::read(test_mode::Snapshot::formats) {
+      return Ok(formats);
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_available_formats().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get available formats: {e}"),
+        )
+      });
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.available_formats().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get available formats: {e}"),
+      )
+    })
+  }
+
+  /// 根据剪贴板中存在的格式汇总可用的粘贴方式，便于统一构建粘贴菜单
+  /// 只在包含文本时读取文本内容以判断是否为链接
+  #[napi]
+  pub fn get_paste_capabilities(&self) -> Result<PasteCapabilities> {
+    let has_text = self.has_format("text".to_string())?;
+    let has_html = self.has_format("html".to_string())?;
+    let has_rtf = self.has_format("rtf".to_string())?;
+    let can_paste_as_link = has_text && self.get_text().is_ok_and(|text| is_web_link(&text));
+
+    Ok(PasteCapabilities {
+      can_paste_text: has_text,
+      can_paste_image: self.has_format("image".to_string())?,
+      can_paste_files: self.has_format("files".to_string())?,
+      can_paste_as_link,
+      can_paste_as_markdown: has_html,
+      can_paste_plain: has_text || has_html || has_rtf,
+    })
+  }
+
+  /// 获取剪贴板来源应用优先提供的格式（按平台原生格式顺序的第一项）
+  /// 标准格式映射为 text、rtf、html、image、files，其他格式返回原始名称，剪贴板为空时返回 null
+  #[napi]
+  pub fn get_primary_format(&self) -> Result<Option<String>> {
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_primary_format().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get primary format: {e}"),
+        )
+      });
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    let native_formats = context.available_formats().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get primary format: {e}"),
+      )
+    })?;
+    Ok(
+      native_formats
+        .iter()
+        .find_map(|format| canonical_format_name(format)),
+    )
+  }
+
+  /// 获取整个剪贴板内容的指纹（SHA-256 十六进制字符串）
+  /// 内容完全相同的剪贴板得到相同的指纹，可用于历史记录去重
+  #[napi]
+  pub fn get_clipboard_fingerprint(&self) -> Result<String> {
+    let data = self.read_full_data()?;
+    Ok(clipboard_fingerprint(&data))
+  }
+
+  /// 分别获取每个标准格式的内容哈希（SHA-256 十六进制字符串）
+  /// 与上次的结果比较即可得知哪些格式发生了变化，只同步变化的格式
+  /// Windows 上所有格式在同一次剪贴板会话中读取，其他平台各格式分别读取
+  #[napi]
+  pub fn get_format_hashes(&self) -> Result<Vec<FormatHash>> {
+    #[cfg(target_os = "windows")]
+    if !test_mode::is_enabled() {
+      let data = windows::read_session(&STANDARD_FORMATS, ImageEncoding::Png).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to read clipboard session: {e}"),
+        )
+      })?;
+      return Ok(format_hashes(data));
+    }
+
+    let data = self.read_full_data()?;
+    Ok(format_hashes(data))
+  }
+
+  /// 列出剪贴板中所有可用格式及其数据大小
+  /// 标准格式通过对应的读取接口统计，其他格式通过自定义格式接口统计，读取失败的格式会被跳过
+  #[napi]
+  pub fn inspect(&self) -> Result<Vec<FormatSize>> {
+    let data = self.read_full_data()?;
+    let mut sizes = standard_format_sizes(&data);
+
+    for format in self.get_available_formats()? {
+      if is_standard_format_name(&format) {
+        continue;
+      }
+      if let Ok(buffer) = self.get_buffer(format.clone()) {
+        sizes.push(FormatSize {
+          format,
+          size: buffer.len() as f64,
+        });
+      }
+    }
+
+    Ok(sizes)
+  }
+
+  /// 列出所有可用格式及其数据大小，读取期间持有进程内剪贴板锁，本进程的写入不会穿插其中
+  /// 各格式仍分别打开剪贴板读取，其他应用在读取期间修改剪贴板时结果可能混合新旧内容
+  /// 标准格式的大小与 inspect 的统计方式相同，其他格式按原生格式名称读取原始字节统计
+  #[napi]
+  pub fn get_format_sizes(&self) -> Result<Vec<FormatSize>> {
+    if let Some(result) = test_snapshot_data() {
+      return Ok(standard_format_sizes(&result?));
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let data = wayland::get_full_clipboard_data().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get clipboard data: {e}"),
+        )
+      })?;
+      let mut sizes = standard_format_sizes(&data);
+      let formats = wayland::get_available_formats().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get available formats: {e}"),
+        )
+      })?;
+      for format in formats {
+        if canonical_format_name(&format).is_some_and(|name| !is_standard_format_name(&name)) {
+          if let Ok(buffer) = wayland::get_buffer(format.clone()) {
+            sizes.push(FormatSize {
+              format,
+              size: buffer.len() as f64,
+            });
+          }
+        }
+      }
+      return Ok(sizes);
+    }
+
+    // 守卫在整个读取过程中持有进程内剪贴板锁
+    let context = get_context_or_error(self.context.as_ref())?;
+    let data = get_clipboard_data(&context);
+    let mut sizes = standard_format_sizes(&data);
+    let native_formats = context.available_formats().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get available formats: {e}"),
+      )
+    })?;
+    for format in native_formats {
+      if canonical_format_name(&format).is_some_and(|name| !is_standard_format_name(&name)) {
+        if let Ok(buffer) = context.get_buffer(&format) {
+          sizes.push(FormatSize {
+            format,
+            size: buffer.len() as f64,
+          });
+        }
+      }
+    }
+    Ok(sizes)
+  }
+
+  /// 按平台原生格式逐一读取剪贴板的原始字节，不做任何转码
+  /// 返回的 platformName 可直接传给 `setRawFormat(platformName, data, true)` 原样写回；无法按字节读取的格式会被跳过
+  #[napi]
+  pub fn get_raw_contents(&self) -> Result<Vec<RawFormat>> {
+    let to_raw_formats = |entries: Vec<(String, Vec<u8>)>| -> Vec<RawFormat> {
+      entries
+        .into_iter()
+        .map(|(platform_name, data)| RawFormat {
+          format: canonical_format_name(&platform_name).unwrap_or_else(|| platform_name.clone()),
+          platform_name,
+          data: Buffer::from(data),
+        })
+        .collect()
+    };
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::get_raw_contents()
+        .map(to_raw_formats)
+        .map_err(|e| {
+          Error::new(
+            Status::GenericFailure,
+            format!("Failed to get raw contents: {e}"),
+          )
+        });
+    }
+
+    #[cfg(target_os = "windows")]
+    return windows::read_raw_formats()
+      .map(to_raw_formats)
+      .map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get raw contents: {e}"),
+        )
+      });
+
+    #[cfg(not(target_os = "windows"))]
+    {
+      let context = get_context_or_error(self.context.as_ref())?;
+      let native_formats = context.available_formats().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get raw contents: {e}"),
+        )
+      })?;
+      let entries = native_formats
+        .into_iter()
+        // TARGETS 等选区协议使用的目标不是剪贴板内容
+        .filter(|format| canonical_format_name(format).is_some())
+        .filter_map(|format| {
+          let data = context.get_buffer(&format).ok()?;
+          Some((format, data))
+        })
+        .collect();
+      Ok(to_raw_formats(entries))
+    }
+  }
+
+  /// 获取剪贴板中无法通过本库读取的平台格式：既不对应标准格式，也无法作为自定义格式读取到非空数据
+  /// 例如 Windows 的图元文件等基于句柄的格式，或读取失败的延迟渲染格式；会读取每个自定义格式的数据，数据较大时耗时较长
+  #[napi]
+  pub fn get_unsupported_formats(&self) -> Result<Vec<String>> {
+    let map_err = |e: String| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get unsupported formats: {e}"),
+      )
+    };
+    let is_custom = |format: &str| {
+      canonical_format_name(format).is_some_and(|canonical| !is_standard_format_name(&canonical))
+    };
+    let unreadable = |formats: Vec<(String, bool)>| -> Vec<String> {
+      formats
+        .into_iter()
+        .filter(|(format, readable)| !readable && is_custom(format))
+        .map(|(format, _)| format)
+        .collect()
+    };
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::format_readability()
+        .map(unreadable)
+        .map_err(map_err);
+    }
+
+    #[cfg(target_os = "windows")]
+    return windows::format_readability()
+      .map(unreadable)
+      .map_err(map_err);
+
+    #[cfg(not(target_os = "windows"))]
+    {
+      let context = get_context_or_error(self.context.as_ref())?;
+      let native_formats = context
+        .available_formats()
+        .map_err(|e| map_err(e.to_string()))?;
+      let formats = native_formats
+        .into_iter()
+        .filter(|format| is_custom(format))
+        .map(|format| {
+          let readable = context
+            .get_buffer(&format)
+            .is_ok_and(|data| !data.is_empty());
+          (format, readable)
+        })
+        .collect();
+      Ok(unreadable(formats))
+    }
+  }
+
+  /// 检查剪贴板中是否存在无法通过本库读取的平台格式，判断方式与 getUnsupportedFormats 相同
+  #[napi]
+  pub fn has_unsupported_content(&self) -> Result<bool> {
+    Ok(!self.get_unsupported_formats()?.is_empty())
+  }
+
+  /// 在一次剪贴板会话中读取指定的标准格式（text、rtf、html、image、files），空列表表示全部
+  /// Windows 上只打开一次剪贴板完成所有读取，缩短其他应用无法访问剪贴板的时间
+  #[napi]
+  pub fn with_read_session(&self, formats: Vec<String>) -> Result<ClipboardData> {
+    let formats = parse_standard_formats(&formats)?;
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      let mut data = self.read_full_data()?;
+      data
+        .available_formats
+        .retain(|format| formats.contains(&format.as_str()));
+      if !formats.contains(&"text") {
+        data.text = None;
+      }
+      if !formats.contains(&"rtf") {
+        data.rtf = None;
+      }
+      if !formats.contains(&"html") {
+        data.html = None;
+      }
+      if !formats.contains(&"image") {
+        data.image = None;
+      }
+      if !formats.contains(&"files") {
+        data.files = None;
+      }
+      return Ok(data);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+      windows::read_session(&formats, ImageEncoding::Png).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to read clipboard session: {e}"),
+        )
+      })
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+      let context = get_context_or_error(self.context.as_ref())?;
+      Ok(get_clipboard_data_for_formats(
+        &context,
+        &formats,
+        ImageEncoding::Png,
+      ))
+    }
+  }
+
+  /// 从剪贴板中移除指定的标准格式（text、rtf、html、image、files），其余标准格式重新写入
+  /// 一次读取、一次写入完成所有移除；自定义格式不会被保留，全部移除时清空剪贴板
+  #[napi]
+  pub fn remove_formats(&self, formats: Vec<String>) -> Result<()> {
+    if let Some(unknown) = formats
+      .iter()
+      .find(|format| !is_standard_format_name(format))
+    {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Unsupported format: {unknown}"),
+      ));
+    }
+    if formats.is_empty() {
+      return Ok(());
+    }
+
+    let mut data = self.read_full_data()?;
+    let removes = |format: &str| formats.iter().any(|removed| removed == format);
+    if removes("text") {
+      data.text = None;
+    }
+    if removes("rtf") {
+      data.rtf = None;
+    }
+    if removes("html") {
+      data.html = None;
+    }
+    if removes("image") {
+      data.image = None;
+    }
+    if removes("files") {
+      data.files = None;
+    }
+
+    if data.text.is_none()
+      && data.rtf.is_none()
+      && data.html.is_none()
+      && data.image.is_none()
+      && data.files.is_none()
+    {
+      return self.clear();
+    }
+    self.set_contents(data)
+  }
+
+  /// 获取 X11 下 CLIPBOARD 选区所有者窗口的标题，可用于记录复制来源
+  /// 没有所有者、无法读取标题或不是 X11 环境时返回 null
+  #[napi]
+  pub fn get_x11_owner_title(&self) -> Result<Option<String>> {
+    #[cfg(target_os = "linux")]
+    if !is_wayland_environment() {
+      return x11::clipboard_owner_title().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get clipboard owner title: {e}"),
+        )
+      });
+    }
+
+    Ok(None)
+  }
+
+  /// 将剪贴板的全部格式导出为带版本号的二进制归档，可保存或传输后通过 importArchive 还原
+  /// 标准格式使用 text、rtf、html、image（PNG）、files 作为名称，其他格式保留原始名称
+  #[napi]
+  pub fn export_archive(&self) -> Result<Buffer> {
+    let mut entries = clipboard_data_to_entries(self.read_full_data()?);
+    for format in self.get_available_formats()? {
+      let is_custom = match canonical_format_name(&format) {
+        Some(canonical) => !is_standard_format_name(&canonical),
+        None => false,
+      };
+      if !is_custom || entries.iter().any(|(name, _)| *name == format) {
+        continue;
+      }
+      if let Ok(buffer) = self.get_buffer(format.clone()) {
+        entries.push((format, buffer.to_vec()));
+      }
+    }
+
+    archive::encode(&entries).map(Buffer::from).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to export archive: {e}"),
+      )
+    })
+  }
+
+  /// 从 exportArchive 生成的归档还原剪贴板内容，归档无效时返回 InvalidArg 错误
+  #[napi]
+  pub fn import_archive(&self, archive: Buffer) -> Result<()> {
+    let entries = archive::decode(&archive)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Failed to import archive: {e}")))?;
+
+    let (contents, extra) = entries_to_clipboard_data(entries, "Failed to import archive")?;
+    if contents.text.is_none()
+      && contents.rtf.is_none()
+      && contents.html.is_none()
+      && contents.image.is_none()
+      && contents.files.is_none()
+      && extra.is_empty()
+    {
+      return self.clear();
+    }
+    self.set_contents_with_extra(contents, extra)
+  }
+
+  /// 将一条剪贴板数据保存为快照文件，使用与 exportArchive 相同的归档格式
+  /// 只保存标准格式，图片数据按原始字节写入
+  #[napi]
+  pub fn save_snapshot_to_file(data: ClipboardData, path: String) -> Result<()> {
+    let snapshot = archive::encode(&clipboard_data_to_entries(data)).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to save snapshot: {e}"),
+      )
+    })?;
+    std::fs::write(&path, snapshot).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to save snapshot to {path}: {e}"),
+      )
+    })
+  }
+
+  /// 从 saveSnapshotToFile 保存的快照文件读取剪贴板数据，不修改剪贴板
+  /// 文件读取失败返回 GenericFailure 错误，文件不是有效的快照时返回 InvalidArg 错误
+  #[napi]
+  pub fn load_snapshot_from_file(path: String) -> Result<ClipboardData> {
+    let snapshot = std::fs::read(&path).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to load snapshot from {path}: {e}"),
+      )
+    })?;
+    let entries = archive::decode(&snapshot)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Failed to load snapshot: {e}")))?;
+    let (mut data, _) = entries_to_clipboard_data(entries, "Failed to load snapshot")?;
+    data.available_formats = STANDARD_FORMATS
+      .iter()
+      .filter(|format| match **format {
+        "text" => data.text.is_some(),
+        "rtf" => data.rtf.is_some(),
+        "html" => data.html.is_some(),
+        "image" => data.image.is_some(),
+        _ => data.files.is_some(),
+      })
+      .map(|format| format.to_string())
+      .collect();
+    Ok(data)
+  }
+
+  /// 启用或关闭测试模式：传入快照后，基础读写接口（getText、getImageData、hasFormat、setContents、clear 等）、
+  /// 对应的异步接口、快速操作函数以及基于它们的接口改为读写内存中的快照，不访问系统剪贴板；传入 null 恢复正常行为
+  /// 写入只保留标准格式；直接访问原生格式的接口（如 getBuffer、setBuffer、getRawContents）和监听器不受影响，仍访问系统剪贴板
+  #[napi]
+  pub fn set_test_mode(snapshot: Option<ClipboardData>) {
+    test_mode::set(snapshot.map(test_mode::Snapshot::from_data));
+  }
+
+  /// 开始一个批量读写事务：在一次剪贴板会话中读取全部标准格式作为快照，写入在 commit 时一次完成
+  #[napi]
+  pub fn begin(&self) -> Result<ClipboardTransaction> {
+    Ok(ClipboardTransaction {
+      snapshot: self.with_read_session(Vec::new())?,
+      manager: ClipboardManager::new()?,
+      staged: ClipboardData::default(),
+      staged_extra: Vec::new(),
+      finished: false,
+    })
+  }
+
+  /// 清空剪贴板
+  #[napi]
+  pub fn clear(&self) -> Result<()> {
+    if test_mode::is_enabled() {
+      test_mode::replace(test_mode::Snapshot::default());
+      return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return wayland::clear().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to clear clipboard: {e}"),
+        )
+      });
+    }
+
+    let context = get_context_or_error(self.context.as_ref())?;
+    context.clear().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to clear clipboard: {e}"),
+      )
+    })
+  }
+
+  /// 异步获取剪贴板文本内容
+  #[napi]
+  pub async fn get_text_async(&self) -> Result<String> {
+    // 测试模式下读写内存快照，无需在阻塞线程中访问剪贴板
+    if test_mode::is_enabled() {
+      return self.get_text();
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return tokio::task::spawn_blocking(move || {
+        wayland::get_text()
+          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
+      })
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let context = ClipboardContext::new().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create clipboard context: {e}"),
+      )
+    })?;
+
+    tokio::task::spawn_blocking(move || {
+      let _lock = lock::acquire();
+      context
+        .get_text()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步设置剪贴板文本内容
+  #[napi]
+  pub async fn set_text_async(&self, text: String) -> Result<()> {
+    if test_mode::is_enabled() {
+      return self.set_text(text);
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return tokio::task::spawn_blocking(move || {
+        wayland::set_text(text)
+          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
+      })
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let context = ClipboardContext::new().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create clipboard context: {e}"),
+      )
+    })?;
+
+    tokio::task::spawn_blocking(move || {
+      let _lock = lock::acquire();
+      context
+        .set_text(text)
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步设置剪贴板文本内容，失败时重试
+  /// 适用于剪贴板可能被其他进程短暂占用的场景（如 Windows），重试间隔在阻塞线程中等待
+  #[napi]
+  pub async fn set_text_with_retry(
+    &self,
+    text: String,
+    attempts: u32,
+    delay_ms: f64,
+  ) -> Result<()> {
+    if !delay_ms.is_finite() || delay_ms < 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid retry delay: {delay_ms}"),
+      ));
+    }
+    let delay = Duration::from_secs_f64(delay_ms / 1000.0);
+    let attempts = attempts.max(1);
+    if test_mode::is_enabled() {
+      return self.set_text(text);
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return tokio::task::spawn_blocking(move || {
+        retry_clipboard_write(attempts, delay, || wayland::set_text(text.clone()))
+      })
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let context = ClipboardContext::new().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create clipboard context: {e}"),
+      )
+    })?;
+
+    tokio::task::spawn_blocking(move || {
+      retry_clipboard_write(attempts, delay, || {
+        let _lock = lock::acquire();
+        context.set_text(text.clone()).map_err(|e| e.to_string())
+      })
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步等待剪贴板中出现指定格式（text、html、rtf、image、files），出现后返回完整的剪贴板数据
+  /// timeout_ms 为空时一直等待，超时后返回错误；检查格式失败（例如无法访问剪贴板）时立即返回错误
+  #[napi]
+  pub async fn wait_for_format(
+    &self,
+    format: String,
+    timeout_ms: Option<f64>,
+  ) -> Result<ClipboardData> {
+    let normalized_format = normalize_format_query(&format)?;
+    let timeout = match timeout_ms {
+      Some(ms) if !ms.is_finite() || ms < 0.0 => {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Invalid timeout: {ms}"),
+        ));
+      }
+      Some(ms) => Some(Duration::from_secs_f64(ms / 1000.0)),
+      None => None,
+    };
+
+    tokio::task::spawn_blocking(move || {
+      let manager = ClipboardManager::new()?;
+      let started = std::time::Instant::now();
+      loop {
+        if manager.has_format(normalized_format.to_string())? {
+          return manager.read_full_data();
+        }
+        if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+          return Err(Error::new(
+            Status::GenericFailure,
+            format!("Timed out waiting for format: {format}"),
+          ));
+        }
+        thread::sleep(WAIT_FOR_FORMAT_POLL_INTERVAL);
+      }
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步获取完整的剪贴板数据，各格式在阻塞线程池中使用独立的上下文读取
+  /// 原始数据的读取受进程级锁保护，实际仍逐个进行；并行的只有图片的 PNG 编码，可与其他格式的读取同时进行
+  /// 各格式在不同时刻读取，读取期间剪贴板被修改时结果可能混合新旧内容；Windows 下在一个线程中按顺序读取
+  #[napi]
+  pub async fn get_full_clipboard_data_parallel_async(&self) -> Result<ClipboardData> {
+    if let Some(result) = test_snapshot_data() {
+      return result;
+    }
+
+    if cfg!(target_os = "windows") {
+      return tokio::task::spawn_blocking(get_full_clipboard_data)
+        .await
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let available_formats = tokio::task::spawn_blocking(read_available_formats)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))??;
+    let tasks: Vec<_> = STANDARD_FORMATS
+      .into_iter()
+      .filter(|format| available_formats.iter().any(|name| name == format))
+      .map(|format| tokio::task::spawn_blocking(move || read_single_format(format)))
+      .collect();
+
+    let mut data = ClipboardData {
+      available_formats,
+      ..Default::default()
+    };
+    for task in tasks {
+      let content = task
+        .await
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+      if let Some(content) = content {
+        content.apply_to(&mut data);
+      }
+    }
+    Ok(data)
+  }
+
+  /// 异步获取完整的剪贴板数据，所有格式的读取总时长不超过 budget_ms 毫秒
+  /// 按文本、HTML、RTF、文件、图片的优先顺序逐个读取，超出预算时停止并返回已读取的内容，partial 为 true，
+  /// 未读取的格式为空；超时的格式仍会在后台线程中读取完毕，结果被丢弃
+  #[napi]
+  pub async fn get_full_clipboard_data_budgeted_async(
+    &self,
+    budget_ms: f64,
+  ) -> Result<ClipboardData> {
+    if !budget_ms.is_finite() || budget_ms < 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Invalid budget: {budget_ms}"),
+      ));
+    }
+    let budget = Duration::from_secs_f64(budget_ms / 1000.0);
+
+    tokio::task::spawn_blocking(move || read_full_data_budgeted(budget))
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步获取剪贴板图片数据（以 base64 编码返回）
+  #[napi]
+  pub async fn get_image_base64_async(&self) -> Result<String> {
+    if test_mode::is_enabled() {
+      return self.get_image_base64();
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return tokio::task::spawn_blocking(move || {
+        let image_data = wayland::get_image_raw()
+          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+        let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
+        Ok(BASE64_STANDARD.encode(png_bytes))
+      })
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let context = ClipboardContext::new().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create clipboard context: {e}"),
+      )
+    })?;
+
+    tokio::task::spawn_blocking(move || {
+      let _lock = lock::acquire();
+      let image_data = context
+        .get_image()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+      let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+      Ok(BASE64_STANDARD.encode(png_bytes))
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步设置剪贴板图片（从 base64 编码的图片数据），解码与写入在阻塞线程中进行
+  #[napi]
+  pub async fn set_image_base64_async(&self, base64_data: String) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+      let image_data = BASE64_STANDARD
+        .decode(base64_data)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid base64 data: {e}")))?;
+      write_image_bytes(&image_data)
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步设置剪贴板图片（从原始图片字节），解码与写入在阻塞线程中进行
+  #[napi]
+  pub async fn set_image_raw_async(&self, image_data: Buffer) -> Result<()> {
+    let image_data = image_data.to_vec();
+    tokio::task::spawn_blocking(move || write_image_bytes(&image_data))
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+
+  /// 异步获取剪贴板图片详细信息（包含宽度、高度、大小和原始数据）
+  #[napi]
+  pub async fn get_image_data_async(&self) -> Result<ImageData> {
+    if test_mode::is_enabled() {
+      return self.get_image_data();
+    }
+
+    #[cfg(target_os = "linux")]
+    if is_wayland_environment() {
+      return tokio::task::spawn_blocking(move || {
+        let image_data = wayland::get_image_raw()
+          .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+        image_bytes_to_image_data(&image_data, "Failed to get image")
+      })
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?;
+    }
+
+    let context = ClipboardContext::new().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create clipboard context: {e}"),
+      )
+    })?;
+
+    tokio::task::spawn_blocking(move || {
+      let _lock = lock::acquire();
+      let image_data = context
+        .get_image()
+        .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+      let (width, height) = image_data.get_size();
+      let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+      Ok(ImageData {
+        width,
+        height,
+        size: png_bytes.len() as u32,
+        data: Buffer::from(png_bytes),
+      })
+    })
+    .await
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Task join error: {e}")))?
+  }
+}
+
+// 便利的静态函数，用于快速操作剪贴板
+// 测试模式下各函数通过管理器实例读写内存快照
+
+/// 快速获取剪贴板文本内容
+#[napi]
+pub fn get_clipboard_text() -> Result<String> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_text();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::get_text()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .get_text()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get text: {e}")))
+}
+
+/// 快速设置剪贴板文本内容
+#[napi]
+pub fn set_clipboard_text(text: String) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_text(text);
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::set_text(text)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_text(text)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
+}
+
+/// 快速获取剪贴板 HTML 内容
+#[napi]
+pub fn get_clipboard_html() -> Result<String> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_html();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::get_html()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .get_html()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")))
+}
+
+/// 快速设置剪贴板 HTML 内容
+#[napi]
+pub fn set_clipboard_html(html: String) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_html(html);
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::set_html(html)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_html(html)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")))
+}
+
+/// 快速获取剪贴板图片（base64 编码）
+#[napi]
+pub fn get_clipboard_image() -> Result<String> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_image_base64();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let image_data = wayland::get_image_raw()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+    let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
+    return Ok(BASE64_STANDARD.encode(png_bytes));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  let image_data = context
+    .get_image()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+  Ok(BASE64_STANDARD.encode(png_bytes))
+}
+
+/// 快速获取剪贴板图片详细信息（包含宽度、高度、大小和原始数据）
+#[napi]
+pub fn get_clipboard_image_data() -> Result<ImageData> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_image_data();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let image_data = wayland::get_image_raw()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+    return image_bytes_to_image_data(&image_data, "Failed to get image");
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  let image_data = context
+    .get_image()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+  let (width, height) = image_data.get_size();
+  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+  Ok(ImageData {
+    width,
+    height,
+    size: png_bytes.len() as u32,
+    data: Buffer::from(png_bytes),
+  })
+}
+
+/// 快速设置剪贴板图片（从 base64 编码）
+#[napi]
+pub fn set_clipboard_image(base64_data: String) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_image_base64(base64_data);
+  }
+
+  let image_data = BASE64_STANDARD
+    .decode(base64_data)
+    .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid base64 data: {e}")))?;
+
+  let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create image from bytes: {e}"),
+    )
+  })?;
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
+    return wayland::set_image_raw(png_bytes)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_image(rust_image)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+}
+
+/// 快速设置剪贴板图片（从原始字节数据）
+#[napi]
+pub fn set_clipboard_image_raw(image_data: Buffer) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_image_raw(image_data);
+  }
+
+  let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create image from bytes: {e}"),
+    )
+  })?;
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
+    return wayland::set_image_raw(png_bytes)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_image(rust_image)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+}
+
+/// 快速获取剪贴板图片原始数据（Buffer）
+#[napi]
+pub fn get_clipboard_image_raw() -> Result<Buffer> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_image_raw();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let image_data = wayland::get_image_raw()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+    let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
+    return Ok(Buffer::from(png_bytes));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  let image_data = context
+    .get_image()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+
+  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
+  Ok(Buffer::from(png_bytes))
+}
+
+/// 快速设置剪贴板自定义格式数据
+#[napi]
+pub fn set_clipboard_buffer(format: String, buffer: Buffer) -> Result<()> {
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::set_buffer(format, buffer.to_vec())
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_buffer(&format, buffer.to_vec())
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")))
+}
+
+/// 快速获取剪贴板自定义格式数据
+#[napi]
+pub fn get_clipboard_buffer(format: String) -> Result<Buffer> {
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    let data = wayland::get_buffer(format)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
+    return Ok(Buffer::from(data));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  let data = context
+    .get_buffer(&format)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
+  Ok(Buffer::from(data))
+}
+
+/// 快速设置剪贴板文件列表
+#[napi]
+pub fn set_clipboard_files(files: Vec<String>) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_files(files);
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::set_files(files)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .set_files(files)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")))
+}
+
+/// 快速获取剪贴板文件列表
+#[napi]
+pub fn get_clipboard_files() -> Result<Vec<String>> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.get_files();
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::get_files()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")));
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  context
+    .get_files()
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")))
+}
+
+/// 快速设置剪贴板复合内容（可同时设置多种格式）
+#[napi]
+pub fn set_clipboard_contents(contents: ClipboardData) -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.set_contents(contents);
+  }
+
+  #[cfg(target_os = "linux")]
+  if is_wayland_environment() {
+    return wayland::set_contents(contents).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to set contents: {e}"),
+      )
+    });
+  }
+
+  let context = ClipboardContext::new().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to create clipboard context: {e}"),
+    )
+  })?;
+  let _lock = lock::acquire();
+
+  use clipboard_rs::ClipboardContent;
+
+  let mut clipboard_contents = Vec::new();
+
+  if let Some(text) = contents.text {
+    clipboard_contents.push(ClipboardContent::Text(text));
+  }
+
+  if let Some(html) = contents.html {
+    clipboard_contents.push(ClipboardContent::Html(html));
+  }
+
+  if let Some(rtf) = contents.rtf {
+    clipboard_contents.push(ClipboardContent::Rtf(rtf));
+  }
+
+  if let Some(image_data) = contents.image {
+    let rust_image = RustImageData::from_bytes(image_data.data.as_ref()).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to create image from bytes: {e}"),
+      )
+    })?;
+
+    clipboard_contents.push(ClipboardContent::Image(rust_image));
+  }
+
+  if let Some(files) = contents.files {
+    clipboard_contents.push(ClipboardContent::Files(files));
+  }
+
+  context.set(clipboard_contents).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to set contents: {e}"),
+    )
+  })
+}
+
+/// 快速获取完整的剪贴板数据
+#[napi]
+pub fn get_full_clipboard_data() -> Result<ClipboardData> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.read_full_data();
+  }
+
+  if let Some(result) = test_snapshot_data() {
+    return result;
+  }
 
-/// 快速设置剪贴板文本内容
-#[napi]
-pub fn set_clipboard_text(text: String) -> Result<()> {
   #[cfg(target_os = "linux")]
   if is_wayland_environment() {
-    return wayland::set_text(text)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")));
+    return wayland::get_full_clipboard_data().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get clipboard data: {e}"),
+      )
+    });
   }
 
   let context = ClipboardContext::new().map_err(|e| {
@@ -758,19 +5024,31 @@ pub fn set_clipboard_text(text: String) -> Result<()> {
       format!("Failed to create clipboard context: {e}"),
     )
   })?;
+  let _lock = lock::acquire();
 
-  context
-    .set_text(text)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set text: {e}")))
+  Ok(get_clipboard_data(&context))
 }
 
-/// 快速获取剪贴板 HTML 内容
+/// 快速清空剪贴板
 #[napi]
-pub fn get_clipboard_html() -> Result<String> {
+pub fn clear_clipboard() -> Result<()> {
+  if test_mode::is_enabled() {
+    return ClipboardManager::new()?.clear();
+  }
+
+  if test_mode::is_enabled() {
+    test_mode::replace(test_mode::Snapshot::default());
+    return Ok(());
+  }
+
   #[cfg(target_os = "linux")]
   if is_wayland_environment() {
-    return wayland::get_html()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")));
+    return wayland::clear().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to clear clipboard: {e}"),
+      )
+    });
   }
 
   let context = ClipboardContext::new().map_err(|e| {
@@ -779,19 +5057,138 @@ pub fn get_clipboard_html() -> Result<String> {
       format!("Failed to create clipboard context: {e}"),
     )
   })?;
+  let _lock = lock::acquire();
 
-  context
-    .get_html()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get HTML: {e}")))
+  context.clear().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to clear clipboard: {e}"),
+    )
+  })
 }
 
-/// 快速设置剪贴板 HTML 内容
-#[napi]
-pub fn set_clipboard_html(html: String) -> Result<()> {
+/// 获取完整的剪贴板数据
+fn get_clipboard_data(context: &ClipboardContext) -> ClipboardData {
+  get_clipboard_data_with_encoding(context, ImageEncoding::Png)
+}
+
+/// 获取完整的剪贴板数据，图片按指定编码方式输出
+fn get_clipboard_data_with_encoding(
+  context: &ClipboardContext,
+  encoding: ImageEncoding,
+) -> ClipboardData {
+  get_clipboard_data_for_formats(context, &STANDARD_FORMATS, encoding)
+}
+
+/// 获取指定标准格式的剪贴板数据，图片按指定编码方式输出
+fn get_clipboard_data_for_formats(
+  context: &ClipboardContext,
+  formats: &[&str],
+  encoding: ImageEncoding,
+) -> ClipboardData {
+  // 多个格式在同一次加锁中读取，避免读取过程中被其他线程的写入打断
+  let _lock = lock::acquire();
+
+  // 定义要检查的格式类型，对应 ClipboardContent 枚举
+  // Text, Rtf, Html, Image, Files
+  let format_checks = [
+    ("text", ContentFormat::Text),
+    ("rtf", ContentFormat::Rtf),
+    ("html", ContentFormat::Html),
+    ("image", ContentFormat::Image),
+    ("files", ContentFormat::Files),
+  ];
+
+  // 初始化数据变量
+  let mut available_formats = Vec::new();
+  let mut text = None;
+  let mut rtf = None;
+  let mut html = None;
+  let mut image = None;
+  let mut files = None;
+
+  // 使用 has 接口检查每种标准格式的可用性并使用对应的 get 接口获取数据
+  for (format_name, content_format) in format_checks
+    .iter()
+    .filter(|(format_name, _)| formats.contains(format_name))
+  {
+    if context.has(content_format.clone()) {
+      available_formats.push(format_name.to_string());
+
+      match *format_name {
+        "text" => {
+          text = context.get_text().ok();
+        }
+        "rtf" => {
+          rtf = context.get_rich_text().ok();
+        }
+        "html" => {
+          html = context.get_html().ok();
+        }
+        "image" => {
+          image = context.get_image().ok().and_then(|img_data| {
+            let (width, height) = img_data.get_size();
+            encode_rust_image(&img_data, encoding, "Failed to get image")
+              .ok()
+              .map(|bytes| ImageData {
+                width,
+                height,
+                size: bytes.len() as u32,
+                data: Buffer::from(bytes),
+              })
+          });
+        }
+        "files" => {
+          files = context.get_files().ok();
+        }
+        _ => {}
+      }
+    }
+  }
+
+  ClipboardData {
+    available_formats,
+    text,
+    rtf,
+    html,
+    image,
+    files,
+    ..Default::default()
+  }
+}
+
+/// 并行读取时单个格式的读取结果
+enum FormatContent {
+  Text(String),
+  Rtf(String),
+  Html(String),
+  Image(ImageData),
+  Files(Vec<String>),
+}
+
+impl FormatContent {
+  /// 将读取到的格式内容填入剪贴板数据
+  fn apply_to(self, data: &mut ClipboardData) {
+    match self {
+      FormatContent::Text(text) => data.text = Some(text),
+      FormatContent::Rtf(rtf) => data.rtf = Some(rtf),
+      FormatContent::Html(html) => data.html = Some(html),
+      FormatContent::Image(image) => data.image = Some(image),
+      FormatContent::Files(files) => data.files = Some(files),
+    }
+  }
+}
+
+/// 获取剪贴板中可用的格式名称，包含 Wayland 下的自定义 MIME 类型
+fn read_available_formats() -> Result<Vec<String>> {
   #[cfg(target_os = "linux")]
   if is_wayland_environment() {
-    return wayland::set_html(html)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")));
+    return wayland::get_available_formats().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get available formats: {e}"),
+      )
+    });
   }
 
   let context = ClipboardContext::new().map_err(|e| {
@@ -800,46 +5197,153 @@ pub fn set_clipboard_html(html: String) -> Result<()> {
       format!("Failed to create clipboard context: {e}"),
     )
   })?;
-
-  context
-    .set_html(html)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set HTML: {e}")))
+  let _lock = lock::acquire();
+  let format_checks = [
+    ("text", ContentFormat::Text),
+    ("rtf", ContentFormat::Rtf),
+    ("html", ContentFormat::Html),
+    ("image", ContentFormat::Image),
+    ("files", ContentFormat::Files),
+  ];
+  Ok(
+    format_checks
+      .into_iter()
+      .filter(|(_, content_format)| context.has(content_format.clone()))
+      .map(|(format_name, _)| format_name.to_string())
+      .collect(),
+  )
 }
 
-/// 快速获取剪贴板图片（base64 编码）
-#[napi]
-pub fn get_clipboard_image() -> Result<String> {
+/// 使用独立的剪贴板上下文读取单个标准格式，读取失败时返回 None
+/// 只在读取原始数据时持有进程级锁，图片的 PNG 编码在锁外进行，可与其他格式的读取并行
+fn read_single_format(format: &str) -> Option<FormatContent> {
   #[cfg(target_os = "linux")]
   if is_wayland_environment() {
-    let image_data = wayland::get_image_raw()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-    let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
-    return Ok(BASE64_STANDARD.encode(png_bytes));
+    return match format {
+      "text" => wayland::get_text().ok().map(FormatContent::Text),
+      "rtf" => wayland::get_rich_text().ok().map(FormatContent::Rtf),
+      "html" => wayland::get_html().ok().map(FormatContent::Html),
+      "image" => wayland::get_image_raw()
+        .ok()
+        .and_then(|bytes| image_bytes_to_image_data(&bytes, "Failed to get image").ok())
+        .map(FormatContent::Image),
+      "files" => wayland::get_files().ok().map(FormatContent::Files),
+      _ => None,
+    };
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
+  let context = ClipboardContext::new().ok()?;
+  if format == "image" {
+    let image_data = {
+      let _lock = lock::acquire();
+      context.get_image().ok()?
+    };
+    let (width, height) = image_data.get_size();
+    let bytes = image_to_png_bytes(&image_data, "Failed to get image").ok()?;
+    return Some(FormatContent::Image(ImageData {
+      width,
+      height,
+      size: bytes.len() as u32,
+      data: Buffer::from(bytes),
+    }));
+  }
+
+  let _lock = lock::acquire();
+  match format {
+    "text" => context.get_text().ok().map(FormatContent::Text),
+    "rtf" => context.get_rich_text().ok().map(FormatContent::Rtf),
+    "html" => context.get_html().ok().map(FormatContent::Html),
+    "files" => context.get_files().ok().map(FormatContent::Files),
+    _ => None,
+  }
+}
+
+/// 限时读取时各格式的优先顺序，开销较大的图片放在最后
+const BUDGETED_FORMAT_PRIORITY: [&str; 5] = ["text", "html", "rtf", "files", "image"];
+
+/// 在时间预算内按优先顺序逐个读取标准格式，超出预算时停止并将 partial 标记为 true
+/// 可用格式列表与每个格式都在独立线程中读取，等待超时后不再读取后续格式
+fn read_full_data_budgeted(budget: Duration) -> Result<ClipboardData> {
+  if let Some(result) = test_snapshot_data() {
+    return result.map(|data| ClipboardData {
+      partial: Some(false),
+      ..data
+    });
+  }
+
+  let deadline = std::time::Instant::now() + budget;
+  let (tx, rx) = std::sync::mpsc::channel();
+  thread::spawn(move || {
+    let _ = tx.send(read_available_formats());
+  });
+  let available_formats = match rx.recv_timeout(budget) {
+    Ok(result) => result?,
+    // 连可用格式都未能在预算内读取时返回空的部分结果
+    Err(_) => {
+      return Ok(ClipboardData {
+        partial: Some(true),
+        ..Default::default()
+      })
+    }
+  };
+  let formats: Vec<&'static str> = BUDGETED_FORMAT_PRIORITY
+    .into_iter()
+    .filter(|format| available_formats.iter().any(|name| name == format))
+    .collect();
+
+  let mut data = ClipboardData {
+    available_formats,
+    partial: Some(false),
+    ..Default::default()
+  };
+  for format in formats {
+    let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+    if remaining.is_zero() {
+      data.partial = Some(true);
+      break;
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+      let _ = tx.send(read_single_format(format));
+    });
+    match rx.recv_timeout(remaining) {
+      Ok(content) => {
+        if let Some(content) = content {
+          content.apply_to(&mut data);
+        }
+      }
+      Err(_) => {
+        data.partial = Some(true);
+        break;
+      }
+    }
+  }
+  Ok(data)
+}
+
+/// 解码图片字节并写入剪贴板，在阻塞线程中调用，图片解码期间不持有剪贴板锁
+fn write_image_bytes(image_data: &[u8]) -> Result<()> {
+  let rust_image = RustImageData::from_bytes(image_data).map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
+      format!("Failed to create image from bytes: {e}"),
     )
   })?;
 
-  let image_data = context
-    .get_image()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-
-  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-  Ok(BASE64_STANDARD.encode(png_bytes))
-}
+  if test_mode::is_enabled() {
+    test_mode::replace(test_mode::Snapshot {
+      image: Some(image_data.to_vec()),
+      ..Default::default()
+    });
+    return Ok(());
+  }
 
-/// 快速获取剪贴板图片详细信息（包含宽度、高度、大小和原始数据）
-#[napi]
-pub fn get_clipboard_image_data() -> Result<ImageData> {
   #[cfg(target_os = "linux")]
   if is_wayland_environment() {
-    let image_data = wayland::get_image_raw()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-    return image_bytes_to_image_data(&image_data, "Failed to get image");
+    let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
+    return wayland::set_image_raw(png_bytes)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
   }
 
   let context = ClipboardContext::new().map_err(|e| {
@@ -848,369 +5352,988 @@ pub fn get_clipboard_image_data() -> Result<ImageData> {
       format!("Failed to create clipboard context: {e}"),
     )
   })?;
+  let _lock = lock::acquire();
+  context
+    .set_image(rust_image)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+}
 
-  let image_data = context
-    .get_image()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+/// 复制图片数据（Buffer 内容会被完整拷贝）
+fn copy_image_data(image: &ImageData) -> ImageData {
+  ImageData {
+    width: image.width,
+    height: image.height,
+    size: image.size,
+    data: Buffer::from(image.data.to_vec()),
+  }
+}
 
-  let (width, height) = image_data.get_size();
-  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-  Ok(ImageData {
-    width,
-    height,
-    size: png_bytes.len() as u32,
-    data: Buffer::from(png_bytes),
-  })
+/// 复制剪贴板数据中的内容字段，不包含监听器附加的信息
+fn copy_clipboard_contents(data: &ClipboardData) -> ClipboardData {
+  ClipboardData {
+    available_formats: data.available_formats.clone(),
+    text: data.text.clone(),
+    rtf: data.rtf.clone(),
+    html: data.html.clone(),
+    image: data.image.as_ref().map(copy_image_data),
+    files: data.files.clone(),
+    ..Default::default()
+  }
 }
 
-/// 快速设置剪贴板图片（从 base64 编码）
-#[napi]
-pub fn set_clipboard_image(base64_data: String) -> Result<()> {
-  let image_data = BASE64_STANDARD
-    .decode(base64_data)
-    .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid base64 data: {e}")))?;
+/// 将监听事件中的图片写入目录，文件名为毫秒时间戳，命名规则见 imaging::save_image_file
+fn save_event_image(dir: &std::path::Path, image: &[u8]) -> std::io::Result<std::path::PathBuf> {
+  let timestamp = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_millis())
+    .unwrap_or(0);
+  imaging::save_image_file(dir, image, timestamp)
+}
 
-  let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create image from bytes: {e}"),
-    )
-  })?;
+/// 尽力判断刚发生的剪贴板变化来自用户操作还是程序写入，平台没有可用信号时返回 unknown
+fn detect_change_origin() -> &'static str {
+  #[cfg(target_os = "windows")]
+  return windows::change_origin();
 
   #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
-    return wayland::set_image_raw(png_bytes)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+  if !is_wayland_environment() {
+    return x11::clipboard_change_origin();
+  }
+
+  #[cfg(not(target_os = "windows"))]
+  "unknown"
+}
+
+/// 截图判断使用的图片尺寸范围（像素）
+const SCREENSHOT_MIN_DIMENSION: u32 = 16;
+const SCREENSHOT_MAX_DIMENSION: u32 = 16384;
+
+/// 判断剪贴板变化是否可能来自截图：只包含尺寸合理的图片，不包含文本、HTML、RTF 或文件
+fn is_likely_screenshot(data: &ClipboardData) -> bool {
+  let Some(image) = &data.image else {
+    return false;
+  };
+  let has_other_content =
+    data.text.is_some() || data.html.is_some() || data.rtf.is_some() || data.files.is_some();
+  let reasonable_size =
+    |dimension: u32| (SCREENSHOT_MIN_DIMENSION..=SCREENSHOT_MAX_DIMENSION).contains(&dimension);
+
+  !has_other_content && reasonable_size(image.width) && reasonable_size(image.height)
+}
+
+/// 监听器保留的最近事件，最旧的在前
+type ListenerHistory = Arc<Mutex<VecDeque<ClipboardData>>>;
+
+/// 监听器是否已启用回调，未启用时监听线程照常运行但丢弃所有事件
+type ListenerArmed = Arc<AtomicBool>;
+
+/// 监听器的 JS 回调
+type ListenerCallback = ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>;
+
+/// 监听器的 JS 过滤函数，返回 null 时丢弃事件，返回对象时用其替换事件数据
+type ListenerFilter =
+  ThreadsafeFunction<ClipboardData, Option<ClipboardData>, ClipboardData, napi::Status, false>;
+
+/// 等待过滤函数返回结果的最长时间，超时的事件被丢弃，避免 JS 线程繁忙时阻塞监听线程
+const LISTENER_FILTER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 在 JS 线程上调用过滤函数并等待结果
+/// 过滤函数返回 null、抛出异常或超时未返回时丢弃事件
+fn apply_listener_filter(filter: &ListenerFilter, data: ClipboardData) -> Option<ClipboardData> {
+  let (result_tx, result_rx) = std::sync::mpsc::channel();
+  let status = filter.call_with_return_value(
+    data,
+    ThreadsafeFunctionCallMode::NonBlocking,
+    move |result, _| {
+      let _ = result_tx.send(result.ok().flatten());
+      Ok(())
+    },
+  );
+  if status != napi::Status::Ok {
+    return None;
+  }
+  result_rx
+    .recv_timeout(LISTENER_FILTER_TIMEOUT)
+    .ok()
+    .flatten()
+}
+
+/// 回调繁忙时的处理方式
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Backpressure {
+  /// 以非阻塞方式调用回调，回调队列已满时丢弃事件（默认）
+  Drop,
+  /// 回调执行期间只保留最新的一个待处理事件
+  Latest,
+  /// 以阻塞方式调用回调，等待上一次回调完成后再处理下一个事件，不丢弃事件
+  Queue,
+}
+
+impl Backpressure {
+  fn parse(mode: &str) -> Result<Self> {
+    match mode.trim().to_ascii_lowercase().as_str() {
+      "drop" => Ok(Backpressure::Drop),
+      "latest" => Ok(Backpressure::Latest),
+      "queue" => Ok(Backpressure::Queue),
+      _ => Err(Error::new(
+        Status::InvalidArg,
+        format!("Unsupported backpressure mode: {mode}"),
+      )),
+    }
+  }
+
+  fn name(self) -> &'static str {
+    match self {
+      Backpressure::Drop => "drop",
+      Backpressure::Latest => "latest",
+      Backpressure::Queue => "queue",
+    }
+  }
+}
+
+/// 以阻塞方式调用回调，并等待 JS 回调执行完成
+fn call_and_wait(callback: &ListenerCallback, data: ClipboardData) -> napi::Status {
+  let (done_tx, done_rx) = std::sync::mpsc::channel();
+  let status =
+    callback.call_with_return_value(data, ThreadsafeFunctionCallMode::Blocking, move |_, _| {
+      let _ = done_tx.send(());
+      Ok(())
+    });
+  if status == napi::Status::Ok {
+    // 回调未执行就被释放时发送端会被丢弃，recv 同样会返回
+    let _ = done_rx.recv();
+  }
+  status
+}
+
+/// latest 模式下等待发送的最新事件
+#[derive(Default)]
+struct LatestSlot {
+  state: Mutex<LatestState>,
+  ready: Condvar,
+}
+
+#[derive(Default)]
+struct LatestState {
+  pending: Option<ClipboardData>,
+  closed: bool,
+}
+
+impl LatestSlot {
+  fn lock(&self) -> std::sync::MutexGuard<'_, LatestState> {
+    self
+      .state
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+/// latest 模式的发送线程：逐个调用回调，回调执行期间到达的事件只保留最新的一个
+/// 等待期间监听器被停用时丢弃保留的事件
+fn run_latest_delivery(callback: ListenerCallback, slot: Arc<LatestSlot>, armed: ListenerArmed) {
+  loop {
+    let data = {
+      let mut state = slot.lock();
+      while state.pending.is_none() && !state.closed {
+        state = slot
+          .ready
+          .wait(state)
+          .unwrap_or_else(|poisoned| poisoned.into_inner());
+      }
+      match state.pending.take() {
+        Some(data) => data,
+        None => return,
+      }
+    };
+    if armed.load(Ordering::SeqCst) {
+      call_and_wait(&callback, data);
+    }
   }
+}
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+/// latest 模式的事件入口，释放时通知发送线程退出
+struct LatestSender(Arc<LatestSlot>);
 
-  context
-    .set_image(rust_image)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+impl Drop for LatestSender {
+  fn drop(&mut self) {
+    self.0.lock().closed = true;
+    self.0.ready.notify_all();
+  }
 }
 
-/// 快速设置剪贴板图片（从原始字节数据）
-#[napi]
-pub fn set_clipboard_image_raw(image_data: Buffer) -> Result<()> {
-  let rust_image = RustImageData::from_bytes(&image_data).map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create image from bytes: {e}"),
-    )
-  })?;
+/// 按背压策略调用 JS 回调
+enum CallbackDelivery {
+  Drop(ListenerCallback),
+  Queue(ListenerCallback),
+  Latest(LatestSender),
+}
 
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    let png_bytes = image_to_png_bytes(&rust_image, "Failed to set image")?;
-    return wayland::set_image_raw(png_bytes)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")));
+impl CallbackDelivery {
+  fn new(callback: ListenerCallback, backpressure: Backpressure, armed: ListenerArmed) -> Self {
+    match backpressure {
+      Backpressure::Drop => CallbackDelivery::Drop(callback),
+      Backpressure::Queue => CallbackDelivery::Queue(callback),
+      Backpressure::Latest => {
+        let slot = Arc::new(LatestSlot::default());
+        let worker_slot = slot.clone();
+        thread::spawn(move || run_latest_delivery(callback, worker_slot, armed));
+        CallbackDelivery::Latest(LatestSender(slot))
+      }
+    }
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  fn deliver(&self, data: ClipboardData) -> napi::Status {
+    match self {
+      CallbackDelivery::Drop(callback) => {
+        callback.call(data, ThreadsafeFunctionCallMode::NonBlocking)
+      }
+      CallbackDelivery::Queue(callback) => call_and_wait(callback, data),
+      CallbackDelivery::Latest(LatestSender(slot)) => {
+        slot.lock().pending = Some(data);
+        slot.ready.notify_one();
+        napi::Status::Ok
+      }
+    }
+  }
+}
 
-  context
-    .set_image(rust_image)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set image: {e}")))
+/// 监听器事件发送器，负责补充事件信息并调用 JS 回调
+/// 每个选区使用独立的发送器，previous_* 只在同一选区的事件之间填充
+struct ListenerEmitter {
+  delivery: Arc<CallbackDelivery>,
+  filter: Option<Arc<ListenerFilter>>,
+  settings: ListenerSettings,
+  /// 上一次发送的剪贴板内容，用于填充 previous_* 字段
+  previous: Option<ClipboardData>,
+  history: ListenerHistory,
 }
 
-/// 快速获取剪贴板图片原始数据（Buffer）
-#[napi]
-pub fn get_clipboard_image_raw() -> Result<Buffer> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    let image_data = wayland::get_image_raw()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
-    let png_bytes = image_bytes_to_png_bytes(&image_data, "Failed to get image")?;
-    return Ok(Buffer::from(png_bytes));
+impl ListenerEmitter {
+  /// 发送一次剪贴板变化事件，返回回调调用状态
+  fn emit(&mut self, data: ClipboardData) -> napi::Status {
+    match self.prepare(data) {
+      Some(data) => self.delivery.deliver(data),
+      None => napi::Status::Ok,
+    }
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 补充事件信息并更新 previous_* 与历史记录，返回待发送的事件，被过滤函数丢弃时返回 None
+  fn prepare(&mut self, data: ClipboardData) -> Option<ClipboardData> {
+    let mut data = match &self.filter {
+      Some(filter) => {
+        let selection = data.selection.clone();
+        let origin = data.origin.clone();
+        let mut filtered = apply_listener_filter(filter, data)?;
+        filtered.selection = filtered.selection.or(selection);
+        filtered.origin = filtered.origin.or(origin);
+        filtered
+      }
+      None => data,
+    };
 
-  let image_data = context
-    .get_image()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get image: {e}")))?;
+    if self.settings.detect_screenshots {
+      data.is_likely_screenshot = Some(is_likely_screenshot(&data));
+    }
 
-  let png_bytes = image_to_png_bytes(&image_data, "Failed to get image")?;
-  Ok(Buffer::from(png_bytes))
-}
+    // 写入失败时通过 savedImageError 告知回调，不影响事件的发送
+    if let (Some(dir), Some(image)) = (&self.settings.auto_save_images_dir, &data.image) {
+      match save_event_image(dir, &image.data) {
+        Ok(path) => data.saved_image_path = Some(path.to_string_lossy().into_owned()),
+        Err(e) => data.saved_image_error = Some(format!("Failed to save image: {e}")),
+      }
+    }
 
-/// 快速设置剪贴板自定义格式数据
-#[napi]
-pub fn set_clipboard_buffer(format: String, buffer: Buffer) -> Result<()> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::set_buffer(format, buffer.to_vec())
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")));
-  }
+    let snapshot = copy_clipboard_contents(&data);
+    if let Some(previous) = self.previous.replace(snapshot) {
+      data.previous_text = previous.text;
+      data.previous_rtf = previous.rtf;
+      data.previous_html = previous.html;
+      data.previous_image = previous.image;
+      data.previous_files = previous.files;
+    }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+    if self.settings.history_size > 0 {
+      let mut history = self
+        .history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+      while history.len() >= self.settings.history_size {
+        history.pop_front();
+      }
+      let mut entry = copy_clipboard_contents(&data);
+      entry.selection = data.selection.clone();
+      history.push_back(entry);
+    }
 
-  context
-    .set_buffer(&format, buffer.to_vec())
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set buffer: {e}")))
+    Some(data)
+  }
 }
 
-/// 快速获取剪贴板自定义格式数据
-#[napi]
-pub fn get_clipboard_buffer(format: String) -> Result<Buffer> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    let data = wayland::get_buffer(format)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
-    return Ok(Buffer::from(data));
+/// 节流线程：首个变化立即发送，之后每个间隔内最多发送一次最新的变化
+/// 分发器被释放（监听停止）后丢弃尚未发送的变化并退出，等待期间监听器被停用时同样丢弃
+fn run_throttle_loop(
+  mut emitter: ListenerEmitter,
+  events: std::sync::mpsc::Receiver<ClipboardData>,
+  interval: Duration,
+  armed: ListenerArmed,
+) {
+  use std::sync::mpsc::RecvTimeoutError;
+  use std::time::Instant;
+
+  while let Ok(first) = events.recv() {
+    emitter.emit(first);
+    let mut last_emit = Instant::now();
+
+    loop {
+      let mut pending = None;
+      loop {
+        let remaining = interval.saturating_sub(last_emit.elapsed());
+        match events.recv_timeout(remaining) {
+          Ok(data) => pending = Some(data),
+          Err(RecvTimeoutError::Timeout) => break,
+          // 监听已停止，不再调用回调
+          Err(RecvTimeoutError::Disconnected) => return,
+        }
+      }
+
+      match pending {
+        Some(data) if armed.load(Ordering::SeqCst) => {
+          emitter.emit(data);
+          last_emit = Instant::now();
+        }
+        _ => break,
+      }
+    }
   }
+}
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+/// 事件的发送方式
+enum DispatchTarget {
+  /// 直接调用回调
+  Direct(ListenerEmitter),
+  /// 交给节流线程发送
+  Throttled(std::sync::mpsc::Sender<ClipboardData>),
+}
 
-  let data = context
-    .get_buffer(&format)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get buffer: {e}")))?;
-  Ok(Buffer::from(data))
+/// 监听器事件分发器，负责补充事件信息并调用 JS 回调
+/// 通用监听器与 Wayland 监听器共用同一分发逻辑，同时监听多个选区时各监听线程共享同一分发器，
+/// 分发器按事件的选区交给该选区独立的发送状态（previous_* 与节流）处理
+#[derive(Clone)]
+pub(crate) struct ListenerDispatcher {
+  image_encoding: ImageEncoding,
+  armed: ListenerArmed,
+  delivery: Arc<CallbackDelivery>,
+  /// 各选区的发送状态，只包含监听的选区
+  targets: Arc<Vec<(&'static str, Mutex<DispatchTarget>)>>,
+  /// 只分发一次事件时的已分发标记与通知通道，分发后发送通知
+  /// 与 armed 分开记录，使已分发的事件在节流或 latest 线程中仍能送达；提前取消时由调用方设置标记
+  fired: Option<(Arc<AtomicBool>, std::sync::mpsc::Sender<()>)>,
 }
 
-/// 快速设置剪贴板文件列表
-#[napi]
-pub fn set_clipboard_files(files: Vec<String>) -> Result<()> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::set_files(files)
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")));
+impl ListenerDispatcher {
+  fn new(
+    callback: ListenerCallback,
+    filter: Option<ListenerFilter>,
+    settings: ListenerSettings,
+    history: ListenerHistory,
+    armed: ListenerArmed,
+  ) -> Self {
+    let delivery = Arc::new(CallbackDelivery::new(
+      callback,
+      settings.backpressure,
+      armed.clone(),
+    ));
+    let filter = filter.map(Arc::new);
+    let targets = [
+      ("clipboard", settings.watch_clipboard),
+      ("primary", settings.watch_primary),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(selection, _)| {
+      let emitter = ListenerEmitter {
+        delivery: delivery.clone(),
+        filter: filter.clone(),
+        settings: settings.clone(),
+        previous: None,
+        history: history.clone(),
+      };
+      let target = match settings.throttle {
+        Some(interval) => {
+          let (events_tx, events_rx) = std::sync::mpsc::channel();
+          let armed = armed.clone();
+          thread::spawn(move || run_throttle_loop(emitter, events_rx, interval, armed));
+          DispatchTarget::Throttled(events_tx)
+        }
+        None => DispatchTarget::Direct(emitter),
+      };
+      (selection, Mutex::new(target))
+    })
+    .collect();
+
+    ListenerDispatcher {
+      image_encoding: settings.image_encoding,
+      armed,
+      delivery,
+      targets: Arc::new(targets),
+      fired: None,
+    }
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 只分发下一次事件：分发后设置 done 标记不再接受事件，并通过 fired 通知调用方停止监听
+  fn once(mut self, done: Arc<AtomicBool>, fired: std::sync::mpsc::Sender<()>) -> Self {
+    self.fired = Some((done, fired));
+    self
+  }
 
-  context
-    .set_files(files)
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to set files: {e}")))
-}
+  /// 监听事件中图片的编码方式
+  pub(crate) fn image_encoding(&self) -> ImageEncoding {
+    self.image_encoding
+  }
 
-/// 快速获取剪贴板文件列表
-#[napi]
-pub fn get_clipboard_files() -> Result<Vec<String>> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::get_files()
-      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")));
+  /// 分发一次剪贴板变化事件，返回回调调用状态
+  /// 未标记选区的事件视为来自 clipboard；来源在变化发生时立即判断，不受节流延迟影响
+  /// 监听器未启用回调时直接丢弃事件，不更新 previous_* 与历史记录
+  pub(crate) fn dispatch(&self, mut data: ClipboardData) -> napi::Status {
+    if !self.armed.load(Ordering::SeqCst) {
+      return napi::Status::Ok;
+    }
+    // 只分发一次时原子地设置已分发标记，保证多个监听线程同时收到变化时也只有一个事件被分发
+    if let Some((done, _)) = &self.fired {
+      if done.swap(true, Ordering::SeqCst) {
+        return napi::Status::Ok;
+      }
+    }
+    if data.selection.is_none() {
+      data.selection = Some("clipboard".to_string());
+    }
+    if data.origin.is_none() {
+      let origin = if data.selection.as_deref() == Some("clipboard") {
+        detect_change_origin()
+      } else {
+        "unknown"
+      };
+      data.origin = Some(origin.to_string());
+    }
+
+    let Some((_, target)) = self
+      .targets
+      .iter()
+      .find(|(selection, _)| data.selection.as_deref() == Some(*selection))
+    else {
+      return napi::Status::Ok;
+    };
+    let mut state = target
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let status = match &mut *state {
+      DispatchTarget::Direct(emitter) => {
+        let prepared = emitter.prepare(data);
+        // 只在补充事件信息时持有选区状态的锁，queue 模式等待回调完成期间不阻塞其他事件
+        drop(state);
+        prepared.map_or(napi::Status::Ok, |data| self.delivery.deliver(data))
+      }
+      DispatchTarget::Throttled(events) => match events.send(data) {
+        Ok(()) => napi::Status::Ok,
+        Err(_) => napi::Status::Closing,
+      },
+    };
+    if let Some((_, fired)) = &self.fired {
+      let _ = fired.send(());
+    }
+    status
   }
+}
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+/// 图片监听器的回调
+type ImageCallback = ThreadsafeFunction<ImageData, (), ImageData, napi::Status, false>;
 
-  context
-    .get_files()
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to get files: {e}")))
+/// watchImages 的事件处理：跳过与上一张相同的图片，生成缩略图后回调
+pub(crate) struct ImageWatchEmitter {
+  callback: ImageCallback,
+  max_dimension: u32,
+  /// 上一张图片的摘要，剪贴板中没有图片时为 None
+  last_digest: Option<Vec<u8>>,
+  armed: ListenerArmed,
 }
 
-/// 快速设置剪贴板复合内容（可同时设置多种格式）
-#[napi]
-pub fn set_clipboard_contents(contents: ClipboardData) -> Result<()> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::set_contents(contents).map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to set contents: {e}"),
-      )
-    });
+impl ImageWatchEmitter {
+  /// 处理一次图片变化，digest 与上一张相同时不解码也不回调
+  pub(crate) fn emit(&mut self, digest: Vec<u8>, decode: impl FnOnce() -> Option<DynamicImage>) {
+    if self.last_digest.as_ref() == Some(&digest) {
+      return;
+    }
+    self.last_digest = Some(digest);
+    // 未启用回调期间出现的图片视为已处理，启用后不会补发
+    if !self.armed.load(Ordering::SeqCst) {
+      return;
+    }
+
+    let Some(image) = decode() else {
+      return;
+    };
+    let thumbnail = if image.width() > self.max_dimension || image.height() > self.max_dimension {
+      image.thumbnail(self.max_dimension, self.max_dimension)
+    } else {
+      image
+    };
+    let Ok(bytes) = dynamic_image_to_png_bytes(&thumbnail, "Failed to encode thumbnail") else {
+      return;
+    };
+    let status = self.callback.call(
+      ImageData {
+        width: thumbnail.width(),
+        height: thumbnail.height(),
+        size: bytes.len() as u32,
+        data: Buffer::from(bytes),
+      },
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
+    listener_log!("watch_images callback dispatched: status={status:?}");
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 剪贴板中不再有图片，之后再次出现的图片即使与之前相同也会回调
+  pub(crate) fn reset(&mut self) {
+    self.last_digest = None;
+  }
+}
 
-  use clipboard_rs::ClipboardContent;
+/// 剪贴板监听选项
+#[napi(object)]
+#[derive(Default)]
+pub struct WatchOptions {
+  /// 监听事件中图片的编码方式：`png`（默认）、`jpeg:<quality>`（quality 取值 1-100）或 `webp`（无损）
+  pub image_mode: Option<String>,
+  /// 是否检测截图，开启后事件中会提供 isLikelyScreenshot 字段
+  pub detect_screenshots: Option<bool>,
+  /// 节流间隔（毫秒）：首个变化立即回调，持续变化时每个间隔最多回调一次最新内容
+  pub throttle_ms: Option<u32>,
+  /// 保留最近事件的数量，通过 getHistory 读取，超出时丢弃最旧的事件
+  pub history_size: Option<u32>,
+  /// 回调繁忙时的处理方式：`drop`（默认，非阻塞调用，队列满时丢弃）、
+  /// `latest`（回调执行期间只保留最新事件）或 `queue`（阻塞调用，逐个处理所有事件）
+  pub backpressure: Option<String>,
+  /// 要监听的选区：`clipboard`（默认）和/或 `primary`
+  /// primary 仅 Linux 支持且只提供文本，其他平台忽略 primary
+  pub selections: Option<Vec<String>>,
+  /// 是否以未启用回调的状态开始监听，调用 arm 后才开始回调，避免准备完成前的事件被送达
+  pub start_disarmed: Option<bool>,
+  /// 自动保存图片的目录：事件包含图片时在监听线程中写入 `<毫秒时间戳>.<扩展名>`，目录不存在时自动创建
+  /// 扩展名按图片数据的文件头识别，与 imageMode 一致（例如 `jpeg:80` 保存为 `.jpg`），并非总是 `.png`；
+  /// 同一毫秒内已有同名文件时追加序号（`<毫秒时间戳>-1.<扩展名>`），不会覆盖已有文件
+  /// 保存的路径通过事件的 savedImagePath 提供，失败原因通过 savedImageError 提供
+  pub auto_save_images_dir: Option<String>,
+}
 
-  let mut clipboard_contents = Vec::new();
+/// 解析后的监听器配置
+#[derive(Clone, Debug)]
+pub(crate) struct ListenerSettings {
+  image_encoding: ImageEncoding,
+  detect_screenshots: bool,
+  throttle: Option<Duration>,
+  history_size: usize,
+  backpressure: Backpressure,
+  watch_clipboard: bool,
+  watch_primary: bool,
+  start_disarmed: bool,
+  auto_save_images_dir: Option<std::path::PathBuf>,
+}
 
-  if let Some(text) = contents.text {
-    clipboard_contents.push(ClipboardContent::Text(text));
+impl ListenerSettings {
+  fn from_options(options: Option<WatchOptions>) -> Result<Self> {
+    let options = options.unwrap_or_default();
+    let selections = options
+      .selections
+      .clone()
+      .unwrap_or_else(|| vec!["clipboard".to_string()]);
+    let mut watch_clipboard = false;
+    let mut watch_primary = false;
+    for selection in &selections {
+      match selection.as_str() {
+        "clipboard" => watch_clipboard = true,
+        "primary" => watch_primary = cfg!(target_os = "linux"),
+        _ => {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!("Unsupported selection: {selection}"),
+          ))
+        }
+      }
+    }
+    // 其他平台忽略 primary 后没有可监听的选区时，回退为监听 clipboard
+    if !watch_clipboard && !watch_primary {
+      watch_clipboard = true;
+    }
+    let image_encoding = match options.image_mode.as_deref() {
+      Some(mode) => ImageEncoding::parse(mode)?,
+      None => ImageEncoding::Png,
+    };
+
+    Ok(ListenerSettings {
+      image_encoding,
+      detect_screenshots: options.detect_screenshots.unwrap_or(false),
+      throttle: options
+        .throttle_ms
+        .filter(|ms| *ms > 0)
+        .map(|ms| Duration::from_millis(u64::from(ms))),
+      history_size: options.history_size.unwrap_or(0) as usize,
+      backpressure: match options.backpressure.as_deref() {
+        Some(mode) => Backpressure::parse(mode)?,
+        None => Backpressure::Drop,
+      },
+      watch_clipboard,
+      watch_primary,
+      start_disarmed: options.start_disarmed.unwrap_or(false),
+      auto_save_images_dir: options
+        .auto_save_images_dir
+        .filter(|dir| !dir.is_empty())
+        .map(std::path::PathBuf::from),
+    })
   }
 
-  if let Some(html) = contents.html {
-    clipboard_contents.push(ClipboardContent::Html(html));
+  fn to_config(&self, is_wayland: bool, is_watching: bool, paused: bool) -> ListenerConfig {
+    let selections = [
+      ("clipboard", self.watch_clipboard),
+      ("primary", self.watch_primary),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(selection, _)| selection.to_string())
+    .collect();
+    ListenerConfig {
+      is_wayland,
+      is_watching,
+      image_mode: self.image_encoding.describe(),
+      detect_screenshots: self.detect_screenshots,
+      throttle_ms: self.throttle.map(|throttle| throttle.as_millis() as u32),
+      history_size: self.history_size as u32,
+      backpressure: self.backpressure.name().to_string(),
+      selections,
+      paused,
+      auto_save_images_dir: self
+        .auto_save_images_dir
+        .as_ref()
+        .map(|dir| dir.to_string_lossy().into_owned()),
+    }
   }
+}
 
-  if let Some(rtf) = contents.rtf {
-    clipboard_contents.push(ClipboardContent::Rtf(rtf));
+/// 监听器当前生效的配置，包含 watch 选项解析后的值以及未指定选项的默认值
+#[napi(object)]
+pub struct ListenerConfig {
+  /// 是否使用 Wayland 专用监听器
+  pub is_wayland: bool,
+  /// 是否正在监听
+  pub is_watching: bool,
+  /// 事件中图片的编码方式：`png`、`jpeg:<quality>` 或 `webp`
+  pub image_mode: String,
+  /// 是否检测截图
+  pub detect_screenshots: bool,
+  /// 节流间隔（毫秒），未启用节流时为空
+  pub throttle_ms: Option<u32>,
+  /// 保留最近事件的数量，0 表示不保留
+  pub history_size: u32,
+  /// 回调繁忙时的处理方式：`drop`、`latest` 或 `queue`
+  pub backpressure: String,
+  /// 实际监听的选区，其他平台忽略的 primary 不包含在内
+  pub selections: Vec<String>,
+  /// 回调是否已停用（未 arm 或已 disarm），停用期间事件被丢弃
+  pub paused: bool,
+  /// 自动保存图片的目录
+  pub auto_save_images_dir: Option<String>,
+}
+
+/// 轮询式剪贴板变化检测器，不使用后台线程和回调
+/// 适用于不便使用原生线程或线程安全函数的运行环境
+/// 优先使用系统剪贴板序列号判断变化，不支持时比较内容指纹
+#[napi]
+pub struct ClipboardPoller {
+  manager: ClipboardManager,
+  last_sequence: Option<u32>,
+  last_fingerprint: Option<String>,
+}
+
+#[napi]
+impl ClipboardPoller {
+  /// 创建新的轮询器实例，并记录当前剪贴板状态作为基准
+  #[napi(constructor)]
+  pub fn new() -> Result<Self> {
+    let manager = ClipboardManager::new()?;
+    let last_sequence = clipboard_sequence_number();
+    let last_fingerprint = if last_sequence.is_none() {
+      Some(Self::poll_fingerprint(&manager))
+    } else {
+      None
+    };
+
+    Ok(ClipboardPoller {
+      manager,
+      last_sequence,
+      last_fingerprint,
+    })
   }
 
-  if let Some(image_data) = contents.image {
-    let rust_image = RustImageData::from_bytes(image_data.data.as_ref()).map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to create image from bytes: {e}"),
-      )
-    })?;
+  /// 检查自上次轮询以来剪贴板是否变化
+  /// 变化时返回完整的剪贴板数据，未变化时返回 null
+  #[napi]
+  pub fn poll_changes(&mut self) -> Result<Option<ClipboardData>> {
+    if let Some(sequence) = clipboard_sequence_number() {
+      if self.last_sequence == Some(sequence) {
+        return Ok(None);
+      }
+      // 读取失败时保留旧序列号，下次轮询重新报告这次变化
+      let data = self.manager.read_full_data()?;
+      self.last_sequence = Some(sequence);
+      return Ok(Some(data));
+    }
 
-    clipboard_contents.push(ClipboardContent::Image(rust_image));
+    let fingerprint = Self::poll_fingerprint(&self.manager);
+    if self.last_fingerprint.as_deref() == Some(fingerprint.as_str()) {
+      return Ok(None);
+    }
+    let data = self.manager.read_full_data()?;
+    self.last_fingerprint = Some(fingerprint);
+    Ok(Some(data))
   }
 
-  if let Some(files) = contents.files {
-    clipboard_contents.push(ClipboardContent::Files(files));
+  /// 计算轮询比较用的内容指纹，图片按源数据计算，避免每次轮询都编码 PNG
+  fn poll_fingerprint(manager: &ClipboardManager) -> String {
+    let data = ClipboardData {
+      text: manager.get_text().ok(),
+      html: manager.get_html().ok(),
+      rtf: manager.get_rich_text().ok(),
+      files: manager.get_files().ok(),
+      ..Default::default()
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(clipboard_fingerprint(&data).as_bytes());
+    if let Ok(image) = manager.read_source_image() {
+      hasher.update(b"image");
+      hasher.update(&image.bytes);
+    }
+
+    hasher
+      .finalize()
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect()
   }
+}
 
-  context.set(clipboard_contents).map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to set contents: {e}"),
-    )
-  })
+/// 分块写入图片时默认允许的最大字节数
+const DEFAULT_IMAGE_STREAM_MAX_SIZE: usize = 64 * 1024 * 1024;
+
+/// 分块写入剪贴板图片的写入器，由 ClipboardManager.setImageFromStream 创建
+/// 调用 finish 完成写入，调用 abort 取消并释放已接收的数据，完成或取消后不能继续写入
+#[napi]
+pub struct ImageStreamWriter {
+  manager: ClipboardManager,
+  /// 已接收的数据，完成或取消后为 None
+  buffer: Option<Vec<u8>>,
+  max_size: usize,
 }
 
-/// 快速获取完整的剪贴板数据
 #[napi]
-pub fn get_full_clipboard_data() -> Result<ClipboardData> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::get_full_clipboard_data().map_err(|e| {
+impl ImageStreamWriter {
+  fn open_buffer(&mut self) -> Result<&mut Vec<u8>> {
+    self.buffer.as_mut().ok_or_else(|| {
       Error::new(
         Status::GenericFailure,
-        format!("Failed to get clipboard data: {e}"),
+        "Image stream is already finished or aborted".to_string(),
       )
-    });
+    })
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 追加一块图片数据，累计大小超过上限时返回错误并取消写入
+  #[napi]
+  pub fn write(&mut self, chunk: Buffer) -> Result<()> {
+    let max_size = self.max_size;
+    let buffer = self.open_buffer()?;
+    if buffer.len() + chunk.len() > max_size {
+      self.buffer = None;
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Image stream exceeds maximum size of {max_size} bytes"),
+      ));
+    }
+    buffer.extend_from_slice(&chunk);
+    Ok(())
+  }
 
-  Ok(get_clipboard_data(&context))
+  /// 完成写入，解码已接收的数据并设置到剪贴板
+  #[napi]
+  pub fn finish(&mut self) -> Result<()> {
+    let bytes = std::mem::take(self.open_buffer()?);
+    self.buffer = None;
+    self.manager.set_image_raw(Buffer::from(bytes))
+  }
+
+  /// 取消写入并丢弃已接收的数据，重复调用无副作用
+  #[napi]
+  pub fn abort(&mut self) {
+    self.buffer = None;
+  }
+
+  /// 获取已接收的字节数
+  #[napi]
+  pub fn get_bytes_written(&self) -> u32 {
+    self.buffer.as_ref().map_or(0, |buffer| buffer.len() as u32)
+  }
 }
 
-/// 快速清空剪贴板
+/// 批量读写剪贴板的事务，由 ClipboardManager.begin 创建
+/// 创建时在一次剪贴板会话中读取全部标准格式，读取方法均返回该快照中的内容，事务期间不会一直占用剪贴板，
+/// commit 时另行打开剪贴板写入；写入方法只暂存数据，调用 commit 时一次性写入剪贴板。
+/// 未提交的事务在调用 abort 或被垃圾回收时丢弃暂存内容，不修改剪贴板
 #[napi]
-pub fn clear_clipboard() -> Result<()> {
-  #[cfg(target_os = "linux")]
-  if is_wayland_environment() {
-    return wayland::clear().map_err(|e| {
-      Error::new(
+pub struct ClipboardTransaction {
+  manager: ClipboardManager,
+  snapshot: ClipboardData,
+  staged: ClipboardData,
+  staged_extra: Vec<(String, Vec<u8>)>,
+  /// 已提交或已取消
+  finished: bool,
+}
+
+#[napi]
+impl ClipboardTransaction {
+  fn ensure_open(&self) -> Result<()> {
+    if self.finished {
+      return Err(Error::new(
         Status::GenericFailure,
-        format!("Failed to clear clipboard: {e}"),
-      )
-    });
+        "Transaction is already finished".to_string(),
+      ));
+    }
+    Ok(())
   }
 
-  let context = ClipboardContext::new().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to create clipboard context: {e}"),
-    )
-  })?;
+  /// 获取快照中的纯文本内容
+  #[napi]
+  pub fn get_text(&self) -> Result<Option<String>> {
+    self.ensure_open()?;
+    Ok(self.snapshot.text.clone())
+  }
 
-  context.clear().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to clear clipboard: {e}"),
-    )
-  })
-}
+  /// 获取快照中的 HTML 内容
+  #[napi]
+  pub fn get_html(&self) -> Result<Option<String>> {
+    self.ensure_open()?;
+    Ok(self.snapshot.html.clone())
+  }
 
-/// 获取完整的剪贴板数据
-fn get_clipboard_data(context: &ClipboardContext) -> ClipboardData {
-  // 定义要检查的格式类型，对应 ClipboardContent 枚举
-  // Text, Rtf, Html, Image, Files
-  let format_checks = [
-    ("text", ContentFormat::Text),
-    ("rtf", ContentFormat::Rtf),
-    ("html", ContentFormat::Html),
-    ("image", ContentFormat::Image),
-    ("files", ContentFormat::Files),
-  ];
+  /// 获取快照中的 RTF 富文本内容
+  #[napi]
+  pub fn get_rich_text(&self) -> Result<Option<String>> {
+    self.ensure_open()?;
+    Ok(self.snapshot.rtf.clone())
+  }
 
-  // 初始化数据变量
-  let mut available_formats = Vec::new();
-  let mut text = None;
-  let mut rtf = None;
-  let mut html = None;
-  let mut image = None;
-  let mut files = None;
+  /// 获取快照中的图片数据（PNG）
+  #[napi]
+  pub fn get_image_data(&self) -> Result<Option<ImageData>> {
+    self.ensure_open()?;
+    Ok(self.snapshot.image.as_ref().map(copy_image_data))
+  }
 
-  // 使用 has 接口检查每种标准格式的可用性并使用对应的 get 接口获取数据
-  for (format_name, content_format) in format_checks.iter() {
-    if context.has(content_format.clone()) {
-      available_formats.push(format_name.to_string());
+  /// 获取快照中的文件列表
+  #[napi]
+  pub fn get_files(&self) -> Result<Option<Vec<String>>> {
+    self.ensure_open()?;
+    Ok(self.snapshot.files.clone())
+  }
 
-      match *format_name {
-        "text" => {
-          text = context.get_text().ok();
-        }
-        "rtf" => {
-          rtf = context.get_rich_text().ok();
-        }
-        "html" => {
-          html = context.get_html().ok();
-        }
-        "image" => {
-          image = context.get_image().ok().and_then(|img_data| {
-            let (width, height) = img_data.get_size();
-            img_data.to_png().ok().map(|png_data| {
-              let bytes = png_data.get_bytes();
-              ImageData {
-                width,
-                height,
-                size: bytes.len() as u32,
-                data: Buffer::from(bytes.to_vec()),
-              }
-            })
-          });
-        }
-        "files" => {
-          files = context.get_files().ok();
-        }
-        _ => {}
-      }
+  /// 暂存纯文本内容
+  #[napi]
+  pub fn set_text(&mut self, text: String) -> Result<()> {
+    self.ensure_open()?;
+    self.staged.text = Some(text);
+    Ok(())
+  }
+
+  /// 暂存 HTML 内容
+  #[napi]
+  pub fn set_html(&mut self, html: String) -> Result<()> {
+    self.ensure_open()?;
+    self.staged.html = Some(html);
+    Ok(())
+  }
+
+  /// 暂存 RTF 富文本内容
+  #[napi]
+  pub fn set_rich_text(&mut self, text: String) -> Result<()> {
+    self.ensure_open()?;
+    self.staged.rtf = Some(text);
+    Ok(())
+  }
+
+  /// 暂存图片，数据无法解析为图片时返回 InvalidArg 错误
+  #[napi]
+  pub fn set_image_raw(&mut self, image_data: Buffer) -> Result<()> {
+    self.ensure_open()?;
+    let (width, height) = image_bytes_dimensions(&image_data, "Failed to set image")?;
+    self.staged.image = Some(ImageData {
+      width,
+      height,
+      size: image_data.len() as u32,
+      data: image_data,
+    });
+    Ok(())
+  }
+
+  /// 暂存文件列表
+  #[napi]
+  pub fn set_files(&mut self, files: Vec<String>) -> Result<()> {
+    self.ensure_open()?;
+    self.staged.files = Some(files);
+    Ok(())
+  }
+
+  /// 暂存自定义格式数据，同名格式会被替换
+  #[napi]
+  pub fn set_buffer(&mut self, format: String, buffer: Buffer) -> Result<()> {
+    self.ensure_open()?;
+    self.staged_extra.retain(|(name, _)| *name != format);
+    self.staged_extra.push((format, buffer.to_vec()));
+    Ok(())
+  }
+
+  /// 将暂存的全部内容一次性写入剪贴板并结束事务，没有暂存内容时不修改剪贴板
+  /// 未暂存的标准格式按快照中的内容一并写入，剪贴板中原有的自定义格式不会保留
+  #[napi]
+  pub fn commit(&mut self) -> Result<()> {
+    self.ensure_open()?;
+    self.finished = true;
+
+    let staged = std::mem::take(&mut self.staged);
+    let staged_extra = std::mem::take(&mut self.staged_extra);
+    let has_staged = staged.text.is_some()
+      || staged.html.is_some()
+      || staged.rtf.is_some()
+      || staged.image.is_some()
+      || staged.files.is_some()
+      || !staged_extra.is_empty();
+    if !has_staged {
+      return Ok(());
     }
+
+    let snapshot = std::mem::take(&mut self.snapshot);
+    let contents = ClipboardData {
+      text: staged.text.or(snapshot.text),
+      html: staged.html.or(snapshot.html),
+      rtf: staged.rtf.or(snapshot.rtf),
+      image: staged.image.or(snapshot.image),
+      files: staged.files.or(snapshot.files),
+      ..Default::default()
+    };
+    self.manager.set_contents_with_extra(contents, staged_extra)
   }
 
-  ClipboardData {
-    available_formats,
-    text,
-    rtf,
-    html,
-    image,
-    files,
+  /// 丢弃暂存内容并结束事务，不修改剪贴板；重复调用无副作用
+  #[napi]
+  pub fn abort(&mut self) {
+    self.finished = true;
+    self.staged = ClipboardData::default();
+    self.staged_extra.clear();
   }
 }
 
@@ -1245,7 +6368,13 @@ enum ListenerType {
 #[napi]
 pub struct ClipboardListener {
   listener_type: Option<ListenerType>,
+  /// PRIMARY 选区监听线程的停止通道（仅 Linux）
+  primary_stop: Option<std::sync::mpsc::Sender<()>>,
   is_wayland: bool,
+  history: ListenerHistory,
+  armed: ListenerArmed,
+  /// 最近一次 watch 使用的配置，watchImages 使用默认配置
+  settings: ListenerSettings,
 }
 
 #[napi]
@@ -1263,23 +6392,36 @@ impl ClipboardListener {
     );
     Ok(ClipboardListener {
       listener_type: None,
+      primary_stop: None,
       is_wayland,
+      history: ListenerHistory::default(),
+      armed: Arc::new(AtomicBool::new(true)),
+      settings: ListenerSettings::from_options(None)?,
     })
   }
 
   /// 开始监听剪贴板变化
   /// callback: 当剪贴板变化时调用的回调函数，参数为包含所有格式数据的复杂对象
+  /// options: 可选的监听选项，例如 `{ imageMode: 'jpeg:80' }`
+  /// filter: 可选的同步过滤函数，在 callback 之前调用；返回 null 丢弃事件，返回对象时以其替换事件数据。
+  /// 过滤函数在 JS 线程上执行，监听线程最多等待 1 秒，超时或抛出异常的事件同样被丢弃
   /// 自动根据当前环境选择合适的监听方式（Wayland 或通用）
   #[napi]
-  pub fn watch(&mut self, callback: Function<ClipboardData, ()>) -> Result<()> {
+  pub fn watch(
+    &mut self,
+    callback: Function<ClipboardData, ()>,
+    options: Option<WatchOptions>,
+    filter: Option<Function<ClipboardData, Option<ClipboardData>>>,
+  ) -> Result<()> {
+    let settings = ListenerSettings::from_options(options)?;
     listener_log!(
       "watch called, current_listener_exists={}, is_wayland={}",
-      self.listener_type.is_some(),
+      self.is_watching(),
       self.is_wayland
     );
 
     // 如果已经在监听，先停止
-    if self.listener_type.is_some() {
+    if self.is_watching() {
       listener_log!("watch called while already watching, stopping previous listener");
       self.stop()?;
     }
@@ -1289,25 +6431,64 @@ impl ClipboardListener {
       .build_threadsafe_function()
       .build_callback(|ctx| Ok(ctx.value))?;
     listener_log!("threadsafe callback created");
+    listener_log!("listener settings: {:?}", settings);
+    let (watch_clipboard, watch_primary) = (settings.watch_clipboard, settings.watch_primary);
+    let filter = filter
+      .map(|filter| {
+        filter
+          .build_threadsafe_function()
+          .build_callback(|ctx| Ok(ctx.value))
+      })
+      .transpose()?;
+    self.armed.store(!settings.start_disarmed, Ordering::SeqCst);
+    self.settings = settings.clone();
+    let dispatcher = ListenerDispatcher::new(
+      tsfn,
+      filter,
+      settings,
+      self.history.clone(),
+      self.armed.clone(),
+    );
+    self.start_dispatcher(dispatcher, watch_clipboard, watch_primary)
+  }
 
-    if self.is_wayland {
+  /// 按选区启动监听线程，各线程共享同一分发器
+  /// clipboard 监听启动失败时停止已启动的 PRIMARY 监听
+  fn start_dispatcher(
+    &mut self,
+    dispatcher: ListenerDispatcher,
+    watch_clipboard: bool,
+    watch_primary: bool,
+  ) -> Result<()> {
+    if watch_primary {
+      listener_log!("starting primary selection listener");
+      self.watch_primary(dispatcher.clone())?;
+    }
+    if !watch_clipboard {
+      return Ok(());
+    }
+
+    let started = if self.is_wayland {
       listener_log!("starting wayland listener");
-      self.watch_wayland(tsfn)
+      self.watch_wayland(dispatcher)
     } else {
       listener_log!("starting generic listener");
-      self.watch_generic(tsfn)
+      self.watch_generic(dispatcher)
+    };
+    if started.is_err() {
+      if let Some(primary_stop) = self.primary_stop.take() {
+        let _ = primary_stop.send(());
+      }
     }
+    started
   }
 
   /// 使用 Wayland 专用监听器监听剪贴板变化
   #[cfg(target_os = "linux")]
-  fn watch_wayland(
-    &mut self,
-    tsfn: ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>,
-  ) -> Result<()> {
+  fn watch_wayland(&mut self, dispatcher: ListenerDispatcher) -> Result<()> {
     listener_log!("watch_wayland setup begin");
 
-    let stop_tx = wayland::start_wayland_watch(tsfn);
+    let stop_tx = wayland::start_wayland_watch(dispatcher);
 
     // 保存停止通道
     self.listener_type = Some(ListenerType::Wayland(stop_tx));
@@ -1317,23 +6498,71 @@ impl ClipboardListener {
 
   /// 非 Linux 平台的 Wayland 监听器（空实现）
   #[cfg(not(target_os = "linux"))]
-  fn watch_wayland(
-    &mut self,
-    _tsfn: ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>,
-  ) -> Result<()> {
+  fn watch_wayland(&mut self, _dispatcher: ListenerDispatcher) -> Result<()> {
     Err(Error::new(
       Status::GenericFailure,
       "Wayland clipboard listener is not supported on this platform".to_string(),
     ))
   }
 
+  /// 监听 PRIMARY 选区变化：Wayland 下轮询选区文本，X11 下使用 XFixes 扩展
+  #[cfg(target_os = "linux")]
+  fn watch_primary(&mut self, dispatcher: ListenerDispatcher) -> Result<()> {
+    let stop_tx = if self.is_wayland {
+      wayland::start_wayland_primary_watch(dispatcher)
+    } else {
+      x11::start_primary_watch(dispatcher).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to watch primary selection: {e}"),
+        )
+      })?
+    };
+    self.primary_stop = Some(stop_tx);
+    Ok(())
+  }
+
+  /// 非 Linux 平台没有 PRIMARY 选区（空实现）
+  #[cfg(not(target_os = "linux"))]
+  fn watch_primary(&mut self, _dispatcher: ListenerDispatcher) -> Result<()> {
+    Ok(())
+  }
+
   /// 使用通用监听器监听剪贴板变化
-  fn watch_generic(
-    &mut self,
-    tsfn: ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>,
-  ) -> Result<()> {
+  fn watch_generic(&mut self, dispatcher: ListenerDispatcher) -> Result<()> {
     listener_log!("watch_generic setup begin");
 
+    // 创建处理器
+    struct Handler {
+      ctx: ClipboardContext,
+      dispatcher: ListenerDispatcher,
+    }
+
+    impl ClipboardHandler for Handler {
+      fn on_clipboard_change(&mut self) {
+        let clipboard_data = {
+          let _lock = lock::acquire();
+          get_clipboard_data_with_encoding(&self.ctx, self.dispatcher.image_encoding())
+        };
+        let status = self.dispatcher.dispatch(clipboard_data);
+        if status == napi::Status::Ok {
+          listener_log!("watch_generic callback dispatched");
+        } else {
+          listener_log!("watch_generic callback dispatch failed: status={status:?}");
+        }
+      }
+    }
+
+    self.start_generic_watcher(move |ctx| Handler { ctx, dispatcher });
+    Ok(())
+  }
+
+  /// 在新线程中启动 clipboard_rs 监听器，处理器在监听线程中使用新建的剪贴板上下文创建
+  fn start_generic_watcher<H, F>(&mut self, make_handler: F)
+  where
+    H: ClipboardHandler + 'static,
+    F: FnOnce(ClipboardContext) -> H + Send + 'static,
+  {
     // 创建通道用于传递 shutdown
     let (shutdown_tx, shutdown_rx) = std::sync::mpsc::channel::<clipboard_rs::WatcherShutdown>();
 
@@ -1353,30 +6582,7 @@ impl ClipboardListener {
         }
       };
 
-      // 创建处理器
-      struct Handler {
-        ctx: ClipboardContext,
-        callback: ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>,
-      }
-
-      impl ClipboardHandler for Handler {
-        fn on_clipboard_change(&mut self) {
-          let clipboard_data = get_clipboard_data(&self.ctx);
-          let status = self
-            .callback
-            .call(clipboard_data, ThreadsafeFunctionCallMode::NonBlocking);
-          if status == napi::Status::Ok {
-            listener_log!("watch_generic callback dispatched");
-          } else {
-            listener_log!("watch_generic callback dispatch failed: status={status:?}");
-          }
-        }
-      }
-
-      let handler = Handler {
-        ctx,
-        callback: tsfn,
-      };
+      let handler = make_handler(ctx);
 
       // 创建监听器上下文
       let mut watcher = match ClipboardWatcherContext::new() {
@@ -1416,7 +6622,73 @@ impl ClipboardListener {
     } else {
       listener_log!("watch_generic failed to receive shutdown channel from worker");
     }
+  }
+
+  /// 只监听剪贴板图片，图片出现或变化时回调不超过 maxDimension 的 PNG 缩略图
+  /// 非图片的变化不读取任何内容也不触发回调；与上一张相同的图片不会重复回调，中间出现非图片内容后再次复制会重新回调
+  /// 与 watch 共用监听状态，调用时会停止正在进行的监听
+  #[napi]
+  pub fn watch_images(
+    &mut self,
+    max_dimension: u32,
+    callback: Function<ImageData, ()>,
+  ) -> Result<()> {
+    if max_dimension == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "maxDimension must be greater than 0".to_string(),
+      ));
+    }
+    if self.is_watching() {
+      self.stop()?;
+    }
+
+    let tsfn = callback
+      .build_threadsafe_function()
+      .build_callback(|ctx| Ok(ctx.value))?;
+    let emitter = ImageWatchEmitter {
+      callback: tsfn,
+      max_dimension,
+      last_digest: None,
+      armed: self.armed.clone(),
+    };
+    self.armed.store(true, Ordering::SeqCst);
+    self.settings = ListenerSettings::from_options(None)?;
+
+    #[cfg(target_os = "linux")]
+    if self.is_wayland {
+      self.listener_type = Some(ListenerType::Wayland(wayland::start_wayland_image_watch(
+        emitter,
+      )));
+      return Ok(());
+    }
+
+    struct Handler {
+      ctx: ClipboardContext,
+      emitter: ImageWatchEmitter,
+    }
 
+    impl ClipboardHandler for Handler {
+      fn on_clipboard_change(&mut self) {
+        let image = {
+          let _lock = lock::acquire();
+          if !self.ctx.has(ContentFormat::Image) {
+            None
+          } else {
+            self.ctx.get_image().ok()
+          }
+        };
+        match image.and_then(|image| image.get_dynamic_image().ok()) {
+          Some(image) => {
+            let digest = Sha256::digest(image.as_bytes()).to_vec();
+            self.emitter.emit(digest, || Some(image));
+          }
+          None => self.emitter.reset(),
+        }
+      }
+    }
+
+    self.start_generic_watcher(move |ctx| Handler { ctx, emitter });
     Ok(())
   }
 
@@ -1440,13 +6712,41 @@ impl ClipboardListener {
     } else {
       listener_log!("stop called but no active listener");
     }
+
+    if let Some(primary_stop) = self.primary_stop.take() {
+      listener_log!("stop called for primary selection listener");
+      let _ = primary_stop.send(());
+    }
     Ok(())
   }
 
+  /// 启用回调：监听线程保持运行，之后的剪贴板变化会送达回调
+  #[napi]
+  pub fn arm(&self) {
+    self.armed.store(true, Ordering::SeqCst);
+  }
+
+  /// 停用回调：监听线程保持运行但丢弃所有事件，调用 arm 后恢复；与 stop 不同，不会停止监听线程
+  #[napi]
+  pub fn disarm(&self) {
+    self.armed.store(false, Ordering::SeqCst);
+  }
+
+  /// 获取监听器保留的最近事件（需开启 historySize 选项），按时间从旧到新排列
+  /// 停止监听后仍可读取
+  #[napi]
+  pub fn get_history(&self) -> Vec<ClipboardData> {
+    let history = self
+      .history
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    history.iter().map(copy_clipboard_contents).collect()
+  }
+
   /// 检查是否正在监听
   #[napi]
   pub fn is_watching(&self) -> bool {
-    self.listener_type.is_some()
+    self.listener_type.is_some() || self.primary_stop.is_some()
   }
 
   /// 获取当前使用的监听器类型
@@ -1458,4 +6758,88 @@ impl ClipboardListener {
       "generic".to_string()
     }
   }
+
+  /// 获取监听器当前生效的配置：最近一次 watch 传入的选项经解析后的值，未指定的选项为默认值
+  #[napi]
+  pub fn get_config(&self) -> ListenerConfig {
+    self.settings.to_config(
+      self.is_wayland,
+      self.is_watching(),
+      !self.armed.load(Ordering::SeqCst),
+    )
+  }
+}
+
+/// watchOnce 返回的句柄，用于在变化发生前取消监听
+#[napi]
+pub struct WatchOnceHandle {
+  done: Arc<AtomicBool>,
+  stop: std::sync::mpsc::Sender<()>,
+}
+
+#[napi]
+impl WatchOnceHandle {
+  /// 停止监听，回调不再触发；回调已触发或已超时后调用无副作用
+  #[napi]
+  pub fn stop(&self) {
+    self.done.store(true, Ordering::SeqCst);
+    let _ = self.stop.send(());
+  }
+}
+
+/// 只监听下一次剪贴板变化：回调至多触发一次，触发后自动停止监听，无需创建 ClipboardListener
+/// timeoutMs 为等待的最长毫秒数，超时后停止监听且不再触发回调；未指定时一直等待，
+/// 等待期间监听线程会让 Node 进程保持运行，可调用返回句柄的 stop 提前取消
+#[napi]
+pub fn watch_once(
+  callback: Function<ClipboardData, ()>,
+  timeout_ms: Option<f64>,
+) -> Result<WatchOnceHandle> {
+  let timeout = timeout_ms
+    .map(|ms| {
+      if ms.is_finite() && ms >= 0.0 {
+        Ok(Duration::from_secs_f64(ms / 1000.0))
+      } else {
+        Err(Error::new(
+          Status::InvalidArg,
+          format!("timeoutMs must be a non-negative finite number, got {ms}"),
+        ))
+      }
+    })
+    .transpose()?;
+
+  let mut listener = ClipboardListener::new()?;
+  let tsfn = callback
+    .build_threadsafe_function()
+    .build_callback(|ctx| Ok(ctx.value))?;
+  let settings = ListenerSettings::from_options(None)?;
+  let (fired_tx, fired_rx) = std::sync::mpsc::channel::<()>();
+  let done = Arc::new(AtomicBool::new(false));
+  let handle = WatchOnceHandle {
+    done: done.clone(),
+    stop: fired_tx.clone(),
+  };
+  let dispatcher = ListenerDispatcher::new(
+    tsfn,
+    None,
+    settings.clone(),
+    listener.history.clone(),
+    listener.armed.clone(),
+  )
+  .once(done, fired_tx);
+  listener.settings = settings;
+  listener.start_dispatcher(dispatcher, true, false)?;
+
+  std::thread::spawn(move || {
+    match timeout {
+      Some(timeout) => {
+        let _ = fired_rx.recv_timeout(timeout);
+      }
+      None => {
+        let _ = fired_rx.recv();
+      }
+    }
+    let _ = listener.stop();
+  });
+  Ok(handle)
 }
@@ -0,0 +1,53 @@
+use std::marker::PhantomData;
+use std::sync::{Condvar, Mutex};
+use std::thread::{self, ThreadId};
+
+/// 进程级剪贴板锁的状态：持有锁的线程及其重入次数
+struct LockState {
+  owner: Option<ThreadId>,
+  depth: usize,
+}
+
+static LOCK_STATE: Mutex<LockState> = Mutex::new(LockState {
+  owner: None,
+  depth: 0,
+});
+static LOCK_RELEASED: Condvar = Condvar::new();
+
+/// 进程级剪贴板锁的守卫，释放时解锁；只能在获取锁的线程上释放
+pub(crate) struct ClipboardLockGuard {
+  _not_send: PhantomData<*const ()>,
+}
+
+/// 获取进程级剪贴板锁，同一线程可重入
+/// 主线程与各 worker 线程共享同一个原生模块实例，通过该锁串行化所有剪贴板访问，
+/// 避免并发打开剪贴板失败（Windows）或交错读写得到不一致的数据
+pub(crate) fn acquire() -> ClipboardLockGuard {
+  let current = thread::current().id();
+  let mut state = LOCK_STATE
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  while state.owner.is_some_and(|owner| owner != current) {
+    state = LOCK_RELEASED
+      .wait(state)
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+  }
+  state.owner = Some(current);
+  state.depth += 1;
+  ClipboardLockGuard {
+    _not_send: PhantomData,
+  }
+}
+
+impl Drop for ClipboardLockGuard {
+  fn drop(&mut self) {
+    let mut state = LOCK_STATE
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.depth -= 1;
+    if state.depth == 0 {
+      state.owner = None;
+      LOCK_RELEASED.notify_one();
+    }
+  }
+}
@@ -0,0 +1,51 @@
+use objc2::runtime::ProtocolObject;
+use objc2_app_kit::{
+  NSPasteboard, NSPasteboardItem, NSPasteboardTypePNG, NSPasteboardTypeString, NSPasteboardWriting,
+};
+use objc2_foundation::{NSArray, NSData, NSString};
+
+type MacosResult<T> = std::result::Result<T, String>;
+
+/// 将文本写入指定名称的私有剪贴板（命名 NSPasteboard），通用剪贴板保持不变
+/// 系统不限制命名剪贴板的访问，知道名称的应用都可以读取
+pub(crate) fn set_named_pasteboard_text(name: &str, text: &str) -> MacosResult<()> {
+  let pasteboard = NSPasteboard::pasteboardWithName(&NSString::from_str(name));
+  pasteboard.clearContents();
+  // SAFETY: NSPasteboardTypeString 是 AppKit 导出的常量，在进程生命周期内有效
+  let string_type = unsafe { NSPasteboardTypeString };
+  if pasteboard.setString_forType(&NSString::from_str(text), string_type) {
+    Ok(())
+  } else {
+    Err(format!("Failed to write to pasteboard: {name}"))
+  }
+}
+
+/// 获取通用剪贴板的 changeCount，剪贴板内容每次变化时递增
+pub(crate) fn general_pasteboard_change_count() -> u32 {
+  // changeCount 只用于判断是否变化，截断为 u32 后回绕不影响比较
+  NSPasteboard::generalPasteboard().changeCount() as u32
+}
+
+/// 将多张 PNG 图片分别作为独立的项目写入通用剪贴板
+pub(crate) fn set_general_pasteboard_images(images: &[Vec<u8>]) -> MacosResult<()> {
+  // SAFETY: NSPasteboardTypePNG 是 AppKit 导出的常量，在进程生命周期内有效
+  let png_type = unsafe { NSPasteboardTypePNG };
+  let mut items = Vec::with_capacity(images.len());
+  for png in images {
+    let item = NSPasteboardItem::new();
+    if !item.setData_forType(&NSData::with_bytes(png), png_type) {
+      return Err("Failed to create pasteboard item".to_string());
+    }
+    items.push(ProtocolObject::<dyn NSPasteboardWriting>::from_retained(
+      item,
+    ));
+  }
+
+  let pasteboard = NSPasteboard::generalPasteboard();
+  pasteboard.clearContents();
+  if pasteboard.writeObjects(&NSArray::from_retained_slice(&items)) {
+    Ok(())
+  } else {
+    Err("Failed to write images to pasteboard".to_string())
+  }
+}
@@ -0,0 +1,252 @@
+use encoding_rs::{Encoding, BIG5, EUC_KR, GBK, SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+/// 内容不作为文本显示的目标组，`{\*...}` 形式的可忽略目标组同样跳过
+const SKIPPED_DESTINATIONS: [&str; 18] = [
+  "colortbl",
+  "datastore",
+  "fldinst",
+  "fonttbl",
+  "footer",
+  "generator",
+  "header",
+  "info",
+  "latentstyles",
+  "listoverridetable",
+  "listtable",
+  "object",
+  "pict",
+  "revtbl",
+  "rsidtbl",
+  "stylesheet",
+  "themedata",
+  "xmlnstbl",
+];
+
+/// 根据 `\ansicpg` 声明的代码页获取编码，未知代码页按 Windows-1252 处理
+fn code_page_encoding(code_page: i32) -> &'static Encoding {
+  match code_page {
+    932 => SHIFT_JIS,
+    936 => GBK,
+    949 => EUC_KR,
+    950 => BIG5,
+    65001 => UTF_8,
+    _ => Encoding::for_label(format!("windows-{code_page}").as_bytes()).unwrap_or(WINDOWS_1252),
+  }
+}
+
+/// 控制字对应的文本，不产生文本的控制字返回 None
+fn control_word_text(word: &str) -> Option<&'static str> {
+  match word {
+    "par" | "line" | "row" | "sect" | "page" => Some("\n"),
+    "tab" | "cell" => Some("\t"),
+    "emdash" => Some("\u{2014}"),
+    "endash" => Some("\u{2013}"),
+    "emspace" | "enspace" | "qmspace" => Some(" "),
+    "bullet" => Some("\u{2022}"),
+    "lquote" => Some("\u{2018}"),
+    "rquote" => Some("\u{2019}"),
+    "ldblquote" => Some("\u{201c}"),
+    "rdblquote" => Some("\u{201d}"),
+    _ => None,
+  }
+}
+
+/// 当前组的解析状态，进入组时保存，离开组时恢复
+#[derive(Clone, Copy)]
+struct GroupState {
+  /// 是否处于不显示的目标组中
+  skip: bool,
+  /// `\uc` 声明的 `\u` 之后替代字符的数量
+  unicode_skip: usize,
+}
+
+/// 提取 RTF 的纯文本内容：跳过字体表、图片等不显示的目标组，
+/// 按 `\ansicpg` 代码页解码 `\'hh` 字节，解析 `\u` Unicode 字符并跳过其替代字符
+pub(crate) fn rtf_to_text(rtf: &str) -> String {
+  let bytes = rtf.as_bytes();
+  let mut text = String::with_capacity(rtf.len() / 2);
+  let mut encoding = WINDOWS_1252;
+  // 连续的 `\'hh` 字节可能组成一个多字节字符，累积后一起解码
+  let mut pending = Vec::new();
+  let mut high_surrogate: Option<u16> = None;
+  let mut state = GroupState {
+    skip: false,
+    unicode_skip: 1,
+  };
+  let mut stack = Vec::new();
+  // `\u` 之后尚需跳过的替代字符数量
+  let mut fallback = 0usize;
+  let mut pos = 0;
+
+  let flush = |text: &mut String, pending: &mut Vec<u8>, encoding: &'static Encoding| {
+    if !pending.is_empty() {
+      text.push_str(&encoding.decode_without_bom_handling(pending).0);
+      pending.clear();
+    }
+  };
+
+  while pos < bytes.len() {
+    match bytes[pos] {
+      b'{' => {
+        flush(&mut text, &mut pending, encoding);
+        stack.push(state);
+        fallback = 0;
+        pos += 1;
+        if rtf[pos..].starts_with("\\*") {
+          state.skip = true;
+        }
+      }
+      b'}' => {
+        flush(&mut text, &mut pending, encoding);
+        state = stack.pop().unwrap_or(state);
+        fallback = 0;
+        pos += 1;
+      }
+      b'\\' if bytes.get(pos + 1) == Some(&b'\'') => {
+        // 输入在两位十六进制数之前结束时丢弃不完整的字节
+        let hex_len = bytes[pos + 2..]
+          .iter()
+          .take(2)
+          .take_while(|b| b.is_ascii_hexdigit())
+          .count();
+        let byte = (hex_len == 2)
+          .then(|| u8::from_str_radix(&rtf[pos + 2..pos + 4], 16).ok())
+          .flatten();
+        pos += 2 + hex_len;
+        if fallback > 0 {
+          fallback -= 1;
+        } else if let (Some(byte), false) = (byte, state.skip) {
+          pending.push(byte);
+        }
+      }
+      b'\\' if bytes.get(pos + 1).is_some_and(u8::is_ascii_alphabetic) => {
+        flush(&mut text, &mut pending, encoding);
+        let start = pos + 1;
+        let word_end = rtf[start..]
+          .find(|c: char| !c.is_ascii_alphabetic())
+          .map_or(rtf.len(), |len| start + len);
+        let param_end = rtf[word_end..]
+          .char_indices()
+          .find(|(i, c)| !(c.is_ascii_digit() || (*i == 0 && *c == '-')))
+          .map_or(rtf.len(), |(len, _)| word_end + len);
+        let word = &rtf[start..word_end];
+        let param = rtf[word_end..param_end].parse::<i32>().ok();
+        // 控制字之后的一个空格是分隔符，不属于文本
+        pos = if bytes.get(param_end) == Some(&b' ') {
+          param_end + 1
+        } else {
+          param_end
+        };
+
+        if fallback > 0 {
+          fallback -= 1;
+          continue;
+        }
+        match word {
+          "u" => {
+            let unit = param.map_or(0, |value| value as u16);
+            fallback = state.unicode_skip;
+            if state.skip {
+              continue;
+            }
+            match (high_surrogate.take(), unit) {
+              (_, 0xd800..=0xdbff) => high_surrogate = Some(unit),
+              (Some(high), 0xdc00..=0xdfff) => {
+                text.extend(char::decode_utf16([high, unit]).filter_map(|ch| ch.ok()));
+              }
+              _ => text.extend(char::from_u32(u32::from(unit))),
+            }
+          }
+          "uc" => state.unicode_skip = param.unwrap_or(1).max(0) as usize,
+          "ansicpg" => encoding = code_page_encoding(param.unwrap_or(1252)),
+          _ if SKIPPED_DESTINATIONS.contains(&word) => state.skip = true,
+          _ => {
+            if let (Some(value), false) = (control_word_text(word), state.skip) {
+              text.push_str(value);
+            }
+          }
+        }
+      }
+      b'\\' => {
+        flush(&mut text, &mut pending, encoding);
+        let symbol = bytes.get(pos + 1).copied();
+        pos += if symbol.is_some_and(|c| c.is_ascii()) {
+          2
+        } else {
+          1
+        };
+        if fallback > 0 {
+          fallback -= 1;
+          continue;
+        }
+        if state.skip {
+          continue;
+        }
+        match symbol {
+          Some(c @ (b'\\' | b'{' | b'}')) => text.push(char::from(c)),
+          Some(b'~') => text.push('\u{a0}'),
+          Some(b'_') => text.push('-'),
+          Some(b'\n' | b'\r') => text.push('\n'),
+          _ => {}
+        }
+      }
+      b'\r' | b'\n' => pos += 1,
+      _ => {
+        flush(&mut text, &mut pending, encoding);
+        let Some(ch) = rtf[pos..].chars().next() else {
+          break;
+        };
+        pos += ch.len_utf8();
+        if fallback > 0 {
+          fallback -= 1;
+        } else if !state.skip {
+          text.push(ch);
+        }
+      }
+    }
+  }
+  flush(&mut text, &mut pending, encoding);
+  text
+}
+
+#[cfg(test)]
+mod tests {
+  use super::rtf_to_text;
+
+  #[test]
+  fn decodes_hex_bytes_with_the_declared_code_page() {
+    assert_eq!(
+      rtf_to_text(r"{\rtf1\ansi\ansicpg936 \'c4\'e3\'ba\'c3}"),
+      "你好"
+    );
+    assert_eq!(rtf_to_text(r"{\rtf1\ansi caf\'e9}"), "café");
+  }
+
+  #[test]
+  fn skips_unicode_fallback_characters() {
+    assert_eq!(rtf_to_text(r"{\rtf1\u20320?\u22909?}"), "你好");
+    assert_eq!(rtf_to_text(r"{\rtf1\uc0\u20320\u22909 !}"), "你好!");
+    assert_eq!(rtf_to_text(r"{\rtf1\uc2\u20320\'3f\'3f!}"), "你!");
+    // \uc 只在声明它的组内生效
+    assert_eq!(rtf_to_text(r"{\rtf1{\uc2\u20320??}\u22909?}"), "你好");
+  }
+
+  #[test]
+  fn combines_surrogate_pairs() {
+    assert_eq!(rtf_to_text(r"{\rtf1\u-10179?\u-8704?}"), "\u{1f600}");
+  }
+
+  #[test]
+  fn skips_ignorable_and_hidden_destinations() {
+    let rtf = r"{\rtf1{\fonttbl{\f0 Arial;}}{\*\generator Riched20;}{\*\unknown ignored}Hello\par}";
+    assert_eq!(rtf_to_text(rtf), "Hello\n");
+  }
+
+  #[test]
+  fn tolerates_truncated_hex_escapes() {
+    assert_eq!(rtf_to_text(r"{\rtf1 abc\'"), "abc");
+    assert_eq!(rtf_to_text(r"{\rtf1 abc\'4"), "abc");
+    assert_eq!(rtf_to_text(r"{\rtf1 abc\'4x"), "abcx");
+    assert_eq!(rtf_to_text("{\\rtf1 abc\\'4中"), "abc中");
+  }
+}
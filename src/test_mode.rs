@@ -0,0 +1,88 @@
+use crate::ClipboardData;
+use std::sync::{Mutex, MutexGuard};
+
+/// 测试模式下代替系统剪贴板的内存快照
+#[derive(Default)]
+pub(crate) struct Snapshot {
+  pub text: Option<String>,
+  pub html: Option<String>,
+  pub rtf: Option<String>,
+  /// 图片的编码数据（PNG 等）
+  pub image: Option<Vec<u8>>,
+  pub files: Option<Vec<String>>,
+}
+
+impl Snapshot {
+  pub(crate) fn from_data(data: ClipboardData) -> Self {
+    Snapshot {
+      text: data.text,
+      html: data.html,
+      rtf: data.rtf,
+      image: data.image.map(|image| image.data.to_vec()),
+      files: data.files,
+    }
+  }
+
+  /// 快照中存在的格式名称
+  pub(crate) fn formats(&self) -> Vec<String> {
+    [
+      ("text", self.text.is_some()),
+      ("rtf", self.rtf.is_some()),
+      ("html", self.html.is_some()),
+      ("image", self.image.is_some()),
+      ("files", self.files.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, present)| *present)
+    .map(|(format, _)| format.to_string())
+    .collect()
+  }
+
+  /// 检查快照是否包含指定的规范格式
+  pub(crate) fn has(&self, format: &str) -> bool {
+    self.formats().iter().any(|name| name == format)
+  }
+
+  /// 转换为 ClipboardData，图片需要由调用方解码后填入
+  pub(crate) fn to_data(&self) -> ClipboardData {
+    ClipboardData {
+      available_formats: self.formats(),
+      text: self.text.clone(),
+      rtf: self.rtf.clone(),
+      html: self.html.clone(),
+      files: self.files.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+static SNAPSHOT: Mutex<Option<Snapshot>> = Mutex::new(None);
+
+fn state() -> MutexGuard<'static, Option<Snapshot>> {
+  SNAPSHOT
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 启用（传入快照）或关闭（传入 None）测试模式
+pub(crate) fn set(snapshot: Option<Snapshot>) {
+  *state() = snapshot;
+}
+
+pub(crate) fn is_enabled() -> bool {
+  state().is_some()
+}
+
+/// 在测试模式下读取快照，未启用测试模式时返回 None
+pub(crate) fn read<T>(read: impl FnOnce(&Snapshot) -> T) -> Option<T> {
+  state().as_ref().map(read)
+}
+
+/// 在测试模式下用新内容替换快照，与写入系统剪贴板一样会清除原有的所有格式
+/// 未启用测试模式时不做任何操作
+pub(crate) fn replace(snapshot: Snapshot) {
+  let mut state = state();
+  if state.is_some() {
+    *state = Some(snapshot);
+  }
+}
@@ -1,7 +1,10 @@
-use crate::{ClipboardData, ImageData};
+use crate::{
+  encode_rust_image, lock, ClipboardData, ImageData, ImageEncoding, ImageWatchEmitter,
+  ListenerDispatcher, OrderedContent,
+};
 use clipboard_rs::common::{RustImage, RustImageData};
 use napi::bindgen_prelude::Buffer;
-use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use sha2::{Digest, Sha256};
 use std::io::Read;
 use std::sync::mpsc;
 use std::thread;
@@ -268,7 +271,11 @@ fn wayland_copy_error_detail(err: &CopyError) -> String {
   err.to_string()
 }
 
+// 以下读写剪贴板的基础函数都持有进程级剪贴板锁，与其他平台一样串行访问剪贴板；
+// 锁可重入，读取多个格式的函数在外层持有锁，使各次读取不会被其他线程的写入打断
+
 fn get_wayland_mime_types_ordered() -> WaylandResult<Vec<String>> {
+  let _lock = lock::acquire();
   paste::get_mime_types_ordered(PasteClipboardType::Regular, PasteSeat::Unspecified).map_err(|e| {
     format!(
       "Failed to query MIME types: {}",
@@ -278,6 +285,7 @@ fn get_wayland_mime_types_ordered() -> WaylandResult<Vec<String>> {
 }
 
 fn get_wayland_mime_types_ordered_or_empty() -> WaylandResult<Vec<String>> {
+  let _lock = lock::acquire();
   match paste::get_mime_types_ordered(PasteClipboardType::Regular, PasteSeat::Unspecified) {
     Ok(mimes) => Ok(mimes),
     Err(PasteError::ClipboardEmpty) | Err(PasteError::NoSeats) => Ok(Vec::new()),
@@ -291,6 +299,7 @@ fn get_wayland_mime_types_ordered_or_empty() -> WaylandResult<Vec<String>> {
 fn get_wayland_contents_bytes(
   requested_mime: PasteMimeType<'_>,
 ) -> WaylandResult<(Vec<u8>, String)> {
+  let _lock = lock::acquire();
   let (mut pipe, actual_mime) = paste::get_contents(
     PasteClipboardType::Regular,
     PasteSeat::Unspecified,
@@ -357,7 +366,10 @@ fn read_wayland_textual_content(
   ))
 }
 
-fn read_wayland_image_content(offered_mimes: &[String]) -> Option<ImageData> {
+fn read_wayland_image_content(
+  offered_mimes: &[String],
+  encoding: ImageEncoding,
+) -> Option<ImageData> {
   let selected_mime =
     find_wayland_mime(offered_mimes, WAYLAND_IMAGE_MIME_PRIORITY).or_else(|| {
       offered_mimes
@@ -366,7 +378,7 @@ fn read_wayland_image_content(offered_mimes: &[String]) -> Option<ImageData> {
         .map(|mime| mime.as_str())
     })?;
   let (payload, _) = get_wayland_contents_bytes(PasteMimeType::Specific(selected_mime)).ok()?;
-  Some(to_wayland_image_data(payload))
+  Some(to_wayland_image_data(payload, encoding))
 }
 
 fn read_wayland_files_content(offered_mimes: &[String]) -> Option<Vec<String>> {
@@ -381,7 +393,10 @@ fn read_wayland_files_content(offered_mimes: &[String]) -> Option<Vec<String>> {
   decode_wayland_files(&payload)
 }
 
-fn read_wayland_complete_data_from_mimes(offered_mimes: &[String]) -> ClipboardData {
+fn read_wayland_complete_data_from_mimes(
+  offered_mimes: &[String],
+  encoding: ImageEncoding,
+) -> ClipboardData {
   let mut available_formats = infer_wayland_available_formats(offered_mimes);
   extend_wayland_formats_with_custom_mimes(&mut available_formats, offered_mimes);
 
@@ -419,7 +434,7 @@ fn read_wayland_complete_data_from_mimes(offered_mimes: &[String]) -> ClipboardD
   };
 
   let image = if has_wayland_format(&available_formats, "image") {
-    read_wayland_image_content(offered_mimes)
+    read_wayland_image_content(offered_mimes, encoding)
   } else {
     None
   };
@@ -437,23 +452,21 @@ fn read_wayland_complete_data_from_mimes(offered_mimes: &[String]) -> ClipboardD
     html,
     image,
     files,
+    ..Default::default()
   }
 }
 
-fn to_wayland_image_data(payload: Vec<u8>) -> ImageData {
+fn to_wayland_image_data(payload: Vec<u8>, encoding: ImageEncoding) -> ImageData {
   match RustImageData::from_bytes(&payload) {
     Ok(image_data) => {
       let (width, height) = image_data.get_size();
-      match image_data.to_png() {
-        Ok(png_data) => {
-          let bytes = png_data.get_bytes();
-          ImageData {
-            width,
-            height,
-            size: bytes.len() as u32,
-            data: Buffer::from(bytes.to_vec()),
-          }
-        }
+      match encode_rust_image(&image_data, encoding, "Failed to get image") {
+        Ok(bytes) => ImageData {
+          width,
+          height,
+          size: bytes.len() as u32,
+          data: Buffer::from(bytes),
+        },
         Err(_) => ImageData {
           width,
           height,
@@ -509,6 +522,7 @@ fn append_wayland_rtf_sources(target: &mut Vec<CopyMimeSource>, rtf: &str) {
 }
 
 fn wayland_copy_single(source: Vec<u8>, mime_type: CopyMimeType) -> WaylandResult<()> {
+  let _lock = lock::acquire();
   let options = CopyOptions::new();
   options
     .copy(CopySource::Bytes(source.into_boxed_slice()), mime_type)
@@ -521,6 +535,7 @@ fn wayland_copy_single(source: Vec<u8>, mime_type: CopyMimeType) -> WaylandResul
 }
 
 fn wayland_copy_multi(sources: Vec<CopyMimeSource>) -> WaylandResult<()> {
+  let _lock = lock::acquire();
   let options = CopyOptions::new();
   options.copy_multi(sources).map_err(|e| {
     format!(
@@ -540,11 +555,17 @@ pub(crate) fn set_text(text: String) -> WaylandResult<()> {
 }
 
 pub(crate) fn get_html() -> WaylandResult<String> {
+  let payload = get_html_bytes()?;
+  String::from_utf8(payload).map_err(|e| format!("Clipboard HTML is not valid UTF-8: {e}"))
+}
+
+/// 读取 HTML 的原始字节，不做编码转换
+pub(crate) fn get_html_bytes() -> WaylandResult<Vec<u8>> {
   let offered_mimes = get_wayland_mime_types_ordered()?;
   let selected_mime = find_wayland_mime(&offered_mimes, &["text/html"])
     .ok_or_else(|| "Clipboard does not contain HTML data".to_string())?;
   let (payload, _) = get_wayland_contents_bytes(PasteMimeType::Specific(selected_mime))?;
-  String::from_utf8(payload).map_err(|e| format!("Clipboard HTML is not valid UTF-8: {e}"))
+  Ok(payload)
 }
 
 pub(crate) fn set_html(html: String) -> WaylandResult<()> {
@@ -647,44 +668,132 @@ pub(crate) fn get_buffer(format: String) -> WaylandResult<Vec<u8>> {
   Ok(payload)
 }
 
+/// 按来源应用提供的顺序读取每个 MIME 类型的原始字节，读取失败的类型会被跳过
+pub(crate) fn get_raw_contents() -> WaylandResult<Vec<(String, Vec<u8>)>> {
+  let _lock = lock::acquire();
+  let offered_mimes = get_wayland_mime_types_ordered_or_empty()?;
+  Ok(
+    offered_mimes
+      .into_iter()
+      .filter_map(|mime| {
+        let (payload, _) = get_wayland_contents_bytes(PasteMimeType::Specific(&mime)).ok()?;
+        Some((mime, payload))
+      })
+      .collect(),
+  )
+}
+
+/// 返回剪贴板提供的所有 MIME 类型以及能否读取到非空数据
+pub(crate) fn format_readability() -> WaylandResult<Vec<(String, bool)>> {
+  let _lock = lock::acquire();
+  let offered_mimes = get_wayland_mime_types_ordered_or_empty()?;
+  Ok(
+    offered_mimes
+      .into_iter()
+      .map(|mime| {
+        let readable = get_wayland_contents_bytes(PasteMimeType::Specific(&mime))
+          .is_ok_and(|(payload, _)| !payload.is_empty());
+        (mime, readable)
+      })
+      .collect(),
+  )
+}
+
+pub(crate) fn peek_buffer(format: String, max_bytes: usize) -> WaylandResult<Vec<u8>> {
+  let _lock = lock::acquire();
+  let (pipe, _) = paste::get_contents(
+    PasteClipboardType::Regular,
+    PasteSeat::Unspecified,
+    PasteMimeType::Specific(&format),
+  )
+  .map_err(|e| {
+    format!(
+      "Failed to read clipboard content: {}",
+      wayland_paste_error_detail(&e)
+    )
+  })?;
+
+  // 只读取前 max_bytes 字节，剩余数据随管道关闭丢弃
+  let mut payload = Vec::new();
+  pipe
+    .take(max_bytes as u64)
+    .read_to_end(&mut payload)
+    .map_err(|e| format!("Failed to read clipboard stream: {e}"))?;
+  Ok(payload)
+}
+
 pub(crate) fn set_contents(contents: ClipboardData) -> WaylandResult<()> {
-  let mut sources = Vec::new();
+  set_contents_with_extra(contents, Vec::new())
+}
+
+pub(crate) fn set_contents_with_extra(
+  contents: ClipboardData,
+  extra: Vec<(String, Vec<u8>)>,
+) -> WaylandResult<()> {
+  let mut entries = Vec::new();
 
   if let Some(text) = contents.text {
-    sources.push(CopyMimeSource {
-      source: CopySource::Bytes(text.into_bytes().into_boxed_slice()),
-      mime_type: CopyMimeType::Text,
-    });
+    entries.push(OrderedContent::Text(text));
   }
 
   if let Some(html) = contents.html {
-    sources.push(CopyMimeSource {
-      source: CopySource::Bytes(html.into_bytes().into_boxed_slice()),
-      mime_type: CopyMimeType::Specific("text/html".to_string()),
-    });
+    entries.push(OrderedContent::Html(html));
   }
 
   if let Some(rtf) = contents.rtf {
-    append_wayland_rtf_sources(&mut sources, &rtf);
+    entries.push(OrderedContent::Rtf(rtf));
   }
 
   if let Some(image_data) = contents.image {
-    let mime_type = match detect_wayland_image_magic(image_data.data.as_ref()) {
-      Some("png") => CopyMimeType::Specific("image/png".to_string()),
-      Some("jpeg") => CopyMimeType::Specific("image/jpeg".to_string()),
-      Some("gif") => CopyMimeType::Specific("image/gif".to_string()),
-      Some("bmp") => CopyMimeType::Specific("image/bmp".to_string()),
-      Some("webp") => CopyMimeType::Specific("image/webp".to_string()),
-      _ => CopyMimeType::Autodetect,
-    };
-    sources.push(CopyMimeSource {
-      source: CopySource::Bytes(image_data.data.to_vec().into_boxed_slice()),
-      mime_type,
-    });
+    entries.push(OrderedContent::Image(image_data.data.to_vec()));
   }
 
   if let Some(files) = contents.files {
-    append_wayland_file_sources(&mut sources, &files);
+    entries.push(OrderedContent::Files(files));
+  }
+
+  for (format, data) in extra {
+    entries.push(OrderedContent::Other(format, data));
+  }
+
+  set_ordered_contents(entries)
+}
+
+/// 按给定顺序提供各格式的数据，粘贴方通常优先使用先提供的 MIME 类型
+pub(crate) fn set_ordered_contents(entries: Vec<OrderedContent>) -> WaylandResult<()> {
+  let mut sources = Vec::new();
+
+  for entry in entries {
+    match entry {
+      OrderedContent::Text(text) => sources.push(CopyMimeSource {
+        source: CopySource::Bytes(text.into_bytes().into_boxed_slice()),
+        mime_type: CopyMimeType::Text,
+      }),
+      OrderedContent::Html(html) => sources.push(CopyMimeSource {
+        source: CopySource::Bytes(html.into_bytes().into_boxed_slice()),
+        mime_type: CopyMimeType::Specific("text/html".to_string()),
+      }),
+      OrderedContent::Rtf(rtf) => append_wayland_rtf_sources(&mut sources, &rtf),
+      OrderedContent::Image(data) => {
+        let mime_type = match detect_wayland_image_magic(&data) {
+          Some("png") => CopyMimeType::Specific("image/png".to_string()),
+          Some("jpeg") => CopyMimeType::Specific("image/jpeg".to_string()),
+          Some("gif") => CopyMimeType::Specific("image/gif".to_string()),
+          Some("bmp") => CopyMimeType::Specific("image/bmp".to_string()),
+          Some("webp") => CopyMimeType::Specific("image/webp".to_string()),
+          _ => CopyMimeType::Autodetect,
+        };
+        sources.push(CopyMimeSource {
+          source: CopySource::Bytes(data.into_boxed_slice()),
+          mime_type,
+        });
+      }
+      OrderedContent::Files(files) => append_wayland_file_sources(&mut sources, &files),
+      OrderedContent::Other(format, data) => sources.push(CopyMimeSource {
+        source: CopySource::Bytes(data.into_boxed_slice()),
+        mime_type: CopyMimeType::Specific(format),
+      }),
+    }
   }
 
   if sources.is_empty() {
@@ -707,7 +816,27 @@ pub(crate) fn get_available_formats() -> WaylandResult<Vec<String>> {
   Ok(formats)
 }
 
+pub(crate) fn get_primary_format() -> WaylandResult<Option<String>> {
+  let offered_mimes = get_wayland_mime_types_ordered_or_empty()?;
+  Ok(offered_mimes.first().map(|mime| {
+    if is_wayland_text_mime(mime) {
+      "text".to_string()
+    } else if is_wayland_rtf_mime(mime) {
+      "rtf".to_string()
+    } else if is_wayland_html_mime(mime) {
+      "html".to_string()
+    } else if is_wayland_image_mime(mime) {
+      "image".to_string()
+    } else if is_wayland_files_mime(mime) {
+      "files".to_string()
+    } else {
+      mime.clone()
+    }
+  }))
+}
+
 pub(crate) fn clear() -> WaylandResult<()> {
+  let _lock = lock::acquire();
   copy::clear(CopyClipboardType::Regular, CopySeat::All).map_err(|e| {
     format!(
       "Failed to clear clipboard: {}",
@@ -717,8 +846,12 @@ pub(crate) fn clear() -> WaylandResult<()> {
 }
 
 pub(crate) fn get_full_clipboard_data() -> WaylandResult<ClipboardData> {
+  let _lock = lock::acquire();
   let offered_mimes = get_wayland_mime_types_ordered_or_empty()?;
-  Ok(read_wayland_complete_data_from_mimes(&offered_mimes))
+  Ok(read_wayland_complete_data_from_mimes(
+    &offered_mimes,
+    ImageEncoding::Png,
+  ))
 }
 
 fn wayland_context_to_clipboard_data(message: ClipBoardListenMessage) -> ClipboardData {
@@ -860,6 +993,7 @@ fn wayland_context_to_clipboard_data(message: ClipBoardListenMessage) -> Clipboa
     html,
     image,
     files,
+    ..Default::default()
   }
 }
 
@@ -888,9 +1022,64 @@ fn merge_wayland_clipboard_data(
   primary
 }
 
-pub(crate) fn start_wayland_watch(
-  tsfn: ThreadsafeFunction<ClipboardData, (), ClipboardData, napi::Status, false>,
-) -> mpsc::Sender<()> {
+/// PRIMARY 选区轮询间隔
+const PRIMARY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// 读取 PRIMARY 选区的文本，选区为空或合成器不支持时返回 None
+fn get_primary_text() -> Option<String> {
+  let (mut pipe, _) = paste::get_contents(
+    PasteClipboardType::Primary,
+    PasteSeat::Unspecified,
+    PasteMimeType::Text,
+  )
+  .ok()?;
+  let mut payload = Vec::new();
+  pipe.read_to_end(&mut payload).ok()?;
+  Some(String::from_utf8_lossy(&payload).into_owned())
+}
+
+/// 轮询 PRIMARY 选区文本，变化时分发事件，返回的发送端用于停止监听
+/// 需要合成器支持 ext-data-control 或 wlr-data-control v2 及以上
+pub(crate) fn start_wayland_primary_watch(dispatcher: ListenerDispatcher) -> mpsc::Sender<()> {
+  let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+  thread::spawn(move || {
+    wayland_log!("watch_wayland_primary thread started");
+    let mut last_text = get_primary_text();
+
+    loop {
+      match stop_rx.recv_timeout(PRIMARY_POLL_INTERVAL) {
+        Err(mpsc::RecvTimeoutError::Timeout) => {}
+        _ => break,
+      }
+
+      let text = get_primary_text();
+      if text == last_text {
+        continue;
+      }
+      last_text = text.clone();
+
+      let available_formats = if text.is_some() {
+        vec!["text".to_string()]
+      } else {
+        Vec::new()
+      };
+      let status = dispatcher.dispatch(ClipboardData {
+        available_formats,
+        text,
+        selection: Some("primary".to_string()),
+        ..Default::default()
+      });
+      wayland_log!("watch_wayland_primary callback dispatched: status={status:?}");
+    }
+
+    wayland_log!("watch_wayland_primary loop exited");
+  });
+
+  stop_tx
+}
+
+pub(crate) fn start_wayland_watch(dispatcher: ListenerDispatcher) -> mpsc::Sender<()> {
   let (stop_tx, stop_rx) = mpsc::channel::<()>();
 
   thread::spawn(move || {
@@ -952,7 +1141,8 @@ pub(crate) fn start_wayland_watch(
             payload_len
           );
 
-          let complete_data = read_wayland_complete_data_from_mimes(&offered_mimes);
+          let complete_data =
+            read_wayland_complete_data_from_mimes(&offered_mimes, dispatcher.image_encoding());
           let fallback_data = wayland_context_to_clipboard_data(message);
           let clipboard_data = merge_wayland_clipboard_data(complete_data, fallback_data);
           wayland_log!(
@@ -966,7 +1156,7 @@ pub(crate) fn start_wayland_watch(
             clipboard_data.files.is_some()
           );
 
-          let status = tsfn.call(clipboard_data, ThreadsafeFunctionCallMode::NonBlocking);
+          let status = dispatcher.dispatch(clipboard_data);
           if status == napi::Status::Ok {
             wayland_log!(
               "watch_wayland callback dispatched for event #{}",
@@ -993,3 +1183,58 @@ pub(crate) fn start_wayland_watch(
 
   stop_tx
 }
+
+/// 只监听图片的 Wayland 监听器：监听流只请求图片 MIME 类型，
+/// 来源没有提供图片时不读取其他内容
+pub(crate) fn start_wayland_image_watch(mut emitter: ImageWatchEmitter) -> mpsc::Sender<()> {
+  let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+  thread::spawn(move || {
+    wayland_log!("watch_wayland_images thread started");
+
+    let mut stream = match WlClipboardPasteStream::init(WlListenType::ListenOnCopy) {
+      Ok(stream) => stream,
+      Err(e) => {
+        wayland_log!(
+          "watch_wayland_images stream init failed: {}",
+          wayland_error_detail(&e)
+        );
+        return;
+      }
+    };
+    stream.set_priority(vec![
+      "image/png".into(),
+      "image/jpeg".into(),
+      "image/webp".into(),
+      "image/bmp".into(),
+      "image/gif".into(),
+      "application/x-qt-image".into(),
+    ]);
+
+    for context_result in stream.paste_stream() {
+      if stop_rx.try_recv().is_ok() {
+        wayland_log!("watch_wayland_images received stop signal");
+        break;
+      }
+
+      match context_result {
+        Ok(message) if is_wayland_image_mime(&message.context.mime_type) => {
+          let payload = message.context.context;
+          let digest = Sha256::digest(&payload).to_vec();
+          emitter.emit(digest, || image::load_from_memory(&payload).ok());
+        }
+        Ok(_) => emitter.reset(),
+        Err(e) => {
+          wayland_log!(
+            "watch_wayland_images stream yielded error: {}",
+            wayland_error_detail(&e)
+          );
+        }
+      }
+    }
+
+    wayland_log!("watch_wayland_images loop exited");
+  });
+
+  stop_tx
+}
@@ -0,0 +1,211 @@
+use crate::{encode_rust_image, html, lock, ClipboardData, ImageData, ImageEncoding};
+use clipboard_rs::common::{RustImage, RustImageData};
+use clipboard_win::{formats, Clipboard};
+use encoding_rs::{Encoding, WINDOWS_1252};
+use napi::bindgen_prelude::Buffer;
+
+type WindowsResult<T> = std::result::Result<T, String>;
+
+const CF_TEXT: u32 = 1;
+const CF_DIB: u32 = 8;
+const CF_UNICODETEXT: u32 = 13;
+const CF_HDROP: u32 = 15;
+
+/// 读取指定的已注册格式（如 `HTML Format`）的原始字节
+fn get_registered_format(name: &str) -> Option<Vec<u8>> {
+  let format_id = clipboard_win::register_format(name)?.get();
+  if !clipboard_win::is_format_avail(format_id) {
+    return None;
+  }
+  clipboard_win::get(formats::RawData(format_id)).ok()
+}
+
+/// 读取 CF_DIB 图片并按指定编码方式输出
+fn read_image(encoding: ImageEncoding) -> Option<ImageData> {
+  let bitmap: Vec<u8> = clipboard_win::get(formats::Bitmap).ok()?;
+  let image_data = RustImageData::from_bytes(&bitmap).ok()?;
+  let (width, height) = image_data.get_size();
+  let bytes = encode_rust_image(&image_data, encoding, "Failed to get image").ok()?;
+  Some(ImageData {
+    width,
+    height,
+    size: bytes.len() as u32,
+    data: Buffer::from(bytes),
+  })
+}
+
+/// 在一次打开的剪贴板会话中读取所有指定的标准格式
+/// 整个读取过程只调用一次 OpenClipboard/CloseClipboard，缩短其他应用被阻塞的时间
+pub(crate) fn read_session(
+  requested_formats: &[&str],
+  encoding: ImageEncoding,
+) -> WindowsResult<ClipboardData> {
+  let _lock = lock::acquire();
+  let _clipboard =
+    Clipboard::new_attempts(10).map_err(|e| format!("Failed to open clipboard: {e}"))?;
+
+  let mut data = ClipboardData::default();
+  for format in requested_formats {
+    let found = match *format {
+      "text" if clipboard_win::is_format_avail(CF_UNICODETEXT) => {
+        data.text = clipboard_win::get(formats::Unicode).ok();
+        data.text.is_some()
+      }
+      "rtf" => {
+        data.rtf = get_registered_format("Rich Text Format").map(|raw| {
+          String::from_utf8_lossy(&raw)
+            .trim_end_matches('\0')
+            .to_string()
+        });
+        data.rtf.is_some()
+      }
+      "html" => {
+        data.html = get_registered_format("HTML Format").map(|raw| html::decode_html(&raw));
+        data.html.is_some()
+      }
+      "image" if clipboard_win::is_format_avail(CF_DIB) => {
+        data.image = read_image(encoding);
+        data.image.is_some()
+      }
+      "files" if clipboard_win::is_format_avail(CF_HDROP) => {
+        data.files = clipboard_win::get(formats::FileList).ok();
+        data.files.is_some()
+      }
+      _ => false,
+    };
+
+    if found {
+      data.available_formats.push(format.to_string());
+    }
+  }
+
+  Ok(data)
+}
+
+/// 基于句柄（GDI 对象或图元文件）的格式，无法作为内存块读取字节
+const HANDLE_FORMATS: [u32; 7] = [2, 3, 9, 14, 0x82, 0x83, 0x8E];
+
+/// 在一次剪贴板会话中按枚举顺序读取所有格式的原始字节，返回格式名称与数据
+/// 标准格式使用 `CF_DIB` 这样的名称，没有名称的格式使用 `#<id>`，基于句柄的格式和读取失败的格式会被跳过
+pub(crate) fn read_raw_formats() -> WindowsResult<Vec<(String, Vec<u8>)>> {
+  let _lock = lock::acquire();
+  let _clipboard =
+    Clipboard::new_attempts(10).map_err(|e| format!("Failed to open clipboard: {e}"))?;
+
+  let mut entries = Vec::new();
+  for format_id in clipboard_win::raw::EnumFormats::new() {
+    if HANDLE_FORMATS.contains(&format_id) {
+      continue;
+    }
+    let mut data = Vec::new();
+    if clipboard_win::raw::get_vec(format_id, &mut data).is_err() {
+      continue;
+    }
+    let name = clipboard_win::raw::format_name_big(format_id).unwrap_or(format!("#{format_id}"));
+    entries.push((name, data));
+  }
+  Ok(entries)
+}
+
+/// 枚举剪贴板中的所有格式，返回格式名称以及能否以非空字节读取
+/// 基于句柄的格式不尝试读取，视为无法读取
+pub(crate) fn format_readability() -> WindowsResult<Vec<(String, bool)>> {
+  let _lock = lock::acquire();
+  let _clipboard =
+    Clipboard::new_attempts(10).map_err(|e| format!("Failed to open clipboard: {e}"))?;
+
+  let mut formats = Vec::new();
+  for format_id in clipboard_win::raw::EnumFormats::new() {
+    let name = clipboard_win::raw::format_name_big(format_id).unwrap_or(format!("#{format_id}"));
+    let mut data = Vec::new();
+    let readable = !HANDLE_FORMATS.contains(&format_id)
+      && clipboard_win::raw::get_vec(format_id, &mut data).is_ok()
+      && !data.is_empty();
+    formats.push((name, readable));
+  }
+  Ok(formats)
+}
+
+#[link(name = "user32")]
+extern "system" {
+  fn GetClipboardOwner() -> *mut std::ffi::c_void;
+  fn GetForegroundWindow() -> *mut std::ffi::c_void;
+  fn GetWindowThreadProcessId(hwnd: *mut std::ffi::c_void, process_id: *mut u32) -> u32;
+}
+
+/// 获取窗口所属进程的 ID，无法获取时返回 None
+fn window_process_id(hwnd: *mut std::ffi::c_void) -> Option<u32> {
+  if hwnd.is_null() {
+    return None;
+  }
+  let mut process_id = 0u32;
+  // SAFETY: hwnd 非空，process_id 指向有效的局部变量；窗口已销毁时函数返回 0 而不会访问无效内存
+  unsafe { GetWindowThreadProcessId(hwnd, &mut process_id) };
+  (process_id != 0).then_some(process_id)
+}
+
+/// 判断剪贴板变化的来源：剪贴板所有者与前台窗口属于同一进程时为 user，否则为 programmatic
+/// 写入方未关联窗口（OpenClipboard(NULL)）或没有前台窗口时为 unknown
+pub(crate) fn change_origin() -> &'static str {
+  // SAFETY: 两个函数都没有参数，只返回窗口句柄
+  let (owner, foreground) = unsafe { (GetClipboardOwner(), GetForegroundWindow()) };
+  match (window_process_id(owner), window_process_id(foreground)) {
+    (Some(owner), Some(foreground)) if owner == foreground => "user",
+    (Some(_), Some(_)) => "programmatic",
+    _ => "unknown",
+  }
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+  fn GetACP() -> u32;
+}
+
+/// 获取系统 ANSI 代码页对应的编码，无法识别时使用 Windows-1252
+fn ansi_encoding() -> &'static Encoding {
+  // SAFETY: GetACP 没有参数且不会失败
+  let code_page = unsafe { GetACP() };
+  let label = match code_page {
+    65001 => "utf-8".to_string(),
+    936 => "gbk".to_string(),
+    950 => "big5".to_string(),
+    932 => "shift_jis".to_string(),
+    949 => "euc-kr".to_string(),
+    _ => format!("windows-{code_page}"),
+  };
+  Encoding::for_label(label.as_bytes()).unwrap_or(WINDOWS_1252)
+}
+
+/// 将文本编码为以空字符结尾的 ANSI 字节，当前代码页无法表示的字符替换为 `?`
+fn encode_ansi(text: &str) -> Vec<u8> {
+  let encoding = ansi_encoding();
+  let mut bytes = Vec::with_capacity(text.len() + 1);
+  let mut buffer = [0u8; 4];
+  for ch in text.chars() {
+    let (encoded, _, had_errors) = encoding.encode(ch.encode_utf8(&mut buffer));
+    if had_errors {
+      bytes.push(b'?');
+    } else {
+      bytes.extend_from_slice(&encoded);
+    }
+  }
+  bytes.push(0);
+  bytes
+}
+
+/// 在一次剪贴板会话中同时写入 CF_UNICODETEXT（UTF-16LE）与 CF_TEXT（系统 ANSI 代码页）文本
+/// 两种格式都以空字符结尾，不依赖系统从 Unicode 文本自动合成 ANSI 文本
+pub(crate) fn set_text_wide(text: &str) -> WindowsResult<()> {
+  let mut unicode: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+  unicode.extend_from_slice(&[0, 0]);
+  let ansi = encode_ansi(text);
+
+  let _lock = lock::acquire();
+  let _clipboard =
+    Clipboard::new_attempts(10).map_err(|e| format!("Failed to open clipboard: {e}"))?;
+  clipboard_win::raw::empty().map_err(|e| format!("Failed to empty clipboard: {e}"))?;
+  clipboard_win::raw::set_without_clear(CF_UNICODETEXT, &unicode)
+    .map_err(|e| format!("Failed to set CF_UNICODETEXT: {e}"))?;
+  clipboard_win::raw::set_without_clear(CF_TEXT, &ansi)
+    .map_err(|e| format!("Failed to set CF_TEXT: {e}"))
+}
@@ -0,0 +1,280 @@
+use crate::{ClipboardData, ListenerDispatcher};
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use x11rb::connection::Connection;
+use x11rb::protocol::xfixes::{ConnectionExt as _, SelectionEventMask};
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, CreateWindowAux, Window, WindowClass};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+type X11Result<T> = std::result::Result<T, String>;
+
+/// 向上查找父窗口的最大层数
+const MAX_PARENT_DEPTH: usize = 8;
+
+fn intern_atom(conn: &RustConnection, name: &[u8]) -> Option<u32> {
+  Some(conn.intern_atom(false, name).ok()?.reply().ok()?.atom)
+}
+
+/// 读取窗口属性的原始数据，属性不存在或为空时返回 None
+fn read_property(
+  conn: &RustConnection,
+  window: Window,
+  property: u32,
+  kind: u32,
+) -> Option<Vec<u8>> {
+  let reply = conn
+    .get_property(false, window, property, kind, 0, 1024)
+    .ok()?
+    .reply()
+    .ok()?;
+  if reply.value.is_empty() {
+    None
+  } else {
+    Some(reply.value)
+  }
+}
+
+/// 读取窗口标题，优先使用 UTF-8 编码的 `_NET_WM_NAME`，其次使用 `WM_NAME`
+fn read_window_title(conn: &RustConnection, window: Window) -> Option<String> {
+  let net_wm_name = intern_atom(conn, b"_NET_WM_NAME");
+  let utf8_string = intern_atom(conn, b"UTF8_STRING");
+  let title = net_wm_name
+    .zip(utf8_string)
+    .and_then(|(property, kind)| read_property(conn, window, property, kind))
+    .or_else(|| read_property(conn, window, AtomEnum::WM_NAME.into(), AtomEnum::ANY.into()))?;
+
+  let title = String::from_utf8_lossy(&title).trim().to_string();
+  if title.is_empty() {
+    None
+  } else {
+    Some(title)
+  }
+}
+
+/// 读取窗口的 `WM_CLIENT_LEADER`，工具包通常将剪贴板交给隐藏的辅助窗口，其主窗口即为 client leader
+fn read_client_leader(conn: &RustConnection, window: Window) -> Option<Window> {
+  let property = intern_atom(conn, b"WM_CLIENT_LEADER")?;
+  let value = read_property(conn, window, property, AtomEnum::WINDOW.into())?;
+  let leader = u32::from_ne_bytes(value.get(0..4)?.try_into().ok()?);
+  (leader != x11rb::NONE && leader != window).then_some(leader)
+}
+
+/// 获取 CLIPBOARD 选区所有者窗口的标题
+/// 依次尝试所有者窗口、其 client leader 以及父窗口，没有所有者或无法读取标题时返回 None
+pub(crate) fn clipboard_owner_title() -> X11Result<Option<String>> {
+  let (conn, _) =
+    x11rb::connect(None).map_err(|e| format!("Failed to connect to X11 server: {e}"))?;
+  let Some(clipboard) = intern_atom(&conn, b"CLIPBOARD") else {
+    return Ok(None);
+  };
+  let owner = conn
+    .get_selection_owner(clipboard)
+    .map_err(|e| format!("Failed to query selection owner: {e}"))?
+    .reply()
+    .map_err(|e| format!("Failed to query selection owner: {e}"))?
+    .owner;
+  if owner == x11rb::NONE {
+    return Ok(None);
+  }
+
+  if let Some(title) = read_window_title(&conn, owner) {
+    return Ok(Some(title));
+  }
+  if let Some(title) =
+    read_client_leader(&conn, owner).and_then(|leader| read_window_title(&conn, leader))
+  {
+    return Ok(Some(title));
+  }
+
+  let mut window = owner;
+  for _ in 0..MAX_PARENT_DEPTH {
+    let Some(tree) = conn
+      .query_tree(window)
+      .ok()
+      .and_then(|cookie| cookie.reply().ok())
+    else {
+      break;
+    };
+    if tree.parent == x11rb::NONE || tree.parent == tree.root {
+      break;
+    }
+    window = tree.parent;
+    if let Some(title) = read_window_title(&conn, window) {
+      return Ok(Some(title));
+    }
+  }
+
+  Ok(None)
+}
+
+/// 读取窗口的 `_NET_WM_PID`，窗口未设置时尝试其 client leader
+fn read_window_pid(conn: &RustConnection, window: Window) -> Option<u32> {
+  let property = intern_atom(conn, b"_NET_WM_PID")?;
+  let read_pid = |window: Window| {
+    let value = read_property(conn, window, property, AtomEnum::CARDINAL.into())?;
+    Some(u32::from_ne_bytes(value.get(0..4)?.try_into().ok()?))
+  };
+  read_pid(window).or_else(|| read_pid(read_client_leader(conn, window)?))
+}
+
+/// 判断 CLIPBOARD 变化的来源：选区所有者与 `_NET_ACTIVE_WINDOW` 属于同一进程时为 user，否则为 programmatic
+/// 无法确定所有者或活动窗口的进程时为 unknown
+pub(crate) fn clipboard_change_origin() -> &'static str {
+  let Ok((conn, screen_num)) = x11rb::connect(None) else {
+    return "unknown";
+  };
+  let root = conn.setup().roots[screen_num].root;
+  let owner = intern_atom(&conn, b"CLIPBOARD")
+    .and_then(|clipboard| conn.get_selection_owner(clipboard).ok()?.reply().ok())
+    .map(|reply| reply.owner)
+    .filter(|owner| *owner != x11rb::NONE);
+  let active = intern_atom(&conn, b"_NET_ACTIVE_WINDOW")
+    .and_then(|property| read_property(&conn, root, property, AtomEnum::WINDOW.into()))
+    .and_then(|value| Some(u32::from_ne_bytes(value.get(0..4)?.try_into().ok()?)))
+    .filter(|active| *active != x11rb::NONE);
+
+  let owner_pid = owner.and_then(|owner| read_window_pid(&conn, owner));
+  let active_pid = active.and_then(|active| read_window_pid(&conn, active));
+  match (owner_pid, active_pid) {
+    (Some(owner), Some(active)) if owner == active => "user",
+    (Some(_), Some(_)) => "programmatic",
+    _ => "unknown",
+  }
+}
+
+/// 等待 SelectionNotify 事件的超时时间
+const SELECTION_CONVERT_TIMEOUT: Duration = Duration::from_millis(500);
+/// 监听线程检查事件与停止信号的间隔
+const PRIMARY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 请求将选区转换为 UTF-8 文本并读取结果，所有者不支持或超时时返回 None
+/// 等待期间收到的其他事件（例如新的选区变化通知）放入 deferred，由调用方随后处理
+fn read_selection_text(
+  conn: &RustConnection,
+  window: Window,
+  selection: u32,
+  target: u32,
+  property: u32,
+  deferred: &mut VecDeque<Event>,
+) -> Option<String> {
+  conn
+    .convert_selection(window, selection, target, property, x11rb::CURRENT_TIME)
+    .ok()?;
+  conn.flush().ok()?;
+
+  let started = Instant::now();
+  let notify = loop {
+    match conn.poll_for_event().ok()? {
+      Some(Event::SelectionNotify(notify)) if notify.selection == selection => break notify,
+      Some(event) => deferred.push_back(event),
+      None if started.elapsed() >= SELECTION_CONVERT_TIMEOUT => return None,
+      None => thread::sleep(Duration::from_millis(5)),
+    }
+  };
+  if notify.property == x11rb::NONE {
+    return None;
+  }
+
+  let reply = conn
+    .get_property(true, window, property, AtomEnum::ANY, 0, u32::MAX / 4)
+    .ok()?
+    .reply()
+    .ok()?;
+  // 大于单次传输上限的数据使用 INCR 协议分段传输，这里不处理
+  if reply.type_ != target && reply.type_ != u32::from(AtomEnum::STRING) {
+    return None;
+  }
+  Some(String::from_utf8_lossy(&reply.value).into_owned())
+}
+
+/// 使用 XFixes 扩展监听 PRIMARY 选区变化，每次变化读取选中的文本并分发事件
+/// 返回的发送端用于停止监听
+pub(crate) fn start_primary_watch(dispatcher: ListenerDispatcher) -> X11Result<mpsc::Sender<()>> {
+  let (conn, screen_num) =
+    x11rb::connect(None).map_err(|e| format!("Failed to connect to X11 server: {e}"))?;
+  let root = conn.setup().roots[screen_num].root;
+  let window = conn
+    .generate_id()
+    .map_err(|e| format!("Failed to allocate X11 window id: {e}"))?;
+  conn
+    .create_window(
+      x11rb::COPY_DEPTH_FROM_PARENT,
+      window,
+      root,
+      0,
+      0,
+      1,
+      1,
+      0,
+      WindowClass::INPUT_OUTPUT,
+      x11rb::COPY_FROM_PARENT,
+      &CreateWindowAux::new(),
+    )
+    .map_err(|e| format!("Failed to create X11 window: {e}"))?;
+  conn
+    .xfixes_query_version(5, 0)
+    .map_err(|e| format!("XFixes extension is unavailable: {e}"))?
+    .reply()
+    .map_err(|e| format!("XFixes extension is unavailable: {e}"))?;
+  conn
+    .xfixes_select_selection_input(
+      window,
+      AtomEnum::PRIMARY.into(),
+      SelectionEventMask::SET_SELECTION_OWNER,
+    )
+    .map_err(|e| format!("Failed to watch PRIMARY selection: {e}"))?;
+  conn
+    .flush()
+    .map_err(|e| format!("Failed to watch PRIMARY selection: {e}"))?;
+
+  let utf8_string = intern_atom(&conn, b"UTF8_STRING").unwrap_or(AtomEnum::STRING.into());
+  let property = intern_atom(&conn, b"CLIPBOARD_RS_PRIMARY").unwrap_or(AtomEnum::PRIMARY.into());
+  let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+  thread::spawn(move || {
+    let mut deferred = VecDeque::new();
+    loop {
+      if !matches!(stop_rx.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+        let _ = conn.destroy_window(window);
+        let _ = conn.flush();
+        return;
+      }
+
+      let event = match deferred.pop_front() {
+        Some(event) => Ok(Some(event)),
+        None => conn.poll_for_event(),
+      };
+      match event {
+        Ok(Some(Event::XfixesSelectionNotify(_))) => {
+          let text = read_selection_text(
+            &conn,
+            window,
+            AtomEnum::PRIMARY.into(),
+            utf8_string,
+            property,
+            &mut deferred,
+          );
+          let available_formats = if text.is_some() {
+            vec!["text".to_string()]
+          } else {
+            Vec::new()
+          };
+          dispatcher.dispatch(ClipboardData {
+            available_formats,
+            text,
+            selection: Some("primary".to_string()),
+            ..Default::default()
+          });
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => thread::sleep(PRIMARY_POLL_INTERVAL),
+        Err(_) => return,
+      }
+    }
+  });
+
+  Ok(stop_tx)
+}